@@ -1,6 +1,7 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
 use heed::EnvOpenOptions;
-use scoped_heed::{Scope, ScopedBytesDatabase, ScopedBytesKeyDatabase, ScopedDatabase};
+use scoped_heed::{GlobalScopeRegistry, Scope, scoped_database_options};
+use std::sync::Arc;
 use tempfile::TempDir;
 
 fn benchmark_generic_database(c: &mut Criterion) {
@@ -13,7 +14,14 @@ fn benchmark_generic_database(c: &mut Criterion) {
             .unwrap()
     };
 
-    let db: ScopedDatabase<Vec<u8>, String> = ScopedDatabase::new(&env, "bench_generic").unwrap();
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .types::<Vec<u8>, String>()
+        .name("bench_generic")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
     let key = b"test_key_12345".to_vec();
     let value = "test_value".to_string();
 
@@ -53,8 +61,14 @@ fn benchmark_bytes_database(c: &mut Criterion) {
             .unwrap()
     };
 
-    let db: ScopedBytesKeyDatabase<String> =
-        ScopedBytesKeyDatabase::new(&env, "bench_bytes").unwrap();
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .bytes_keys::<String>()
+        .name("bench_bytes")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
     let key = b"test_key_12345";
     let value = "test_value".to_string();
 
@@ -143,7 +157,14 @@ fn benchmark_fully_optimized_bytes(c: &mut Criterion) {
             .unwrap()
     };
 
-    let db = ScopedBytesDatabase::new(&env, "bench_pure").unwrap();
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .raw_bytes()
+        .name("bench_pure")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
     let key = b"test_key_12345";
     let value = b"test_value";
 
@@ -173,11 +194,110 @@ fn benchmark_fully_optimized_bytes(c: &mut Criterion) {
     });
 }
 
+fn benchmark_generic_clear(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(100 * 1024 * 1024)
+            .max_dbs(3)
+            .open(dir.path())
+            .unwrap()
+    };
+
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .types::<String, String>()
+        .name("bench_clear")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
+
+    let scope1 = Scope::named("scope1").unwrap();
+    let value = "test_value".to_string();
+
+    // Each iteration clears a freshly filled scope of 10k entries
+    c.bench_function("generic_db_clear_10k", |b| {
+        b.iter_batched(
+            || {
+                let mut wtxn = env.write_txn().unwrap();
+                for i in 0..10_000 {
+                    db.put(&mut wtxn, &scope1, &format!("key_{i:05}"), &value)
+                        .unwrap();
+                }
+                wtxn.commit().unwrap();
+            },
+            |()| {
+                let mut wtxn = env.write_txn().unwrap();
+                black_box(db.clear(&mut wtxn, &scope1).unwrap());
+                wtxn.commit().unwrap();
+            },
+            BatchSize::PerIteration,
+        );
+    });
+}
+
+fn benchmark_counters(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(5)
+            .open(dir.path())
+            .unwrap()
+    };
+
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let bincode_db = scoped_database_options(&env, registry.clone())
+        .bytes_keys::<u64>()
+        .name("bench_bincode_counters")
+        .create(&mut wtxn)
+        .unwrap();
+    let u64_db = scoped_database_options(&env, registry)
+        .u64_values()
+        .name("bench_u64_counters")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
+
+    let scope1 = Scope::named("scope1").unwrap();
+    let keys: Vec<Vec<u8>> = (0..100)
+        .map(|i| format!("counter_{i:03}").into_bytes())
+        .collect();
+
+    // 1000 increments per transaction; the transaction is aborted to keep sizes stable
+    c.bench_function("bincode_u64_increment_1k", |b| {
+        b.iter(|| {
+            let mut wtxn = env.write_txn().unwrap();
+            for key in keys.iter().cycle().take(1000) {
+                let current = bincode_db.get(&wtxn, &scope1, key).unwrap().unwrap_or(0);
+                bincode_db
+                    .put(&mut wtxn, &scope1, key, &(current + 1))
+                    .unwrap();
+            }
+            wtxn.abort();
+        });
+    });
+
+    c.bench_function("u64_values_increment_1k", |b| {
+        b.iter(|| {
+            let mut wtxn = env.write_txn().unwrap();
+            for key in keys.iter().cycle().take(1000) {
+                black_box(u64_db.increment(&mut wtxn, &scope1, key, 1).unwrap());
+            }
+            wtxn.abort();
+        });
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_generic_database,
     benchmark_bytes_database,
     benchmark_fully_optimized_bytes,
-    benchmark_key_encoding_only
+    benchmark_key_encoding_only,
+    benchmark_generic_clear,
+    benchmark_counters
 );
 criterion_main!(benches);
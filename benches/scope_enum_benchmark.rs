@@ -1,6 +1,7 @@
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use heed::EnvOpenOptions;
-use scoped_heed::{Scope, ScopedBytesDatabase, ScopedDatabase};
+use scoped_heed::{GlobalScopeRegistry, Scope, scoped_database_options};
+use std::sync::Arc;
 use tempfile::TempDir;
 
 fn benchmark_scope_creation(c: &mut Criterion) {
@@ -29,7 +30,14 @@ fn benchmark_scope_enum_vs_option_str(c: &mut Criterion) {
             .unwrap()
     };
 
-    let db: ScopedDatabase<Vec<u8>, String> = ScopedDatabase::new(&env, "bench_enum").unwrap();
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .types::<Vec<u8>, String>()
+        .name("bench_enum")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
     let key = b"test_key_12345".to_vec();
     let value = "test_value".to_string();
 
@@ -94,7 +102,14 @@ fn benchmark_scope_enum_with_bytes_db(c: &mut Criterion) {
             .unwrap()
     };
 
-    let db = ScopedBytesDatabase::new(&env, "bench_bytes_enum").unwrap();
+    let mut wtxn = env.write_txn().unwrap();
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn).unwrap());
+    let db = scoped_database_options(&env, registry)
+        .raw_bytes()
+        .name("bench_bytes_enum")
+        .create(&mut wtxn)
+        .unwrap();
+    wtxn.commit().unwrap();
     let key = b"test_key_12345";
     let value = b"test_value";
 
@@ -34,7 +34,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut wtxn = env.write_txn()?;
     let generic_db = scoped_database_options(&env, registry.clone())
-        .types::<Vec<u8>, Document>()
+        .types::<[u8; 6], Document>()
         .name("generic_docs")
         .create(&mut wtxn)?;
     wtxn.commit()?;
@@ -75,7 +75,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Add to generic DB
         let start = Instant::now();
         for i in 0..100 {
-            let key = format!("doc{:03}", i).into_bytes();
+            let key: [u8; 6] = *format!("doc{:03}", i).as_bytes().first_chunk().unwrap();
             generic_db.put(&mut wtxn, &tenant_a, &key, &doc1)?;
             generic_db.put(&mut wtxn, &tenant_b, &key, &doc2)?;
         }
@@ -143,7 +143,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let count = bytes_db.range(&rtxn, &tenant_a, &range)?.count();
         let bytes_range_time = start.elapsed();
 
-        let range_vec = b"doc010".to_vec()..=b"doc019".to_vec();
+        // Generic range queries need an order-preserving key type such as [u8; N]
+        let range_vec = *b"doc010"..=*b"doc019";
         let start = Instant::now();
         let count_gen = generic_db.range(&rtxn, &tenant_a, &range_vec)?.count();
         let generic_range_time = start.elapsed();
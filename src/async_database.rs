@@ -0,0 +1,225 @@
+//! Async wrapper for use inside tokio services.
+//!
+//! Available with the `tokio` feature. LMDB calls are synchronous and can block for a
+//! long time, e.g. while waiting for the write lock or for disk I/O on commit. Running
+//! them directly on an async executor stalls every other task scheduled on that thread.
+//! `AsyncScopedDatabase` moves each operation onto tokio's blocking thread pool with
+//! `spawn_blocking` instead.
+
+use heed::{Env, RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::panic;
+use std::sync::Arc;
+use tokio::task::{self, JoinError};
+
+use crate::{Scope, ScopedBytesDatabase, ScopedBytesKeyDatabase, ScopedDatabase, ScopedDbError};
+
+/// A scoped database whose operations run on tokio's blocking thread pool.
+///
+/// Each call clones the `Env` and the `Arc` holding the database, opens its own
+/// transaction inside `spawn_blocking`, and commits it before returning. Keys, values
+/// and scopes are therefore taken by value so they can be moved to the blocking thread.
+/// Use `read` and `write` to run several operations in one transaction.
+///
+/// This wrapper does not add any concurrency. LMDB allows a single write transaction
+/// per environment at a time, so concurrent writes through this wrapper queue up on
+/// blocking threads waiting for the write lock, exactly like synchronous writers would.
+/// Reads run in parallel as usual. The only benefit is that the async executor keeps
+/// serving other tasks while LMDB blocks.
+///
+/// A panic inside the operation is propagated to the awaiting task. If the runtime
+/// shuts down before the operation ran, an `Interrupted` I/O error is returned.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{AsyncScopedDatabase, Scope, ScopedDbError};
+/// # async fn demo() -> Result<(), ScopedDbError> {
+/// let users = AsyncScopedDatabase::new(&env, users_db);
+/// let tenant = Scope::named("tenant1")?;
+///
+/// users
+///     .put(tenant.clone(), "alice".to_string(), "admin".to_string())
+///     .await?;
+/// let role = users.get(tenant, "alice".to_string()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncScopedDatabase<D> {
+    env: Env,
+    db: Arc<D>,
+}
+
+impl<D> Clone for AsyncScopedDatabase<D> {
+    fn clone(&self) -> Self {
+        Self {
+            env: self.env.clone(),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+impl<D> AsyncScopedDatabase<D>
+where
+    D: Send + Sync + 'static,
+{
+    /// Wrap a database created in `env`
+    pub fn new(env: &Env, db: D) -> Self {
+        Self::from_arc(env, Arc::new(db))
+    }
+
+    /// Wrap a database that is already shared through an `Arc`
+    pub fn from_arc(env: &Env, db: Arc<D>) -> Self {
+        Self {
+            env: env.clone(),
+            db,
+        }
+    }
+
+    /// The underlying synchronous database
+    pub fn inner(&self) -> &Arc<D> {
+        &self.db
+    }
+
+    /// The LMDB environment the database belongs to
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Run `f` with a read transaction on the blocking thread pool.
+    pub async fn read<T, F>(&self, f: F) -> Result<T, ScopedDbError>
+    where
+        F: FnOnce(&D, &RoTxn) -> Result<T, ScopedDbError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let env = self.env.clone();
+        let db = Arc::clone(&self.db);
+        let handle = task::spawn_blocking(move || {
+            let rtxn = env.read_txn()?;
+            f(&db, &rtxn)
+        });
+        handle.await.unwrap_or_else(|e| Err(join_error(e)))
+    }
+
+    /// Run `f` with a write transaction on the blocking thread pool.
+    ///
+    /// The transaction is committed if `f` succeeds and aborted otherwise.
+    pub async fn write<T, F>(&self, f: F) -> Result<T, ScopedDbError>
+    where
+        F: FnOnce(&D, &mut RwTxn) -> Result<T, ScopedDbError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let env = self.env.clone();
+        let db = Arc::clone(&self.db);
+        let handle = task::spawn_blocking(move || {
+            let mut wtxn = env.write_txn()?;
+            let result = f(&db, &mut wtxn)?;
+            wtxn.commit()?;
+            Ok(result)
+        });
+        handle.await.unwrap_or_else(|e| Err(join_error(e)))
+    }
+}
+
+impl<K, V> AsyncScopedDatabase<ScopedDatabase<K, V>>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + Send + Sync + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+    /// Get a value. See `ScopedDatabase::get`.
+    pub async fn get(&self, scope: Scope, key: K) -> Result<Option<V>, ScopedDbError> {
+        self.read(move |db, txn| db.get(txn, &scope, &key)).await
+    }
+
+    /// Insert a key-value pair. See `ScopedDatabase::put`.
+    pub async fn put(&self, scope: Scope, key: K, value: V) -> Result<(), ScopedDbError> {
+        self.write(move |db, txn| db.put(txn, &scope, &key, &value))
+            .await
+    }
+
+    /// Delete a key-value pair. See `ScopedDatabase::delete`.
+    pub async fn delete(&self, scope: Scope, key: K) -> Result<bool, ScopedDbError> {
+        self.write(move |db, txn| db.delete(txn, &scope, &key))
+            .await
+    }
+
+    /// Clear a scope, returning the number of entries removed. See `ScopedDatabase::clear`.
+    pub async fn clear(&self, scope: Scope) -> Result<usize, ScopedDbError> {
+        self.write(move |db, txn| db.clear(txn, &scope)).await
+    }
+}
+
+impl<V> AsyncScopedDatabase<ScopedBytesKeyDatabase<V>>
+where
+    V: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+    /// Get a value. See `ScopedBytesKeyDatabase::get`.
+    pub async fn get(&self, scope: Scope, key: Vec<u8>) -> Result<Option<V>, ScopedDbError> {
+        self.read(move |db, txn| db.get(txn, &scope, &key)).await
+    }
+
+    /// Insert a key-value pair. See `ScopedBytesKeyDatabase::put`.
+    pub async fn put(&self, scope: Scope, key: Vec<u8>, value: V) -> Result<(), ScopedDbError> {
+        self.write(move |db, txn| db.put(txn, &scope, &key, &value))
+            .await
+    }
+
+    /// Delete a key-value pair. See `ScopedBytesKeyDatabase::delete`.
+    pub async fn delete(&self, scope: Scope, key: Vec<u8>) -> Result<bool, ScopedDbError> {
+        self.write(move |db, txn| db.delete(txn, &scope, &key))
+            .await
+    }
+
+    /// Clear a scope, returning the number of entries removed. See
+    /// `ScopedBytesKeyDatabase::clear`.
+    pub async fn clear(&self, scope: Scope) -> Result<usize, ScopedDbError> {
+        self.write(move |db, txn| db.clear(txn, &scope)).await
+    }
+}
+
+impl AsyncScopedDatabase<ScopedBytesDatabase> {
+    /// Get a copy of a value. See `ScopedBytesDatabase::get`.
+    ///
+    /// The value is copied because the transaction ends before the future resolves; use
+    /// `read` to work with the borrowed bytes instead.
+    pub async fn get(&self, scope: Scope, key: Vec<u8>) -> Result<Option<Vec<u8>>, ScopedDbError> {
+        self.read(move |db, txn| Ok(db.get(txn, &scope, &key)?.map(<[u8]>::to_vec)))
+            .await
+    }
+
+    /// Insert a key-value pair. See `ScopedBytesDatabase::put`.
+    pub async fn put(
+        &self,
+        scope: Scope,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), ScopedDbError> {
+        self.write(move |db, txn| db.put(txn, &scope, &key, &value))
+            .await
+    }
+
+    /// Delete a key-value pair. See `ScopedBytesDatabase::delete`.
+    pub async fn delete(&self, scope: Scope, key: Vec<u8>) -> Result<bool, ScopedDbError> {
+        self.write(move |db, txn| db.delete(txn, &scope, &key))
+            .await
+    }
+
+    /// Clear a scope, returning the number of entries removed. See
+    /// `ScopedBytesDatabase::clear`.
+    pub async fn clear(&self, scope: Scope) -> Result<usize, ScopedDbError> {
+        self.write(move |db, txn| db.clear(txn, &scope)).await
+    }
+}
+
+/// Resume a panic of the blocking task, or report that the runtime cancelled it
+fn join_error(error: JoinError) -> ScopedDbError {
+    match error.try_into_panic() {
+        Ok(payload) => panic::resume_unwind(payload),
+        Err(error) => ScopedDbError::Heed(heed::Error::Io(io::Error::new(
+            io::ErrorKind::Interrupted,
+            error,
+        ))),
+    }
+}
@@ -1,7 +1,11 @@
+use crate::observer::OperationObserver;
+use crate::utils::{self, OrderedKey, SortableKey};
 use crate::{
-    GlobalScopeRegistry, ScopedBytesDatabase, ScopedBytesKeyDatabase, ScopedDatabase, ScopedDbError,
+    CounterValue, GlobalScopeRegistry, Scope, ScopedBytesDatabase, ScopedBytesKeyDatabase,
+    ScopedCounterDatabase, ScopedDatabase, ScopedDbError, ScopedMultiBytesDatabase,
+    ScopedOrderedDatabase, ScopedTupleKeyDatabase,
 };
-use heed::{Env, RwTxn};
+use heed::{DatabaseFlags, Env, RwTxn};
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -9,7 +13,7 @@ use std::sync::Arc;
 /// Builder for creating scoped databases with flexible type configurations
 pub struct ScopedDatabaseOptions<'env> {
     env: &'env Env,
-    global_registry: Arc<GlobalScopeRegistry>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
 }
 
 impl<'env> ScopedDatabaseOptions<'env> {
@@ -17,13 +21,24 @@ impl<'env> ScopedDatabaseOptions<'env> {
     pub fn new(env: &'env Env, global_registry: Arc<GlobalScopeRegistry>) -> Self {
         Self {
             env,
-            global_registry,
+            global_registry: Some(global_registry),
+        }
+    }
+
+    /// Create an options builder without a registry
+    ///
+    /// Databases must then be created with `create_with_new_registry`, or a registry
+    /// must be supplied with `with_registry` before calling `create`.
+    pub fn standalone(env: &'env Env) -> Self {
+        Self {
+            env,
+            global_registry: None,
         }
     }
 
-    /// Alias for backward compatibility
-    pub fn with_registry(self, _registry: Arc<GlobalScopeRegistry>) -> Self {
-        // Registry is already provided at construction time
+    /// Set the global registry used by the created database
+    pub fn with_registry(mut self, registry: Arc<GlobalScopeRegistry>) -> Self {
+        self.global_registry = Some(registry);
         self
     }
 
@@ -37,7 +52,15 @@ impl<'env> ScopedDatabaseOptions<'env> {
         TypedOptions {
             env: self.env,
             name: None,
+            initial_scopes: Vec::new(),
+            initial_data: Vec::new(),
             global_registry: self.global_registry,
+            unified: false,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            backfill_registry_on_open: false,
             _phantom: PhantomData,
         }
     }
@@ -51,7 +74,29 @@ impl<'env> ScopedDatabaseOptions<'env> {
         BytesKeysOptions {
             env: self.env,
             name: None,
+            initial_scopes: Vec::new(),
+            initial_data: Vec::new(),
             global_registry: self.global_registry,
+            unified: false,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            backfill_registry_on_open: false,
+            heed_flags: DatabaseFlags::empty(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Configure database with fixed-width compound keys and serialized values
+    /// Keys are encoded with `OrderedKey` so byte order matches numeric order
+    pub fn tuple_keys<K, V>(self) -> TupleKeysOptions<'env, K, V>
+    where
+        K: OrderedKey + 'static,
+        V: Serialize + for<'de> Deserialize<'de> + 'static,
+    {
+        TupleKeysOptions {
+            inner: self.bytes_keys::<V>(),
             _phantom: PhantomData,
         }
     }
@@ -62,8 +107,52 @@ impl<'env> ScopedDatabaseOptions<'env> {
         RawBytesOptions {
             env: self.env,
             name: None,
+            initial_scopes: Vec::new(),
+            initial_data: Vec::new(),
             global_registry: self.global_registry,
             use_unnamed_for_default: false,
+            unified: false,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            backfill_registry_on_open: false,
+            heed_flags: DatabaseFlags::empty(),
+        }
+    }
+
+    /// Configure database with raw byte slice keys and `u64` values
+    /// Values are stored as 8 native-endian bytes without serialization
+    pub fn u64_values(self) -> CounterValuesOptions<'env, u64> {
+        CounterValuesOptions {
+            inner: self.raw_bytes(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Configure database with raw byte slice keys and `i64` values
+    /// Values are stored as 8 native-endian bytes without serialization
+    pub fn i64_values(self) -> CounterValuesOptions<'env, i64> {
+        CounterValuesOptions {
+            inner: self.raw_bytes(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Configure a raw bytes database holding several values per key
+    /// Values under a key form a sorted set backed by LMDB's `MDB_DUPSORT`
+    pub fn multi_bytes(self) -> MultiBytesOptions<'env> {
+        MultiBytesOptions {
+            env: self.env,
+            name: None,
+            initial_scopes: Vec::new(),
+            global_registry: self.global_registry,
+            unified: false,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            backfill_registry_on_open: false,
         }
     }
 }
@@ -72,7 +161,15 @@ impl<'env> ScopedDatabaseOptions<'env> {
 pub struct TypedOptions<'env, K, V> {
     env: &'env Env,
     name: Option<String>,
-    global_registry: Arc<GlobalScopeRegistry>,
+    initial_scopes: Vec<Scope>,
+    initial_data: Vec<(Scope, K, V)>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    backfill_registry_on_open: bool,
     _phantom: PhantomData<(K, V)>,
 }
 
@@ -87,6 +184,113 @@ where
         self
     }
 
+    /// Register a set of scopes in the global registry when the database is created
+    ///
+    /// This makes `list_scopes` complete before any data is written and surfaces
+    /// hash collisions at creation time instead of on first write.
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.initial_scopes.extend_from_slice(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// The pairs are written with `put` in the creation transaction, after the database
+    /// is opened, so named scopes are registered as usual. Calling this several times
+    /// seeds several scopes; later pairs overwrite earlier ones with the same key. This
+    /// is meant for fixtures and reference data built once at startup.
+    pub fn initial_data(mut self, scope: Scope, pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        self.initial_data.extend(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (scope.clone(), key, value)),
+        );
+        self
+    }
+
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// Default-scope keys are prefixed with the reserved `DEFAULT_SCOPE_HASH`, which
+    /// costs 4 bytes per default-scope key but halves the number of named databases
+    /// counted against the environment's `max_dbs`. The data lives in a database named
+    /// `<name>_unified`, so this layout is not compatible with the two-database layout
+    /// (`<name>` and `<name>_scoped`): choose it when the database is first created and
+    /// keep it for the lifetime of the data. Named scopes whose hash equals the reserved
+    /// value are rejected with `ScopedDbError::InvalidInput`.
+    pub fn unified(mut self) -> Self {
+        self.unified = true;
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    ///
+    /// See `OperationObserver`. Without an observer no hooks are invoked.
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// By default writing to a scope registers it, so a mistyped tenant name silently
+    /// creates a new scope. With this flag, `put` and the other writing methods return
+    /// `ScopedDbError::InvalidInput` for unregistered scopes instead. Scopes must then be
+    /// provisioned explicitly, e.g. with `register_scopes` or `register_scope`. Reads
+    /// are unaffected.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// Reject scopes whose hash does not match their name
+    ///
+    /// A `Scope` only carries a name and a hash, and nothing ties it to the registry
+    /// or environment it was built for. Scopes from `Scope::named` or a registry are
+    /// always consistent, but a hand-built `Scope::Named` with a stale or wrong hash
+    /// would silently read and write another scope's prefix. With this flag, every
+    /// operation checks `Scope::is_consistent` first and returns
+    /// `ScopedDbError::InvalidInput` for an inconsistent scope, at the cost of hashing
+    /// the name once per call. Scopes built with a namespaced registry hash their qualified name
+    /// and pass the check.
+    ///
+    /// The check cannot tell which registry a scope came from: the same name hashes the
+    /// same everywhere, so a scope built for one environment is accepted by databases of
+    /// another. Applications juggling several registries should keep scopes next to the
+    /// registry they belong to, e.g. by building them with `GlobalScopeRegistry::scope`
+    /// right before use.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// Reject every operation on the default scope
+    ///
+    /// Databases that only ever hold tenant data have no use for `Scope::Default`, and a
+    /// default-scope write there is a routing bug, e.g. a scope that was never resolved.
+    /// With this flag, `put`, `get`, `delete`, `clear`, iteration and the other methods
+    /// taking a scope return `ScopedDbError::InvalidInput` for `Scope::Default` instead of
+    /// touching the default-scope data, which turns the bug into an immediate error.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.deny_default_scope = true;
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// Data written before the registry existed, or by a writer using another registry,
+    /// is invisible to `list_scopes` and to pruning. With this flag, `create` scans the
+    /// scope hashes present in the data and registers the missing ones. Their original
+    /// names cannot be recovered from the hashes, so they are registered under synthetic
+    /// names, the hash in hexadecimal (e.g. `0x0000002a`), exactly like
+    /// `GlobalScopeRegistry::repair` does. Registering the real scope later replaces the
+    /// synthetic name; until then, databases built with `require_registered_scopes`
+    /// reject writes to the real name. The scan writes to the registry during `create`,
+    /// which is why it is opt-in.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.backfill_registry_on_open = backfill;
+        self
+    }
+
     /// Create the database with the current transaction
     pub fn create(self, txn: &mut RwTxn) -> Result<ScopedDatabase<K, V>, ScopedDbError> {
         let name = self
@@ -94,7 +298,72 @@ where
             .ok_or_else(|| ScopedDbError::InvalidInput("Database name is required".into()))?;
 
         // Always use the global registry (required for scope management)
-        ScopedDatabase::create(self.env, &name, txn, self.global_registry.clone())
+        let registry = required_registry(self.global_registry)?;
+        let db = ScopedDatabase::create(self.env, &name, txn, registry.clone(), self.unified)?
+            .with_observer(self.observer)
+            .with_require_registered_scopes(self.require_registered_scopes)
+            .with_verify_scope_hashes(self.verify_scope_hashes)
+            .with_deny_default_scope(self.deny_default_scope);
+
+        if self.backfill_registry_on_open {
+            registry.backfill_orphan_scopes(txn, &db)?;
+        }
+
+        for scope in &self.initial_scopes {
+            db.register_scope(txn, scope)?;
+        }
+        for (scope, key, value) in &self.initial_data {
+            db.put(txn, scope, key, value)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// This opens and commits its own write transaction, so it must not be called
+    /// while another write transaction is open on the same thread. Any registry set
+    /// on the builder is replaced by the newly created one. Use `create` with a shared
+    /// registry when several databases need to be created together.
+    pub fn create_with_new_registry(
+        mut self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedDatabase<K, V>), ScopedDbError> {
+        let env = self.env;
+        let mut wtxn = env.write_txn()?;
+        let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+        self.global_registry = Some(registry.clone());
+        let db = self.create(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok((registry, db))
+    }
+}
+
+impl<'env, K, V> TypedOptions<'env, K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Encode keys with `SortableKey` so that they sort in their logical order
+    ///
+    /// Creates a `ScopedOrderedDatabase` instead of a `ScopedDatabase`, whose `range`,
+    /// `ceiling` and `floor` are correct for integer and `String` keys. This is an
+    /// on-disk format choice: the data lives in a single database named `<name>_ordered`,
+    /// which cannot be opened as a bincode-keyed database and vice versa, so choose it
+    /// when the database is first created. The format always stores default-scope keys
+    /// under the reserved `DEFAULT_SCOPE_HASH`, so `unified` has no further effect.
+    pub fn ordered_keys(self) -> OrderedKeysOptions<'env, K, V> {
+        OrderedKeysOptions {
+            env: self.env,
+            name: self.name,
+            initial_scopes: self.initial_scopes,
+            initial_data: self.initial_data,
+            global_registry: self.global_registry,
+            observer: self.observer,
+            require_registered_scopes: self.require_registered_scopes,
+            verify_scope_hashes: self.verify_scope_hashes,
+            deny_default_scope: self.deny_default_scope,
+            backfill_registry_on_open: self.backfill_registry_on_open,
+        }
     }
 }
 
@@ -102,7 +371,16 @@ where
 pub struct BytesKeysOptions<'env, V> {
     env: &'env Env,
     name: Option<String>,
-    global_registry: Arc<GlobalScopeRegistry>,
+    initial_scopes: Vec<Scope>,
+    initial_data: Vec<(Scope, Vec<u8>, V)>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    backfill_registry_on_open: bool,
+    heed_flags: DatabaseFlags,
     _phantom: PhantomData<V>,
 }
 
@@ -116,18 +394,456 @@ where
         self
     }
 
+    /// Register a set of scopes in the global registry when the database is created
+    ///
+    /// This makes `list_scopes` complete before any data is written and surfaces
+    /// hash collisions at creation time instead of on first write.
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.initial_scopes.extend_from_slice(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// The pairs are written with `put` in the creation transaction, after the database
+    /// is opened, so named scopes are registered as usual. Calling this several times
+    /// seeds several scopes; later pairs overwrite earlier ones with the same key. This
+    /// is meant for fixtures and reference data built once at startup.
+    pub fn initial_data(
+        mut self,
+        scope: Scope,
+        pairs: impl IntoIterator<Item = (Vec<u8>, V)>,
+    ) -> Self {
+        self.initial_data.extend(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (scope.clone(), key, value)),
+        );
+        self
+    }
+
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// Default-scope keys are prefixed with the reserved `DEFAULT_SCOPE_HASH`, which
+    /// costs 4 bytes per default-scope key but halves the number of named databases
+    /// counted against the environment's `max_dbs`. The data lives in a database named
+    /// `<name>_unified`, so this layout is not compatible with the two-database layout
+    /// (`<name>` and `<name>_scoped`): choose it when the database is first created and
+    /// keep it for the lifetime of the data. Named scopes whose hash equals the reserved
+    /// value are rejected with `ScopedDbError::InvalidInput`.
+    pub fn unified(mut self) -> Self {
+        self.unified = true;
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    ///
+    /// See `OperationObserver`. Without an observer no hooks are invoked.
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.deny_default_scope = true;
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.backfill_registry_on_open = backfill;
+        self
+    }
+
+    /// Set heed database flags on the default-scope database
+    ///
+    /// See `RawBytesOptions::heed_flags`.
+    pub fn heed_flags(mut self, flags: DatabaseFlags) -> Self {
+        self.heed_flags = flags;
+        self
+    }
+
     /// Create the database with the current transaction
     pub fn create(self, txn: &mut RwTxn) -> Result<ScopedBytesKeyDatabase<V>, ScopedDbError> {
         let name = self
             .name
             .ok_or_else(|| ScopedDbError::InvalidInput("Database name is required".into()))?;
 
-        crate::scoped_bytes_key_database::ScopedBytesKeyDatabase::create(
+        check_default_db_flags(self.heed_flags, self.unified)?;
+
+        let registry = required_registry(self.global_registry)?;
+        let db = crate::scoped_bytes_key_database::ScopedBytesKeyDatabase::create(
             self.env,
             &name,
             txn,
-            self.global_registry.clone(),
-        )
+            registry.clone(),
+            self.unified,
+            self.heed_flags,
+        )?
+        .with_observer(self.observer)
+        .with_require_registered_scopes(self.require_registered_scopes)
+        .with_verify_scope_hashes(self.verify_scope_hashes)
+        .with_deny_default_scope(self.deny_default_scope);
+
+        if self.backfill_registry_on_open {
+            registry.backfill_orphan_scopes(txn, &db)?;
+        }
+
+        for scope in &self.initial_scopes {
+            db.register_scope(txn, scope)?;
+        }
+        for (scope, key, value) in &self.initial_data {
+            db.put(txn, scope, key, value)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// This opens and commits its own write transaction, so it must not be called
+    /// while another write transaction is open on the same thread. Any registry set
+    /// on the builder is replaced by the newly created one. Use `create` with a shared
+    /// registry when several databases need to be created together.
+    pub fn create_with_new_registry(
+        mut self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedBytesKeyDatabase<V>), ScopedDbError> {
+        let env = self.env;
+        let mut wtxn = env.write_txn()?;
+        let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+        self.global_registry = Some(registry.clone());
+        let db = self.create(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok((registry, db))
+    }
+}
+
+/// Options for databases with order-preserving compound keys and serialized values
+pub struct TupleKeysOptions<'env, K, V> {
+    inner: BytesKeysOptions<'env, V>,
+    _phantom: PhantomData<K>,
+}
+
+impl<K, V> TupleKeysOptions<'_, K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Set the database name
+    pub fn name(mut self, name: &str) -> Self {
+        self.inner = self.inner.name(name);
+        self
+    }
+
+    /// Register a set of scopes in the global registry when the database is created
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.inner = self.inner.register_scopes(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// See `BytesKeysOptions::initial_data`.
+    pub fn initial_data(mut self, scope: Scope, pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, value)| (utils::encode_tuple(&key), value));
+        self.inner = self.inner.initial_data(scope, pairs);
+        self
+    }
+
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// See `BytesKeysOptions::unified`.
+    pub fn unified(mut self) -> Self {
+        self.inner = self.inner.unified();
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.inner = self.inner.observer(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.inner = self.inner.require_registered_scopes(require);
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.inner = self.inner.verify_scope_hashes(verify);
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.inner = self.inner.deny_default_scope();
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.inner = self.inner.backfill_registry_on_open(backfill);
+        self
+    }
+
+    /// Create the database with the current transaction
+    pub fn create(self, txn: &mut RwTxn) -> Result<ScopedTupleKeyDatabase<K, V>, ScopedDbError> {
+        self.inner.create(txn).map(ScopedTupleKeyDatabase::new)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// See `BytesKeysOptions::create_with_new_registry`.
+    pub fn create_with_new_registry(
+        self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedTupleKeyDatabase<K, V>), ScopedDbError> {
+        let (registry, db) = self.inner.create_with_new_registry()?;
+        Ok((registry, ScopedTupleKeyDatabase::new(db)))
+    }
+}
+
+/// Options for databases with raw byte keys and integer counter values
+pub struct CounterValuesOptions<'env, N> {
+    inner: RawBytesOptions<'env>,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: CounterValue> CounterValuesOptions<'_, N> {
+    /// Set the database name
+    pub fn name(mut self, name: &str) -> Self {
+        self.inner = self.inner.name(name);
+        self
+    }
+
+    /// Register a set of scopes in the global registry when the database is created
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.inner = self.inner.register_scopes(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// See `RawBytesOptions::initial_data`.
+    pub fn initial_data(
+        mut self,
+        scope: Scope,
+        pairs: impl IntoIterator<Item = (Vec<u8>, N)>,
+    ) -> Self {
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, value)| (key, value.to_bytes().to_vec()));
+        self.inner = self.inner.initial_data(scope, pairs);
+        self
+    }
+
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// See `RawBytesOptions::unified`.
+    pub fn unified(mut self) -> Self {
+        self.inner = self.inner.unified();
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.inner = self.inner.observer(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.inner = self.inner.require_registered_scopes(require);
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.inner = self.inner.verify_scope_hashes(verify);
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.inner = self.inner.deny_default_scope();
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.inner = self.inner.backfill_registry_on_open(backfill);
+        self
+    }
+
+    /// Set heed database flags on the default-scope database
+    ///
+    /// See `RawBytesOptions::heed_flags`.
+    pub fn heed_flags(mut self, flags: DatabaseFlags) -> Self {
+        self.inner = self.inner.heed_flags(flags);
+        self
+    }
+
+    /// Create the database with the current transaction
+    pub fn create(self, txn: &mut RwTxn) -> Result<ScopedCounterDatabase<N>, ScopedDbError> {
+        self.inner.create(txn).map(ScopedCounterDatabase::new)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// See `RawBytesOptions::create_with_new_registry`.
+    pub fn create_with_new_registry(
+        self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedCounterDatabase<N>), ScopedDbError> {
+        let (registry, db) = self.inner.create_with_new_registry()?;
+        Ok((registry, ScopedCounterDatabase::new(db)))
+    }
+}
+
+/// Options for databases with order-preserving keys of any width and serialized values
+pub struct OrderedKeysOptions<'env, K, V> {
+    env: &'env Env,
+    name: Option<String>,
+    initial_scopes: Vec<Scope>,
+    initial_data: Vec<(Scope, K, V)>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    backfill_registry_on_open: bool,
+}
+
+impl<K, V> OrderedKeysOptions<'_, K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Set the database name
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Register a set of scopes in the global registry when the database is created
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.initial_scopes.extend_from_slice(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// See `TypedOptions::initial_data`.
+    pub fn initial_data(mut self, scope: Scope, pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        self.initial_data.extend(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (scope.clone(), key, value)),
+        );
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.deny_default_scope = true;
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.backfill_registry_on_open = backfill;
+        self
+    }
+
+    /// Create the database with the current transaction
+    pub fn create(self, txn: &mut RwTxn) -> Result<ScopedOrderedDatabase<K, V>, ScopedDbError> {
+        let name = self
+            .name
+            .ok_or_else(|| ScopedDbError::InvalidInput("Database name is required".into()))?;
+
+        let registry = required_registry(self.global_registry)?;
+        let db = ScopedOrderedDatabase::create(self.env, &name, txn, registry.clone())?
+            .with_observer(self.observer)
+            .with_require_registered_scopes(self.require_registered_scopes)
+            .with_verify_scope_hashes(self.verify_scope_hashes)
+            .with_deny_default_scope(self.deny_default_scope);
+
+        if self.backfill_registry_on_open {
+            registry.backfill_orphan_scopes(txn, &db)?;
+        }
+
+        for scope in &self.initial_scopes {
+            db.register_scope(txn, scope)?;
+        }
+        for (scope, key, value) in &self.initial_data {
+            db.put(txn, scope, key, value)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// See `TypedOptions::create_with_new_registry`.
+    pub fn create_with_new_registry(
+        mut self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedOrderedDatabase<K, V>), ScopedDbError> {
+        let env = self.env;
+        let mut wtxn = env.write_txn()?;
+        let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+        self.global_registry = Some(registry.clone());
+        let db = self.create(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok((registry, db))
     }
 }
 
@@ -135,8 +851,17 @@ where
 pub struct RawBytesOptions<'env> {
     env: &'env Env,
     name: Option<String>,
-    global_registry: Arc<GlobalScopeRegistry>,
+    initial_scopes: Vec<Scope>,
+    initial_data: Vec<(Scope, Vec<u8>, Vec<u8>)>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
     use_unnamed_for_default: bool,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    backfill_registry_on_open: bool,
+    heed_flags: DatabaseFlags,
 }
 
 impl RawBytesOptions<'_> {
@@ -146,6 +871,34 @@ impl RawBytesOptions<'_> {
         self
     }
 
+    /// Register a set of scopes in the global registry when the database is created
+    ///
+    /// This makes `list_scopes` complete before any data is written and surfaces
+    /// hash collisions at creation time instead of on first write.
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.initial_scopes.extend_from_slice(scopes);
+        self
+    }
+
+    /// Seed a scope with key-value pairs when the database is created
+    ///
+    /// The pairs are written with `put` in the creation transaction, after the database
+    /// is opened, so named scopes are registered as usual. Calling this several times
+    /// seeds several scopes; later pairs overwrite earlier ones with the same key. This
+    /// is meant for fixtures and reference data built once at startup.
+    pub fn initial_data(
+        mut self,
+        scope: Scope,
+        pairs: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+    ) -> Self {
+        self.initial_data.extend(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (scope.clone(), key, value)),
+        );
+        self
+    }
+
     /// Use unnamed database for default scope instead of a named database
     /// This is useful for backward compatibility with existing LMDB databases
     /// that store data in the unnamed database
@@ -154,26 +907,309 @@ impl RawBytesOptions<'_> {
         self
     }
 
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// Default-scope keys are prefixed with the reserved `DEFAULT_SCOPE_HASH`, which
+    /// costs 4 bytes per default-scope key but halves the number of named databases
+    /// counted against the environment's `max_dbs`. The data lives in a database named
+    /// `<name>_unified`, so this layout is not compatible with the two-database layout
+    /// (`<name>` and `<name>_scoped`): choose it when the database is first created and
+    /// keep it for the lifetime of the data. Named scopes whose hash equals the reserved
+    /// value are rejected with `ScopedDbError::InvalidInput`.
+    pub fn unified(mut self) -> Self {
+        self.unified = true;
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get`, `delete` and `iter` calls
+    ///
+    /// See `OperationObserver`. Without an observer no hooks are invoked.
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.deny_default_scope = true;
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.backfill_registry_on_open = backfill;
+        self
+    }
+
+    /// Set heed database flags on the default-scope database
+    ///
+    /// The flags are passed to LMDB when the default-scope database of the two-database
+    /// layout is opened, e.g. `DatabaseFlags::INTEGER_KEY` to store native-endian `u32`
+    /// or `usize` keys compared as integers, or `REVERSE_KEY` to compare keys from their
+    /// last byte. Keys of that database are stored exactly as given, so they must meet
+    /// the requirements of the flags.
+    ///
+    /// Named scopes always use default flags: their keys carry the 12-byte scope header
+    /// of `ScopedBytesCodec`, which is neither an integer nor meaningful in reverse, and
+    /// scope isolation relies on the header sorting first. For the same reason `create`
+    /// rejects flags with the unified layout, and the `DUP_SORT` family of flags, which
+    /// belongs to `multi_bytes`, with `ScopedDbError::InvalidInput`. LMDB records the flags
+    /// in the database, so pass the same flags every time it is opened.
+    ///
+    /// Both flags make the default-scope key order non-lexicographic, so `delete_prefix`
+    /// and `prefix_range` return `ScopedDbError::InvalidInput` for the default scope of a
+    /// database created with them. They keep working for named scopes.
+    pub fn heed_flags(mut self, flags: DatabaseFlags) -> Self {
+        self.heed_flags = flags;
+        self
+    }
+
     /// Create the database with the current transaction
     pub fn create(self, txn: &mut RwTxn) -> Result<ScopedBytesDatabase, ScopedDbError> {
         let name = self
             .name
             .ok_or_else(|| ScopedDbError::InvalidInput("Database name is required".into()))?;
 
-        crate::scoped_bytes_database::ScopedBytesDatabase::create(
+        if self.unified && self.use_unnamed_for_default {
+            return Err(ScopedDbError::InvalidInput(
+                "unified databases have no separate default database to place in the unnamed database".into(),
+            ));
+        }
+
+        check_default_db_flags(self.heed_flags, self.unified)?;
+
+        let registry = required_registry(self.global_registry)?;
+        let db = crate::scoped_bytes_database::ScopedBytesDatabase::create(
             self.env,
             &name,
             txn,
-            self.global_registry.clone(),
+            registry.clone(),
             self.use_unnamed_for_default,
+            self.unified,
+            self.heed_flags,
+        )?
+        .with_observer(self.observer)
+        .with_require_registered_scopes(self.require_registered_scopes)
+        .with_verify_scope_hashes(self.verify_scope_hashes)
+        .with_deny_default_scope(self.deny_default_scope);
+
+        if self.backfill_registry_on_open {
+            registry.backfill_orphan_scopes(txn, &db)?;
+        }
+
+        for scope in &self.initial_scopes {
+            db.register_scope(txn, scope)?;
+        }
+        for (scope, key, value) in &self.initial_data {
+            db.put(txn, scope, key, value)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// This opens and commits its own write transaction, so it must not be called
+    /// while another write transaction is open on the same thread. Any registry set
+    /// on the builder is replaced by the newly created one. Use `create` with a shared
+    /// registry when several databases need to be created together.
+    pub fn create_with_new_registry(
+        mut self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedBytesDatabase), ScopedDbError> {
+        let env = self.env;
+        let mut wtxn = env.write_txn()?;
+        let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+        self.global_registry = Some(registry.clone());
+        let db = self.create(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok((registry, db))
+    }
+}
+
+/// Options for raw bytes databases with several values per key
+pub struct MultiBytesOptions<'env> {
+    env: &'env Env,
+    name: Option<String>,
+    initial_scopes: Vec<Scope>,
+    global_registry: Option<Arc<GlobalScopeRegistry>>,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    backfill_registry_on_open: bool,
+}
+
+impl MultiBytesOptions<'_> {
+    /// Set the database name
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Register a set of scopes in the global registry when the database is created
+    ///
+    /// See `RawBytesOptions::register_scopes`.
+    pub fn register_scopes(mut self, scopes: &[Scope]) -> Self {
+        self.initial_scopes.extend_from_slice(scopes);
+        self
+    }
+
+    /// Store default-scope and named-scope entries in a single heed database
+    ///
+    /// See `RawBytesOptions::unified`.
+    pub fn unified(mut self) -> Self {
+        self.unified = true;
+        self
+    }
+
+    /// Attach an observer notified of `put`, `get_all`, `delete` and `iter` calls
+    ///
+    /// `get_all` is reported through `on_iter`. See `OperationObserver`.
+    pub fn observer(mut self, observer: Arc<dyn OperationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Reject writes to named scopes that are not registered yet
+    ///
+    /// See `TypedOptions::require_registered_scopes`.
+    pub fn require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// See `TypedOptions::verify_scope_hashes`.
+    pub fn verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// See `TypedOptions::deny_default_scope`.
+    pub fn deny_default_scope(mut self) -> Self {
+        self.deny_default_scope = true;
+        self
+    }
+
+    /// Register scopes that hold data but are missing from the registry when opening
+    ///
+    /// See `TypedOptions::backfill_registry_on_open`.
+    pub fn backfill_registry_on_open(mut self, backfill: bool) -> Self {
+        self.backfill_registry_on_open = backfill;
+        self
+    }
+
+    /// Create the database with the current transaction
+    pub fn create(self, txn: &mut RwTxn) -> Result<ScopedMultiBytesDatabase, ScopedDbError> {
+        let name = self
+            .name
+            .ok_or_else(|| ScopedDbError::InvalidInput("Database name is required".into()))?;
+
+        let registry = required_registry(self.global_registry)?;
+        let db =
+            ScopedMultiBytesDatabase::create(self.env, &name, txn, registry.clone(), self.unified)?
+                .with_observer(self.observer)
+                .with_require_registered_scopes(self.require_registered_scopes)
+                .with_verify_scope_hashes(self.verify_scope_hashes)
+                .with_deny_default_scope(self.deny_default_scope);
+
+        if self.backfill_registry_on_open {
+            registry.backfill_orphan_scopes(txn, &db)?;
+        }
+
+        for scope in &self.initial_scopes {
+            db.register_scope(txn, scope)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Create a new global registry and the database in a single write transaction
+    ///
+    /// See `RawBytesOptions::create_with_new_registry`.
+    pub fn create_with_new_registry(
+        mut self,
+    ) -> Result<(Arc<GlobalScopeRegistry>, ScopedMultiBytesDatabase), ScopedDbError> {
+        let env = self.env;
+        let mut wtxn = env.write_txn()?;
+        let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+        self.global_registry = Some(registry.clone());
+        let db = self.create(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok((registry, db))
+    }
+}
+
+/// Take the registry configured on a builder, failing if none was provided
+fn required_registry(
+    registry: Option<Arc<GlobalScopeRegistry>>,
+) -> Result<Arc<GlobalScopeRegistry>, ScopedDbError> {
+    registry.ok_or_else(|| {
+        ScopedDbError::InvalidInput(
+            "A global registry is required; use with_registry or create_with_new_registry".into(),
         )
+    })
+}
+
+/// Reject heed flags that cannot be applied to the default-scope database, see
+/// `RawBytesOptions::heed_flags`
+fn check_default_db_flags(flags: DatabaseFlags, unified: bool) -> Result<(), ScopedDbError> {
+    if flags.is_empty() {
+        return Ok(());
+    }
+    if unified {
+        return Err(ScopedDbError::InvalidInput(
+            "heed flags only apply to the default-scope database, which unified databases do not have".into(),
+        ));
+    }
+    let supported = DatabaseFlags::INTEGER_KEY | DatabaseFlags::REVERSE_KEY;
+    if !supported.contains(flags) {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "Unsupported heed flags {:?}; only INTEGER_KEY and REVERSE_KEY can be set",
+            flags.difference(supported)
+        )));
     }
+    Ok(())
 }
 
 /// Module-level function to create scoped database options
 pub fn scoped_database_options(
     env: &Env,
     global_registry: Arc<GlobalScopeRegistry>,
-) -> ScopedDatabaseOptions {
+) -> ScopedDatabaseOptions<'_> {
     ScopedDatabaseOptions::new(env, global_registry)
 }
+
+/// Module-level function to create scoped database options without a registry
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{standalone_database_options, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let (registry, db) = standalone_database_options(&env)
+///     .types::<String, String>()
+///     .name("config")
+///     .create_with_new_registry()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn standalone_database_options(env: &Env) -> ScopedDatabaseOptions<'_> {
+    ScopedDatabaseOptions::standalone(env)
+}
@@ -1,6 +1,10 @@
-use crate::{Scope, ScopedDbError};
-use heed::types::SerdeBincode;
-use heed::{Database as HeedDatabase, Env, RoTxn, RwTxn};
+use crate::scope::{ScopeNameRules, compute_xxhash};
+use crate::{Scope, ScopeIterResult, ScopedDbError, utils};
+use heed::types::{Bytes, DecodeIgnore, SerdeBincode};
+use heed::{BytesDecode, Database as HeedDatabase, Env, RoTxn, RwTxn};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::sync::Arc;
 
 /// A centralized registry for managing scope metadata at the environment level.
 ///
@@ -27,11 +31,34 @@ use heed::{Database as HeedDatabase, Env, RoTxn, RwTxn};
 ///
 /// ## Performance Considerations
 ///
-/// The `lookup_scope_hash()` method, which finds a hash by scope name, iterates through
-/// all registered scopes. This is generally fast for normal usage (up to thousands of scopes),
-/// but could become a performance concern with a very large number of unique scopes (tens
-/// of thousands or more). In such cases, consider grouping related data under fewer scopes
-/// or using a hierarchical scope naming scheme.
+/// The `lookup_scope_hash()` method, which finds a hash by scope name, is a single point
+/// read of the metadata database keyed by the xxhash of the namespace-qualified name.
+/// There is no cache and no scan, so its cost does not grow with the number of scopes,
+/// whether the name is registered or not.
+///
+/// ## Concurrency
+///
+/// Registry entries live in the environment, not in the `GlobalScopeRegistry` value, so
+/// any number of databases and threads can share one registry through an `Arc` or
+/// clones. `register_scope` reads the entry for the hash and writes it in the caller's
+/// write transaction, and LMDB allows a single write transaction per environment at a
+/// time, so the check and the write cannot interleave with another writer's: two
+/// databases registering the same new scope in separate transactions both succeed,
+/// the second finding the first's entry, and a colliding name is rejected whichever
+/// transaction commits first. A registration becomes visible to other transactions
+/// when its transaction commits and disappears if it is aborted. Readers see the
+/// registry as of their own snapshot.
+///
+/// The registry keeps no shared state in memory: every lookup reads the caller's
+/// transaction, so answers always match that transaction's snapshot.
+///
+/// ## Namespaces
+///
+/// Unrelated subsystems sharing an environment can each open the registry with
+/// `GlobalScopeRegistry::namespaced`. Scope names are then qualified with the namespace
+/// before hashing, so "acme" in the "billing" namespace and "acme" in the "analytics"
+/// namespace are different scopes with different hashes. Scopes for a namespaced
+/// registry are built with `GlobalScopeRegistry::scope`.
 ///
 /// # Example
 ///
@@ -70,15 +97,121 @@ pub trait ScopeEmptinessChecker {
     fn is_scope_empty_in_db(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError>;
 }
 
+/// Trait for database types that can list the scope hashes present in their data
+///
+/// Used by `GlobalScopeRegistry::audit` to find data whose scope is not registered.
+pub trait ScopeHashEnumerator: ScopeEmptinessChecker {
+    /// List the distinct hashes of named scopes holding at least one entry, in ascending order
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError>;
+}
+
+/// Result of comparing the registry against the data of a set of databases.
+///
+/// Returned by `GlobalScopeRegistry::audit` and `GlobalScopeRegistry::repair`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryAudit {
+    /// Hashes with data in at least one database but no registry entry, in ascending order
+    pub orphan_hashes: Vec<u32>,
+    /// Registered scopes without data in any of the audited databases
+    pub stale_entries: Vec<Scope>,
+}
+
+impl RegistryAudit {
+    /// Check if the registry matches the audited data
+    pub fn is_consistent(&self) -> bool {
+        self.orphan_hashes.is_empty() && self.stale_entries.is_empty()
+    }
+}
+
+/// Trait for database types that can clear a scope
+///
+/// This is object safe, so databases of different types can be held together, e.g. as
+/// `Vec<Box<dyn ScopedClear>>`, and cleared uniformly by administrative code.
+pub trait ScopedClear {
+    /// Clear a scope in this database, returning the number of entries removed
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError>;
+}
+
+impl<T: ScopedClear + ?Sized> ScopedClear for Box<T> {
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        (**self).clear_scope(txn, scope)
+    }
+}
+
+impl<T: ScopedClear + ?Sized> ScopedClear for Arc<T> {
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        (**self).clear_scope(txn, scope)
+    }
+}
+
+/// Clears a scope in every given database within a single transaction.
+///
+/// Returns the total number of entries removed across all databases. The scope stays
+/// registered; use `GlobalScopeRegistry::clear_and_unregister_scope` to also remove it
+/// from the registry.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{clear_scope_everywhere, Scope, ScopedClear, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let tenant = Scope::named("tenant1")?;
+/// let mut wtxn = env.write_txn()?;
+/// let databases: [&dyn ScopedClear; 2] = [&users_db, &orders_db];
+/// let removed = clear_scope_everywhere(&mut wtxn, &tenant, &databases)?;
+/// wtxn.commit()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn clear_scope_everywhere(
+    txn: &mut RwTxn,
+    scope: &Scope,
+    databases: &[&dyn ScopedClear],
+) -> Result<usize, ScopedDbError> {
+    let mut removed = 0;
+    for db in databases {
+        removed += db.clear_scope(txn, scope)?;
+    }
+    Ok(removed)
+}
+
+/// Options controlling how `GlobalScopeRegistry` stores scope metadata.
+///
+/// Passed to `GlobalScopeRegistry::new_with_options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistryOptions {
+    /// Whether scope names are stored next to their hashes (the default).
+    ///
+    /// Without names the registry only records which hashes are in use, which saves
+    /// space when names are long. Names can then no longer be recovered from the
+    /// registry: `get_scope_name` returns `None`, `list_all_scopes` and `iter_scopes`
+    /// report hexadecimal placeholder names (e.g. `0x0000002a`), and hash collisions
+    /// between different names go undetected. Name-based lookups such as
+    /// `lookup_scope_hash` and `scope_exists_by_name` fall back to hashing the name.
+    pub store_names: bool,
+}
+
+impl Default for RegistryOptions {
+    fn default() -> Self {
+        Self { store_names: true }
+    }
+}
+
 #[derive(Debug)]
 pub struct GlobalScopeRegistry {
     metadata_db: HeedDatabase<SerdeBincode<u32>, SerdeBincode<String>>,
+    name_rules: Option<ScopeNameRules>,
+    store_names: bool,
+    namespace: Option<String>,
 }
 
 impl GlobalScopeRegistry {
     /// The name of the LMDB database used for global scope metadata
     pub const GLOBAL_METADATA_DB_NAME: &'static str = "__global_scope_metadata";
 
+    /// Separator between a registry namespace and a scope name, see `namespaced`
+    pub const NAMESPACE_SEPARATOR: char = ':';
+
     /// Creates a new global scope registry.
     ///
     /// This method creates or opens the shared LMDB database for storing scope metadata.
@@ -92,13 +225,205 @@ impl GlobalScopeRegistry {
     ///
     /// A new `GlobalScopeRegistry` instance
     pub fn new(env: &Env, txn: &mut RwTxn) -> Result<Self, ScopedDbError> {
+        Self::new_with_options(env, txn, RegistryOptions::default())
+    }
+
+    /// Creates a new global scope registry with the given storage options.
+    ///
+    /// The storage mode is recorded in the entries themselves: entries written without
+    /// names have empty values, which no stored name encodes to. A registry holding
+    /// entries must therefore always be reopened with the same `store_names` setting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{GlobalScopeRegistry, RegistryOptions};
+    /// let options = RegistryOptions { store_names: false };
+    /// let registry = Arc::new(GlobalScopeRegistry::new_with_options(&env, &mut wtxn, options)?);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if the registry already holds entries
+    /// written with the other `store_names` setting.
+    pub fn new_with_options(
+        env: &Env,
+        txn: &mut RwTxn,
+        options: RegistryOptions,
+    ) -> Result<Self, ScopedDbError> {
         let metadata_db = env
             .database_options()
             .types::<SerdeBincode<u32>, SerdeBincode<String>>()
             .name(Self::GLOBAL_METADATA_DB_NAME)
             .create(txn)?;
 
-        Ok(Self { metadata_db })
+        if let Some((_, value)) = metadata_db.remap_data_type::<Bytes>().first(txn)? {
+            let stores_names = !value.is_empty();
+            if stores_names != options.store_names {
+                return Err(ScopedDbError::InvalidInput(format!(
+                    "Scope registry was created with store_names = {}, but opened with store_names = {}",
+                    stores_names, options.store_names
+                )));
+            }
+        }
+
+        Ok(Self {
+            metadata_db,
+            name_rules: None,
+            store_names: options.store_names,
+            namespace: None,
+        })
+    }
+
+    /// Creates a registry whose scope names live in their own namespace.
+    ///
+    /// The registry shares the environment's metadata database with every other
+    /// registry, but scope names are qualified as `<namespace>:<name>` before hashing.
+    /// Scopes must be built with `scope`, which applies the qualification; writing
+    /// through this registry to a scope outside the namespace fails. Name-based
+    /// lookups (`scope_exists_by_name`, `lookup_scope_hash`) take unqualified names,
+    /// while `list_all_scopes` and `iter_scopes` report only this namespace's scopes,
+    /// with their qualified names. Naming rules apply to the unqualified name.
+    ///
+    /// Every user of a namespace must open the registry with the same namespace, since
+    /// a scope's hash depends on it. Synthetic entries registered by `repair` or a
+    /// backfill belong to no namespace and are not listed by namespaced registries.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::GlobalScopeRegistry;
+    /// let billing = Arc::new(GlobalScopeRegistry::namespaced(&env, &mut wtxn, "billing")?);
+    /// let analytics = Arc::new(GlobalScopeRegistry::namespaced(&env, &mut wtxn, "analytics")?);
+    /// assert_ne!(billing.scope("acme")?.hash(), analytics.scope("acme")?.hash());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if the namespace is empty or contains
+    /// `NAMESPACE_SEPARATOR`.
+    pub fn namespaced(env: &Env, txn: &mut RwTxn, namespace: &str) -> Result<Self, ScopedDbError> {
+        if namespace.is_empty() || namespace.contains(Self::NAMESPACE_SEPARATOR) {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Registry namespace '{}' must be non-empty and must not contain '{}'",
+                namespace,
+                Self::NAMESPACE_SEPARATOR
+            )));
+        }
+        let mut registry = Self::new(env, txn)?;
+        registry.namespace = Some(namespace.to_owned());
+        Ok(registry)
+    }
+
+    /// The namespace of this registry, if it was opened with `namespaced`
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Builds the scope for `name` in this registry's namespace.
+    ///
+    /// Without a namespace this is `Scope::named`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty.
+    pub fn scope(&self, name: &str) -> Result<Scope, ScopedDbError> {
+        if name.is_empty() {
+            return Err(ScopedDbError::EmptyScopeDisallowed);
+        }
+        Scope::named(&self.qualify(name))
+    }
+
+    /// Qualify a scope name with the namespace, if any
+    fn qualify<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match &self.namespace {
+            Some(namespace) => Cow::Owned(format!(
+                "{}{}{}",
+                namespace,
+                Self::NAMESPACE_SEPARATOR,
+                name
+            )),
+            None => Cow::Borrowed(name),
+        }
+    }
+
+    /// Strip the namespace from a qualified name, or `None` if the name is outside it
+    fn local_name<'a>(&self, name: &'a str) -> Option<&'a str> {
+        strip_namespace(self.namespace.as_deref(), name)
+    }
+
+    /// Whether this registry stores scope names, see `RegistryOptions::store_names`
+    pub fn stores_names(&self) -> bool {
+        self.store_names
+    }
+
+    /// Whether a hash is registered, without decoding its name
+    fn contains_hash(&self, txn: &RoTxn, hash: &u32) -> Result<bool, ScopedDbError> {
+        Ok(self
+            .metadata_db
+            .remap_data_type::<DecodeIgnore>()
+            .get(txn, hash)?
+            .is_some())
+    }
+
+    /// Write a registry entry, leaving out the name unless names are stored
+    fn put_entry(&self, txn: &mut RwTxn, hash: &u32, name: &str) -> Result<(), ScopedDbError> {
+        if self.store_names {
+            self.metadata_db.put(txn, hash, &name.to_owned())?;
+        } else {
+            self.metadata_db
+                .remap_data_type::<Bytes>()
+                .put(txn, hash, &[])?;
+        }
+        Ok(())
+    }
+
+    /// Iterate over all entries as `(hash, name)`, using placeholder names when names
+    /// are not stored
+    fn entries<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+    ) -> Result<impl Iterator<Item = Result<(u32, String), ScopedDbError>> + 'txn, ScopedDbError>
+    {
+        let store_names = self.store_names;
+        let namespace = self.namespace.clone();
+        let iter = self
+            .metadata_db
+            .remap_data_type::<Bytes>()
+            .iter(txn)?
+            .map(move |result| {
+                let (hash, value) = result?;
+                let name = if store_names {
+                    SerdeBincode::<String>::bytes_decode(value)?
+                } else {
+                    utils::placeholder_scope_name(hash)
+                };
+                Ok((hash, name))
+            })
+            // Namespaced registries only see their own entries
+            .filter(move |entry| match entry {
+                Ok((_, name)) => strip_namespace(namespace.as_deref(), name).is_some(),
+                Err(_) => true,
+            });
+        Ok(iter)
+    }
+
+    /// Enforces naming rules on every scope registered through this registry.
+    ///
+    /// Since write operations register their scope implicitly, this rejects writes
+    /// to scopes with invalid names across all databases sharing the registry.
+    /// Scopes that were registered before the rules were attached are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{GlobalScopeRegistry, ScopeNameRules};
+    /// let rules = ScopeNameRules::new().max_len(64);
+    /// let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?.with_name_rules(rules));
+    /// ```
+    pub fn with_name_rules(mut self, rules: ScopeNameRules) -> Self {
+        self.name_rules = Some(rules);
+        self
     }
 
     /// Registers a scope in the global metadata database.
@@ -114,13 +439,37 @@ impl GlobalScopeRegistry {
     ///
     /// # Errors
     ///
-    /// Returns an error if there's a hash collision between different scope names.
+    /// Returns an error if there's a hash collision between different scope names,
+    /// if the name violates the registry's naming rules, or if the scope lies outside
+    /// the registry's namespace.
     pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
         if let Scope::Named { name, hash } = scope {
+            let Some(local_name) = self.local_name(name) else {
+                return Err(ScopedDbError::InvalidInput(format!(
+                    "Scope '{}' is outside the registry namespace '{}'; build it with GlobalScopeRegistry::scope",
+                    name,
+                    self.namespace.as_deref().unwrap_or_default()
+                )));
+            };
+            if let Some(rules) = &self.name_rules {
+                rules.validate(local_name)?;
+            }
+
+            if !self.store_names {
+                // Without names, collisions cannot be detected
+                if !self.contains_hash(txn, hash)? {
+                    self.put_entry(txn, hash, name)?;
+                }
+                return Ok(());
+            }
+
             // Check if this hash already exists
             if let Some(existing_name) = self.metadata_db.get(txn, hash)? {
-                // If it exists but points to a different scope name, we have a collision
-                if &existing_name != name {
+                // Synthetic names from `repair` or a backfill give way to the real name
+                if existing_name == utils::placeholder_scope_name(*hash) {
+                    self.put_entry(txn, hash, name)?;
+                } else if &existing_name != name {
+                    // It points to a different scope name, so we have a collision
                     return Err(ScopedDbError::InvalidInput(format!(
                         "Hash collision detected between '{}' and '{}'",
                         name, existing_name
@@ -128,12 +477,57 @@ impl GlobalScopeRegistry {
                 }
             } else {
                 // Register new scope in metadata database
-                self.metadata_db.put(txn, hash, name)?;
+                self.put_entry(txn, hash, name)?;
             }
         }
         Ok(())
     }
 
+    /// Registers a batch of scopes, reporting hash collisions instead of failing on them.
+    ///
+    /// Every scope that does not collide is registered exactly as by `register_scope`,
+    /// and becomes durable when `txn` is committed. A scope whose hash is already
+    /// registered under a different name is skipped and returned together with that
+    /// name, which is given without this registry's namespace. Scopes are registered in
+    /// order, so of two scopes in the batch that share a hash the first is registered
+    /// and the second reported. The default scope and scopes that are already
+    /// registered are accepted without change.
+    ///
+    /// Registries that do not store names cannot detect collisions and report none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name violates the registry's naming rules or lies outside
+    /// its namespace. Scopes registered before the failing one stay registered in `txn`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut wtxn = env.write_txn()?;
+    /// let collisions = registry.register_scopes(&mut wtxn, &new_tenants)?;
+    /// for (scope, existing) in &collisions {
+    ///     eprintln!("{:?} collides with '{}'", scope.name(), existing);
+    /// }
+    /// wtxn.commit()?;
+    /// ```
+    pub fn register_scopes(
+        &self,
+        txn: &mut RwTxn,
+        scopes: &[Scope],
+    ) -> Result<Vec<(Scope, String)>, ScopedDbError> {
+        let mut collisions = Vec::new();
+        for scope in scopes {
+            if let Scope::Named { name, hash } = scope
+                && let Some(existing) = self.colliding_name(txn, *hash, name)?
+            {
+                collisions.push((scope.clone(), self.display_name(existing)));
+                continue;
+            }
+            self.register_scope(txn, scope)?;
+        }
+        Ok(collisions)
+    }
+
     /// Gets the name of a scope by its hash.
     ///
     /// # Arguments
@@ -143,8 +537,12 @@ impl GlobalScopeRegistry {
     ///
     /// # Returns
     ///
-    /// The name of the scope if found, or `None` if not registered
+    /// The name of the scope if found, or `None` if not registered or if this registry
+    /// does not store names
     pub fn get_scope_name(&self, txn: &RoTxn, hash: &u32) -> Result<Option<String>, ScopedDbError> {
+        if !self.store_names {
+            return Ok(None);
+        }
         self.metadata_db.get(txn, hash).map_err(ScopedDbError::from)
     }
 
@@ -158,18 +556,54 @@ impl GlobalScopeRegistry {
     /// # Returns
     ///
     /// The hash of the scope if found, or `None` if not registered
+    ///
+    /// # Performance
+    ///
+    /// A scope is registered under the hash of its name, so the lookup hashes `name` and
+    /// reads that single entry of the metadata database, checking that the stored name
+    /// matches. Hits and misses both cost one point read, however many scopes exist.
     pub fn lookup_scope_hash(&self, txn: &RoTxn, name: &str) -> Result<Option<u32>, ScopedDbError> {
-        for result in self.metadata_db.iter(txn)? {
-            let (hash, stored_name) = result?;
-            if stored_name == name {
-                return Ok(Some(hash));
-            }
+        let name = self.qualify(name);
+        let name = name.as_ref();
+        let hash = compute_xxhash(name.as_bytes());
+        if !self.store_names {
+            return Ok(self.contains_hash(txn, &hash)?.then_some(hash));
         }
-        Ok(None)
+        let stored_name = self.metadata_db.get(txn, &hash)?;
+        Ok((stored_name.as_deref() == Some(name)).then_some(hash))
+    }
+
+    /// Lazily iterates over all scopes registered in the global metadata database.
+    ///
+    /// The default scope is yielded first, followed by the named scopes in the order of
+    /// their hashes in the metadata database. Names are decoded one entry at a time, so
+    /// callers looking for a few matching scopes do not pay for materializing all of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let rtxn = env.read_txn()?;
+    /// let first_acme = registry
+    ///     .iter_scopes(&rtxn)?
+    ///     .find(|scope| matches!(scope, Ok(scope) if scope.name().is_some_and(|n| n.starts_with("acme-"))))
+    ///     .transpose()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_scopes<'txn>(&self, txn: &'txn RoTxn<'txn>) -> ScopeIterResult<'txn> {
+        let named = self.entries(txn)?.map(|result| {
+            let (hash, name) = result?;
+            Ok(Scope::Named { name, hash })
+        });
+        Ok(Box::new(std::iter::once(Ok(Scope::Default)).chain(named)))
     }
 
     /// Lists all scopes registered in the global metadata database.
     ///
+    /// This collects `iter_scopes`.
+    ///
     /// # Arguments
     ///
     /// * `txn` - A read transaction
@@ -178,20 +612,48 @@ impl GlobalScopeRegistry {
     ///
     /// A vector of all registered scopes, including the default scope
     pub fn list_all_scopes(&self, txn: &RoTxn) -> Result<Vec<Scope>, ScopedDbError> {
-        let mut scopes = Vec::new();
-
-        // Always include the default scope
-        scopes.push(Scope::Default);
+        self.iter_scopes(txn)?.collect()
+    }
 
-        // Add all named scopes from the metadata database
-        for result in self.metadata_db.iter(txn)? {
-            let (hash, name) = result?;
-            scopes.push(Scope::Named { name, hash });
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// Scopes are streamed from `iter_scopes` and filtered as they are decoded, so
+    /// only the matching scopes are collected. The default scope is passed to `pred`
+    /// as the empty name, like in `Scope::try_named`, so it is included only when
+    /// `pred("")` holds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let rtxn = env.read_txn()?;
+    /// let acme_scopes = registry.list_scopes_where(&rtxn, |name| name.contains("acme"))?;
+    /// ```
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut scopes = Vec::new();
+        for scope in self.iter_scopes(txn)? {
+            let scope = scope?;
+            if pred(scope.name().unwrap_or_default()) {
+                scopes.push(scope);
+            }
         }
-
         Ok(scopes)
     }
 
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    ///
+    /// Built on `list_scopes_where`; an empty prefix matches every scope, including
+    /// the default scope.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.list_scopes_where(txn, |name| name.starts_with(prefix))
+    }
+
     /// Checks if a scope is empty across multiple database instances.
     ///
     /// This is a utility method for checking if a scope is truly empty
@@ -232,7 +694,122 @@ impl GlobalScopeRegistry {
     pub fn scope_exists(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
         match scope {
             Scope::Default => Ok(true), // Default scope always exists
-            Scope::Named { hash, .. } => Ok(self.metadata_db.get(txn, hash)?.is_some()),
+            Scope::Named { hash, .. } => self.contains_hash(txn, hash),
+        }
+    }
+
+    /// Fails unless `scope` is registered under its name.
+    ///
+    /// Used on the write path of databases built with `require_registered_scopes`.
+    pub(crate) fn ensure_registered(
+        &self,
+        txn: &RoTxn,
+        scope: &Scope,
+    ) -> Result<(), ScopedDbError> {
+        if let Scope::Named { name, hash } = scope
+            && !self.registered_under(txn, *hash, name)?
+        {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Scope '{}' is not registered; register it before writing to it",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks if a scope is registered under exactly the given name.
+    ///
+    /// This hashes the name internally, so callers don't need to construct a `Scope`
+    /// first. If the hash is registered under a *different* name (a hash collision),
+    /// this returns `false`, since the requested scope itself was never registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `txn` - A read transaction
+    /// * `name` - The name of the scope
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty.
+    pub fn scope_exists_by_name(&self, txn: &RoTxn, name: &str) -> Result<bool, ScopedDbError> {
+        if name.is_empty() {
+            return Err(ScopedDbError::EmptyScopeDisallowed);
+        }
+
+        let name = self.qualify(name);
+        self.registered_under(txn, compute_xxhash(name.as_bytes()), &name)
+    }
+
+    /// Returns the registered name that `name` would collide with, if any.
+    ///
+    /// This is the check `register_scope` performs, without writing anything: it
+    /// returns `Some(existing_name)` when the hash of `name` is already registered under
+    /// a different name, and `None` when the name is free or already registered as
+    /// itself. Names are qualified with the registry's namespace, and a colliding name
+    /// inside the namespace is returned without it. Placeholder names left by `repair`
+    /// never collide, since registering the real name replaces them.
+    ///
+    /// Registries that do not store names cannot detect collisions and always return
+    /// `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty, and
+    /// `ScopedDbError::InvalidInput` if it hashes to the reserved `DEFAULT_SCOPE_HASH`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let rtxn = env.read_txn()?;
+    /// if let Some(existing) = registry.would_collide(&rtxn, "new_tenant")? {
+    ///     return Err(format!("'new_tenant' collides with '{}'", existing).into());
+    /// }
+    /// ```
+    pub fn would_collide(&self, txn: &RoTxn, name: &str) -> Result<Option<String>, ScopedDbError> {
+        let Scope::Named { name, hash } = self.scope(name)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .colliding_name(txn, hash, &name)?
+            .map(|existing| self.display_name(existing)))
+    }
+
+    /// The name registered under `hash` if it is neither `name` nor a placeholder
+    fn colliding_name(
+        &self,
+        txn: &RoTxn,
+        hash: u32,
+        name: &str,
+    ) -> Result<Option<String>, ScopedDbError> {
+        if !self.store_names {
+            return Ok(None);
+        }
+        match self.metadata_db.get(txn, &hash)? {
+            Some(existing)
+                if existing != name && existing != utils::placeholder_scope_name(hash) =>
+            {
+                Ok(Some(existing))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// A registered name without this registry's namespace, if it lies inside it
+    fn display_name(&self, name: String) -> String {
+        match self.local_name(&name) {
+            Some(local) => local.to_owned(),
+            None => name,
+        }
+    }
+
+    /// Whether `hash` is registered for exactly `name`
+    fn registered_under(&self, txn: &RoTxn, hash: u32, name: &str) -> Result<bool, ScopedDbError> {
+        if !self.store_names {
+            return self.contains_hash(txn, &hash);
+        }
+        match self.metadata_db.get(txn, &hash)? {
+            Some(registered_name) => Ok(registered_name == name),
+            None => Ok(false),
         }
     }
 
@@ -252,7 +829,7 @@ impl GlobalScopeRegistry {
     /// `Ok(())` if the scope was successfully unregistered, or `Err` if an error occurred
     pub fn unregister_scope(&self, txn: &mut RwTxn, hash: &u32) -> Result<(), ScopedDbError> {
         // Check if the hash exists before attempting to delete
-        if self.metadata_db.get(txn, hash)?.is_some() {
+        if self.contains_hash(txn, hash)? {
             self.metadata_db.delete(txn, hash)?;
             Ok(())
         } else {
@@ -308,36 +885,223 @@ impl GlobalScopeRegistry {
         txn: &mut RwTxn,
         databases: &[&dyn ScopeEmptinessChecker],
     ) -> Result<usize, ScopedDbError> {
+        let unused = self.find_globally_unused_scopes(txn, databases)?;
+
+        for scope in &unused {
+            if let Scope::Named { hash, .. } = scope {
+                self.unregister_scope(txn, hash)?;
+            }
+        }
+
+        Ok(unused.len())
+    }
+
+    /// Lists the scopes that `prune_globally_unused_scopes` would unregister.
+    ///
+    /// Performs the same emptiness checks against the given databases but leaves the
+    /// registry untouched and only needs a read transaction, so the candidates can be
+    /// reviewed before pruning. The default scope is never reported, and an empty
+    /// `databases` slice yields no candidates.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{ScopeEmptinessChecker, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let rtxn = env.read_txn()?;
+    /// let databases: [&dyn ScopeEmptinessChecker; 2] = [&db1, &db2];
+    /// for scope in registry.find_globally_unused_scopes(&rtxn, &databases)? {
+    ///     println!("would prune {:?}", scope.name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_globally_unused_scopes(
+        &self,
+        txn: &RoTxn,
+        databases: &[&dyn ScopeEmptinessChecker],
+    ) -> Result<Vec<Scope>, ScopedDbError> {
         if databases.is_empty() {
-            return Ok(0);
+            return Ok(Vec::new());
         }
 
-        let mut pruned_count = 0;
+        let mut unused = Vec::new();
         let scopes = self.list_all_scopes(txn)?;
 
         // Skip the default scope - it's always needed
-        for scope in scopes.iter().filter(|s| !matches!(s, Scope::Default)) {
-            // Assume the scope is empty until we find otherwise
+        for scope in scopes.into_iter().filter(|s| !matches!(s, Scope::Default)) {
+            // The scope is unused only if it is empty in every database
             let mut is_empty = true;
-
-            // Check each database to see if the scope is empty
             for db in databases {
-                if !db.is_scope_empty_in_db(txn, scope)? {
+                if !db.is_scope_empty_in_db(txn, &scope)? {
                     is_empty = false;
                     break;
                 }
             }
 
-            // If the scope is empty in all databases, unregister it
             if is_empty {
-                if let Scope::Named { hash, .. } = scope {
-                    self.unregister_scope(txn, hash)?;
-                    pruned_count += 1;
-                }
+                unused.push(scope);
+            }
+        }
+
+        Ok(unused)
+    }
+
+    /// Compares the registry against the scopes actually present in the given databases.
+    ///
+    /// After crashes or manual LMDB edits the registry can drift from the data. The audit
+    /// reports `orphan_hashes`, scope hashes that have data but no registry entry, and
+    /// `stale_entries`, registered scopes without data in any of the databases. Every
+    /// database sharing the registry should be passed, otherwise scopes used only by the
+    /// missing databases are reported as stale. With no databases the audit is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{ScopeHashEnumerator, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let rtxn = env.read_txn()?;
+    /// let databases: [&dyn ScopeHashEnumerator; 2] = [&users_db, &orders_db];
+    /// let audit = registry.audit(&rtxn, &databases)?;
+    /// if !audit.is_consistent() {
+    ///     println!("orphans: {:?}, stale: {:?}", audit.orphan_hashes, audit.stale_entries);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn audit(
+        &self,
+        txn: &RoTxn,
+        databases: &[&dyn ScopeHashEnumerator],
+    ) -> Result<RegistryAudit, ScopedDbError> {
+        if databases.is_empty() {
+            return Ok(RegistryAudit::default());
+        }
+
+        let mut present = BTreeSet::new();
+        for db in databases {
+            present.extend(db.scope_hashes_in_db(txn)?);
+        }
+
+        let mut stale_entries = Vec::new();
+        for result in self.entries(txn)? {
+            let (hash, name) = result?;
+            if !present.remove(&hash) {
+                stale_entries.push(Scope::Named { name, hash });
+            }
+        }
+
+        // Whatever is left has data but no registry entry, unless another namespace owns it
+        let mut orphan_hashes = Vec::new();
+        for hash in present {
+            if self.namespace.is_none() || !self.contains_hash(txn, &hash)? {
+                orphan_hashes.push(hash);
+            }
+        }
+
+        Ok(RegistryAudit {
+            orphan_hashes,
+            stale_entries,
+        })
+    }
+
+    /// Audits the registry and fixes the inconsistencies found.
+    ///
+    /// Orphan hashes are registered under a synthetic name, the hash in hexadecimal
+    /// (e.g. `0x0000002a`), so they show up in `list_all_scopes` and can be cleared or
+    /// renamed; the synthetic name bypasses the registry's naming rules. Registering
+    /// the real scope later replaces the synthetic name instead of reporting a collision. Stale entries
+    /// are unregistered. Returns the audit describing what was repaired.
+    pub fn repair(
+        &self,
+        txn: &mut RwTxn,
+        databases: &[&dyn ScopeHashEnumerator],
+    ) -> Result<RegistryAudit, ScopedDbError> {
+        let audit = self.audit(txn, databases)?;
+
+        for hash in &audit.orphan_hashes {
+            self.put_entry(txn, hash, &utils::placeholder_scope_name(*hash))?;
+        }
+        for scope in &audit.stale_entries {
+            if let Scope::Named { hash, .. } = scope {
+                self.unregister_scope(txn, hash)?;
             }
         }
 
-        Ok(pruned_count)
+        Ok(audit)
+    }
+
+    /// Registers the scope hashes holding data in a database that have no registry entry.
+    ///
+    /// Orphan hashes get the same synthetic names as in `repair`. Entries that are not
+    /// backed by data in `db` are left alone, since other databases may use them.
+    /// Returns the hashes that were registered.
+    pub(crate) fn backfill_orphan_scopes(
+        &self,
+        txn: &mut RwTxn,
+        db: &dyn ScopeHashEnumerator,
+    ) -> Result<Vec<u32>, ScopedDbError> {
+        let mut registered = Vec::new();
+        for hash in db.scope_hashes_in_db(txn)? {
+            if !self.contains_hash(txn, &hash)? {
+                self.put_entry(txn, &hash, &utils::placeholder_scope_name(hash))?;
+                registered.push(hash);
+            }
+        }
+        Ok(registered)
+    }
+
+    /// Clears a scope in every given database and then unregisters it.
+    ///
+    /// This is intended for offboarding a tenant: all of its data is removed and the
+    /// scope disappears from `list_all_scopes`. The databases should cover every
+    /// database that uses the scope, otherwise entries remain that are no longer
+    /// listed in the registry.
+    ///
+    /// Returns the total number of entries removed.
+    pub fn clear_and_unregister_scope(
+        &self,
+        txn: &mut RwTxn,
+        scope: &Scope,
+        databases: &[&dyn ScopedClear],
+    ) -> Result<usize, ScopedDbError> {
+        let removed = clear_scope_everywhere(txn, scope, databases)?;
+        if let Scope::Named { hash, .. } = scope {
+            self.unregister_scope(txn, hash)?;
+        }
+        Ok(removed)
+    }
+
+    /// Unregisters a scope unless it still holds data in one of the given databases.
+    ///
+    /// Returns `true` if the scope was removed from the registry. The default scope is
+    /// never registered, so it always yields `false`.
+    pub(crate) fn unregister_if_unused(
+        &self,
+        txn: &mut RwTxn,
+        scope: &Scope,
+        databases: &[&dyn ScopeEmptinessChecker],
+    ) -> Result<bool, ScopedDbError> {
+        let Scope::Named { hash, .. } = scope else {
+            return Ok(false);
+        };
+        for db in databases {
+            if !db.is_scope_empty_in_db(txn, scope)? {
+                return Ok(false);
+            }
+        }
+        self.unregister_scope(txn, hash)?;
+        Ok(true)
+    }
+}
+
+/// Strip `namespace` from a qualified scope name, or `None` if the name is outside it
+fn strip_namespace<'a>(namespace: Option<&str>, name: &'a str) -> Option<&'a str> {
+    match namespace {
+        Some(namespace) => name
+            .strip_prefix(namespace)?
+            .strip_prefix(GlobalScopeRegistry::NAMESPACE_SEPARATOR),
+        None => Some(name),
     }
 }
 
@@ -345,6 +1109,9 @@ impl Clone for GlobalScopeRegistry {
     fn clone(&self) -> Self {
         Self {
             metadata_db: self.metadata_db,
+            name_rules: self.name_rules.clone(),
+            store_names: self.store_names,
+            namespace: self.namespace.clone(),
         }
     }
 }
@@ -74,6 +74,16 @@
 //!    - Maximum performance with zero serialization
 //!    - ~1.8x faster writes than generic version
 //!
+//! `ScopedMultiBytesDatabase` stores several raw byte values per key using LMDB's
+//! `MDB_DUPSORT`, with the same key layout as the raw bytes database.
+//!
+//! `ScopedU64Database` and `ScopedI64Database` store integer counters under raw byte
+//! keys as 8 native-endian bytes, skipping bincode, and offer an `increment` helper.
+//!
+//! `ScopedTupleKeyDatabase<K, V>` is a thin typed facade over the bytes key database
+//! for fixed-width compound keys such as `(u64, u32)`. Keys are encoded big-endian so
+//! that range queries follow numeric ordering.
+//!
 //! ## Key Encoding
 //!
 //! Scoped entries use different key encoding strategies depending on the database type:
@@ -101,6 +111,61 @@
 //!   
 //! This specialized binary format in the byte databases provides substantial performance
 //! improvements over the generic encoding, particularly for key decoding operations.
+//!
+//! ### Unified Layout
+//! By default each logical database uses two heed databases: `<name>` for the default
+//! scope and `<name>_scoped` for named scopes. Databases built with the `unified()`
+//! option instead keep every entry in a single `<name>_unified` database and encode
+//! default-scope keys like named-scope keys, using the reserved `DEFAULT_SCOPE_HASH`.
+//! The two layouts are not interchangeable, so the choice is made when the database is
+//! first created.
+//!
+//! ### Ordered Keys Database (`ScopedOrderedDatabase<K,V>`)
+//! Created with the `ordered_keys()` builder option. Every entry lives in a single
+//! `<name>_ordered` database, with keys stored as
+//! ```text
+//! [scope_hash_be: 4 bytes][SortableKey encoding]
+//! ```
+//! and default-scope keys using `DEFAULT_SCOPE_HASH`, so range queries follow the
+//! logical order of integer and string keys.
+//!
+//! ### Byte Order
+//! The scope hash prefix is part of the on-disk format and will not change. The generic
+//! and bytes layouts write it little-endian (bincode encodes `ScopedKey::scope_hash` the
+//! same way), so both start every key of a scope with `ScopedBytesCodec::hash_prefix`.
+//! Each scope therefore occupies one contiguous run of keys, but runs are ordered by
+//! the prefix bytes rather than by the numeric hash: the scope after `hash` is not
+//! `hash + 1`, and scope bounds are computed as the lexicographic successor of the
+//! prefix instead. Only the ordered layout writes the hash big-endian.
+//!
+//! ## Thread Safety
+//!
+//! All database types hold only heed database handles and an `Arc<GlobalScopeRegistry>`,
+//! so they are `Send + Sync` whenever their key and value types are. They can be shared
+//! across threads by reference (e.g. with `std::thread::scope`) or cloned cheaply into
+//! each thread; wrapping them in an outer `Arc` is not required.
+//!
+//! A database over a type that is not `Sync` is not `Sync` either:
+//!
+//! ```compile_fail
+//! use scoped_heed::ScopedDatabase;
+//! use std::cell::Cell;
+//!
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<ScopedDatabase<String, Cell<u32>>>();
+//! ```
+//!
+//! ## Async Runtimes
+//!
+//! LMDB calls block the calling thread. With the `tokio` feature, `AsyncScopedDatabase`
+//! wraps any database type and runs its operations on tokio's blocking thread pool, so
+//! async services do not stall their executor.
+//!
+//! ## JSON Dumps
+//!
+//! With the `json` feature, `ScopedDatabase::dump_ndjson` writes every scope as
+//! newline-delimited JSON and `load_ndjson` reads such a dump back, e.g. to migrate
+//! data across incompatible on-disk versions.
 
 use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
@@ -120,39 +185,161 @@ pub type BytesIterResult<'txn> = Result<
     ScopedDbError,
 >;
 
+/// Iterator result type for the scopes listed in the global registry
+pub type ScopeIterResult<'txn> =
+    Result<Box<dyn Iterator<Item = Result<Scope, ScopedDbError>> + 'txn>, ScopedDbError>;
+
+/// Iterator result type for the scope hashes stored in a database
+pub type ScopeHashIterResult<'txn> =
+    Result<Box<dyn Iterator<Item = Result<u32, ScopedDbError>> + 'txn>, ScopedDbError>;
+
+/// Iterator result type for generic database operations that also surface the stored
+/// scope hash (`None` for the default scope)
+pub type HashIterResult<'txn, K, V> = Result<
+    Box<dyn Iterator<Item = Result<(Option<u32>, K, V), ScopedDbError>> + 'txn>,
+    ScopedDbError,
+>;
+
+/// Iterator result type for bytes key database operations that also surface the stored
+/// scope hash (`None` for the default scope)
+pub type BytesKeyHashIterResult<'txn, V> = Result<
+    Box<dyn Iterator<Item = Result<(Option<u32>, &'txn [u8], V), ScopedDbError>> + 'txn>,
+    ScopedDbError,
+>;
+
+/// Iterator result type for bytes database operations that also surface the stored
+/// scope hash (`None` for the default scope)
+pub type BytesHashIterResult<'txn> = Result<
+    Box<dyn Iterator<Item = Result<(Option<u32>, &'txn [u8], &'txn [u8]), ScopedDbError>> + 'txn>,
+    ScopedDbError,
+>;
+
+/// Iterator result type for generic database operations spanning several scopes, tagging
+/// each entry with its scope
+pub type MultiScopeIterResult<'txn, K, V> =
+    Result<Box<dyn Iterator<Item = Result<(Scope, K, V), ScopedDbError>> + 'txn>, ScopedDbError>;
+
+/// Iterator result type for bytes key database operations spanning several scopes,
+/// tagging each entry with its scope
+pub type BytesKeyMultiScopeIterResult<'txn, V> = Result<
+    Box<dyn Iterator<Item = Result<(Scope, &'txn [u8], V), ScopedDbError>> + 'txn>,
+    ScopedDbError,
+>;
+
+/// Iterator result type for bytes database operations spanning several scopes, tagging
+/// each entry with its scope
+pub type BytesMultiScopeIterResult<'txn> = Result<
+    Box<dyn Iterator<Item = Result<(Scope, &'txn [u8], &'txn [u8]), ScopedDbError>> + 'txn>,
+    ScopedDbError,
+>;
+
+/// Iterator result type for the values stored under a single key of a multi-value database
+pub type BytesValueIterResult<'txn> =
+    Result<Box<dyn Iterator<Item = Result<&'txn [u8], ScopedDbError>> + 'txn>, ScopedDbError>;
+
+/// Single entry result type for bytes database lookups such as `ceiling` and `floor`
+pub type BytesEntryResult<'txn> = Result<Option<(&'txn [u8], &'txn [u8])>, ScopedDbError>;
+
+#[cfg(feature = "tokio")]
+pub mod async_database;
 pub mod builder;
 pub mod global_registry;
+pub mod maintenance;
+pub mod observer;
 pub mod scope;
+pub mod scope_cache;
 pub mod scoped_bytes_database;
 pub mod scoped_bytes_key_database;
+pub mod scoped_counter_database;
 pub mod scoped_database;
+pub mod scoped_env;
+pub mod scoped_multi_bytes_database;
+pub mod scoped_ordered_database;
+pub mod scoped_tuple_key_database;
+pub mod scoped_view;
+pub mod snapshot;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod typed_scope;
 pub mod utils;
 
-pub use builder::scoped_database_options;
-pub use global_registry::{GlobalScopeRegistry, ScopeEmptinessChecker};
-pub use scope::Scope;
+#[cfg(feature = "tokio")]
+pub use async_database::AsyncScopedDatabase;
+pub use builder::{scoped_database_options, standalone_database_options};
+pub use global_registry::{
+    GlobalScopeRegistry, RegistryAudit, RegistryOptions, ScopeEmptinessChecker,
+    ScopeHashEnumerator, ScopedClear, clear_scope_everywhere,
+};
+pub use observer::OperationObserver;
+pub use scope::{DEFAULT_SCOPE_HASH, Scope, ScopeNameRules};
+pub use scope_cache::ScopeInterner;
 pub use scoped_bytes_database::ScopedBytesDatabase;
 pub use scoped_bytes_key_database::ScopedBytesKeyDatabase;
-pub use scoped_database::ScopedDatabase;
-pub use utils::{HeedRangeAdapter, ScopedBytesCodec};
+pub use scoped_counter_database::{
+    CounterValue, ScopedCounterDatabase, ScopedI64Database, ScopedU64Database,
+};
+pub use scoped_database::{Entry, ScopedDatabase};
+pub use scoped_env::ScopedEnv;
+pub use scoped_multi_bytes_database::ScopedMultiBytesDatabase;
+pub use scoped_ordered_database::ScopedOrderedDatabase;
+pub use scoped_tuple_key_database::ScopedTupleKeyDatabase;
+pub use scoped_view::{ScopedReadView, ScopedView};
+pub use snapshot::{ScopeDiff, ScopeSnapshot, diff_snapshots};
+pub use typed_scope::{TypedScope, TypedScoped};
+pub use utils::{
+    HeedRangeAdapter, OrderPreservingKey, OrderedKey, ScopedBytesCodec, SortableKey, with_child_txn,
+};
 
 /// Tuple type for scoped keys: (scope_hash, original_key)
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// `Ord` compares `scope_hash` first and `key` second, like the `(scope_hash, key)`
+/// tuple, so sorting collected keys groups them by scope. This is the logical order in
+/// which entries are grouped, not the byte order LMDB uses for the encoded keys: the
+/// hash is encoded little-endian, so scopes are contiguous in the database but do not
+/// follow numeric hash order, and keys within a scope follow their encoded bytes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ScopedKey<K> {
     pub scope_hash: u32,
     pub key: K,
 }
 
+/// A single write of a batch applied with `ScopedBytesDatabase::apply_batch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOp<K, V> {
+    /// Insert `key` with the value, overwriting any previous value
+    Put(K, V),
+    /// Remove `key` if it is present
+    Delete(K),
+}
+
+impl<K, V> BatchOp<K, V> {
+    /// The key written by this operation
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Put(key, _) | Self::Delete(key) => key,
+        }
+    }
+}
+
+/// Batch operation on raw byte keys and values, see `ScopedBytesDatabase::apply_batch`
+pub type BytesBatchOp = BatchOp<Vec<u8>, Vec<u8>>;
+
 /// Error type for scoped database operations.
 #[derive(Debug)]
 pub enum ScopedDbError {
+    /// Error reported by LMDB or heed, such as I/O failures or a full map.
+    ///
+    /// Key and value codec failures are reported as `Encoding` instead.
     Heed(heed::Error),
     /// Attempted to use an empty string as a scope name, which is disallowed.
     EmptyScopeDisallowed,
     /// Other input validation errors.
     InvalidInput(String),
-    /// Encoding error
+    /// A key or value could not be encoded or decoded, e.g. because its `Serialize`
+    /// implementation failed or the stored bytes do not match the type.
     Encoding(String),
+    /// A key that was expected to be present does not exist, see `get_expect`.
+    NotFound,
 }
 
 impl fmt::Display for ScopedDbError {
@@ -167,6 +354,7 @@ impl fmt::Display for ScopedDbError {
             }
             ScopedDbError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             ScopedDbError::Encoding(msg) => write!(f, "Encoding error: {}", msg),
+            ScopedDbError::NotFound => write!(f, "Key not found"),
         }
     }
 }
@@ -175,7 +363,11 @@ impl StdError for ScopedDbError {}
 
 impl From<heed::Error> for ScopedDbError {
     fn from(error: heed::Error) -> Self {
-        ScopedDbError::Heed(error)
+        match error {
+            heed::Error::Encoding(e) => ScopedDbError::Encoding(format!("Failed to encode: {}", e)),
+            heed::Error::Decoding(e) => ScopedDbError::Encoding(format!("Failed to decode: {}", e)),
+            error => ScopedDbError::Heed(error),
+        }
     }
 }
 
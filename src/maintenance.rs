@@ -0,0 +1,299 @@
+//! Environment-level maintenance helpers.
+//!
+//! These operate on the whole LMDB environment rather than on individual scoped
+//! databases, so they cover every database, scope and the global scope registry at once.
+
+use crate::ScopedDbError;
+use heed::types::Bytes;
+use heed::{CompactionOption, Database as HeedDatabase, DatabaseFlags, Env, EnvFlags, RoTxn};
+use std::fs;
+use std::ops::Bound;
+use std::path::Path;
+
+/// File name LMDB uses for the data file inside an environment directory
+const DATA_FILE_NAME: &str = "data.mdb";
+
+/// Suffixes appended to a database name to form the names of its heed databases, for
+/// the two-database layout (`""`, `"_scoped"`), the unified layout and ordered databases
+const DATABASE_SUFFIXES: [&str; 4] = ["", "_scoped", "_unified", "_ordered"];
+
+/// Number of entries `rename_database` buffers before writing them to the new database
+const RENAME_BATCH_SIZE: usize = 1024;
+
+/// Writes a compacted copy of the environment into the directory `dest_path`.
+///
+/// LMDB files never shrink on their own: pages freed by deletes and `clear` are reused
+/// but not returned to the file system. The copy omits free pages and renumbers the rest,
+/// so it is usually smaller than the source. It contains every named database of the
+/// environment, i.e. the default and scoped databases of each scoped database as well as
+/// the global scope registry, so scopes and their names survive unchanged.
+///
+/// The copy is taken from a consistent read snapshot and can run while the environment
+/// is in use, although writers keep going during the copy and their changes are not
+/// included. `dest_path` is created if needed and the data is written to `data.mdb`
+/// inside it, so the result can be opened like any environment directory. The call fails
+/// if `data.mdb` already exists there. Compaction fails if the environment has leaked
+/// pages.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{maintenance, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// maintenance::compact_to(&env, Path::new("/var/lib/app/db.compacted"))?;
+/// // Once no process uses the environment, swap the directories and reopen
+/// # Ok(())
+/// # }
+/// ```
+pub fn compact_to(env: &Env, dest_path: &Path) -> Result<(), ScopedDbError> {
+    fs::create_dir_all(dest_path).map_err(heed::Error::Io)?;
+    env.copy_to_file(dest_path.join(DATA_FILE_NAME), CompactionOption::Enabled)?;
+    Ok(())
+}
+
+/// Flushes the environment's data buffers to disk.
+///
+/// Committed scoped writes are durable once `commit` returns, unless the environment was
+/// opened with `EnvFlags::NO_SYNC`, `NO_META_SYNC` or `MAP_ASYNC`. Those flags trade
+/// durability for write throughput: a crash can lose the most recent commits, although
+/// the database itself stays consistent. Calling `sync` after a batch of commits makes
+/// everything committed so far durable, including the global scope registry, which is
+/// stored in the same environment.
+///
+/// With `force` set the flush always happens. Without it, an environment opened with
+/// `NO_SYNC` is left alone, matching LMDB's own `mdb_env_sync` semantics, so the call is
+/// cheap for environments that opted out of durability.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{maintenance, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// for batch in batches {
+///     let mut wtxn = env.write_txn()?;
+///     for (scope, key, value) in batch {
+///         db.put(&mut wtxn, &scope, key, value)?;
+///     }
+///     wtxn.commit()?;
+/// }
+/// maintenance::sync(&env, true)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn sync(env: &Env, force: bool) -> Result<(), ScopedDbError> {
+    if !force
+        && env
+            .flags()?
+            .is_some_and(|flags| flags.contains(EnvFlags::NO_SYNC))
+    {
+        return Ok(());
+    }
+    env.force_sync()?;
+    Ok(())
+}
+
+/// Grows the memory map so that at least `additional_bytes` more can be written.
+///
+/// The LMDB map size is fixed when the environment is opened, and a write that needs
+/// more fails with `MDB_MAP_FULL`. Calling `reserve` before a large import compares the
+/// space used by the last committed transaction with the map size and, when fewer than
+/// `additional_bytes` are left, grows the map to fit them, rounded up to whole pages.
+/// Free pages left by earlier deletes are not counted as available, so the estimate errs
+/// on the side of growing. Nothing happens when enough room is left.
+///
+/// The map size bounds the data file, which grows on demand, so a generous reservation
+/// costs address space rather than disk space.
+///
+/// Returns the map size after the call.
+///
+/// # Safety
+///
+/// This resizes the environment with `Env::resize` and has the same requirements: no
+/// transaction may be open on the environment in this process, including read
+/// transactions held by other threads. Other processes sharing the environment pick up
+/// the new size when they next resize or reopen it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{maintenance, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// // No transactions are open yet
+/// unsafe { maintenance::reserve(&env, 2 * 1024 * 1024 * 1024)? };
+/// let mut wtxn = env.write_txn()?;
+/// for (scope, key, value) in import {
+///     db.put(&mut wtxn, &scope, &key, &value)?;
+/// }
+/// wtxn.commit()?;
+/// # Ok(())
+/// # }
+/// ```
+pub unsafe fn reserve(env: &Env, additional_bytes: usize) -> Result<usize, ScopedDbError> {
+    let page_size = page_size(env)?;
+    let info = env.info();
+    let used = (info.last_page_number + 1).saturating_mul(page_size);
+    let required = used.saturating_add(additional_bytes);
+    if required <= info.map_size {
+        return Ok(info.map_size);
+    }
+
+    let new_size = required.div_ceil(page_size).saturating_mul(page_size);
+    // Safety: The caller guarantees that no transaction is open
+    unsafe { env.resize(new_size)? };
+    Ok(env.info().map_size)
+}
+
+/// The page size of the environment, read from the stat of its main database
+pub(crate) fn page_size(env: &Env) -> Result<usize, ScopedDbError> {
+    let rtxn = env.read_txn()?;
+    let main_db = env
+        .open_database::<Bytes, Bytes>(&rtxn, None)?
+        .ok_or_else(|| ScopedDbError::InvalidInput("Missing LMDB main database".into()))?;
+    Ok(main_db.stat(&rtxn)?.page_size as usize)
+}
+
+/// Renames a scoped database by copying its heed databases to the new name.
+///
+/// A scoped database named `name` is stored in heed databases derived from it: `name`
+/// and `{name}_scoped` for the two-database layout, `{name}_unified` for the unified
+/// layout and `{name}_ordered` for ordered databases. LMDB cannot rename a named
+/// database, so every one of them that exists is copied entry by entry, including all
+/// scopes, to the same suffix under `new_name`, and the old one is cleared. LMDB also
+/// cannot delete a named database, so the emptied old names remain in the environment
+/// and still count against `max_dbs`. Multi-value databases keep their `DUP_SORT` flag.
+/// Databases created with the builder's `heed_flags` option are not supported, as the
+/// copy does not carry over other flags.
+///
+/// The copy runs in a single write transaction that is committed at the end, so either
+/// the whole rename happens or nothing changes. It costs a full copy of the data, and
+/// the free pages left behind by the old names are only reused by later writes, so the
+/// file can temporarily grow to hold both copies; run `compact_to` afterwards to
+/// reclaim the space. The global scope registry is shared by all databases and needs no
+/// change. Database handles opened under the old name must not be used afterwards;
+/// reopen the database with the builder under `new_name`.
+///
+/// Returns the number of entries copied.
+///
+/// # Errors
+///
+/// Returns `ScopedDbError::InvalidInput` if the names are equal, if no heed database
+/// exists under `old_name`, or if one of the target names is already taken.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{maintenance, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// maintenance::rename_database(&env, "users", "accounts")?;
+/// let accounts = scoped_database_options(&env, registry.clone())
+///     .types::<String, User>()
+///     .name("accounts")
+///     .create(&mut env.write_txn()?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn rename_database(env: &Env, old_name: &str, new_name: &str) -> Result<u64, ScopedDbError> {
+    if old_name == new_name {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "Cannot rename database '{}' to itself",
+            old_name
+        )));
+    }
+
+    let mut wtxn = env.write_txn()?;
+    let mut renames = Vec::new();
+    for suffix in DATABASE_SUFFIXES {
+        let Some(source) =
+            env.open_database::<Bytes, Bytes>(&wtxn, Some(&format!("{old_name}{suffix}")))?
+        else {
+            continue;
+        };
+        let target_name = format!("{new_name}{suffix}");
+        if env
+            .open_database::<Bytes, Bytes>(&wtxn, Some(&target_name))?
+            .is_some()
+        {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Cannot rename database '{}': '{}' already exists",
+                old_name, target_name
+            )));
+        }
+        renames.push((source, target_name));
+    }
+    if renames.is_empty() {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "No database named '{}' exists",
+            old_name
+        )));
+    }
+
+    let mut copied = 0;
+    for (source, target_name) in renames {
+        if source.is_empty(&wtxn)? {
+            // Nothing to copy; the builder creates the target with the right flags
+            continue;
+        }
+        let flags = if is_dup_sort(&wtxn, source)? {
+            DatabaseFlags::DUP_SORT
+        } else {
+            DatabaseFlags::empty()
+        };
+        let target = env
+            .database_options()
+            .types::<Bytes, Bytes>()
+            .name(&target_name)
+            .flags(flags)
+            .create(&mut wtxn)?;
+
+        // Entries are buffered in batches because the source cannot be read while the
+        // same transaction writes. Batches only end between keys, so resuming after the
+        // last key never skips duplicates of it.
+        let mut last_key: Option<Vec<u8>> = None;
+        loop {
+            let start = match &last_key {
+                Some(key) => Bound::Excluded(key.as_slice()),
+                None => Bound::Unbounded,
+            };
+            let mut batch: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+            for result in source.range(&wtxn, &(start, Bound::Unbounded))? {
+                let (key, value) = result?;
+                if batch.len() >= RENAME_BATCH_SIZE
+                    && batch.last().is_some_and(|(last, _)| last.as_slice() != key)
+                {
+                    break;
+                }
+                batch.push((key.to_vec(), value.to_vec()));
+            }
+            let Some((key, _)) = batch.last() else {
+                break;
+            };
+            last_key = Some(key.clone());
+            for (key, value) in &batch {
+                target.put(&mut wtxn, key, value)?;
+            }
+            copied += batch.len() as u64;
+        }
+        source.clear(&mut wtxn)?;
+    }
+
+    wtxn.commit()?;
+    Ok(copied)
+}
+
+/// Whether `db` was created with `DUP_SORT`.
+///
+/// heed does not expose the flags of an open database, but LMDB refuses to move to the
+/// first duplicate of a key in databases without sorted duplicates.
+fn is_dup_sort(txn: &RoTxn, db: HeedDatabase<Bytes, Bytes>) -> Result<bool, ScopedDbError> {
+    let Some((key, _)) = db.first(txn)? else {
+        return Ok(false);
+    };
+    let Some(mut duplicates) = db.get_duplicates(txn, key)? else {
+        return Ok(false);
+    };
+    match duplicates.next() {
+        Some(Err(heed::Error::Mdb(heed::MdbError::Incompatible))) => Ok(false),
+        Some(Err(e)) => Err(e.into()),
+        _ => Ok(true),
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Scope;
+use std::fmt;
+
+/// Hooks invoked by database operations, e.g. to export per-scope metrics.
+///
+/// Attach an observer with the builder's `observer` option. Each successful `put`,
+/// `get`, `delete` and `iter` call (including the `_with_name` variants and calls made
+/// through views) invokes the matching hook with the scope passed by the caller. All
+/// hooks default to doing nothing, so implementations only override what they need.
+/// Databases without an observer only pay for an `Option` check.
+///
+/// Hooks run inline on the calling thread while the transaction is open, so they
+/// should be cheap, such as incrementing atomic counters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{OperationObserver, Scope};
+/// # use std::sync::atomic::{AtomicU64, Ordering};
+/// #[derive(Default)]
+/// struct Counters {
+///     reads: AtomicU64,
+///     misses: AtomicU64,
+/// }
+///
+/// impl OperationObserver for Counters {
+///     fn on_get(&self, _scope: &Scope, hit: bool) {
+///         self.reads.fetch_add(1, Ordering::Relaxed);
+///         if !hit {
+///             self.misses.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let counters = Arc::new(Counters::default());
+/// let db = scoped_database_options(&env, registry)
+///     .types::<String, String>()
+///     .name("users")
+///     .observer(counters.clone())
+///     .create(&mut wtxn)?;
+/// ```
+pub trait OperationObserver: Send + Sync {
+    /// Called after a value was written
+    fn on_put(&self, scope: &Scope) {
+        let _ = scope;
+    }
+
+    /// Called after a lookup; `hit` tells whether the key was found
+    fn on_get(&self, scope: &Scope, hit: bool) {
+        let _ = (scope, hit);
+    }
+
+    /// Called after a delete; `deleted` tells whether the key existed
+    fn on_delete(&self, scope: &Scope, deleted: bool) {
+        let _ = (scope, deleted);
+    }
+
+    /// Called when an iterator over a scope was created
+    fn on_iter(&self, scope: &Scope) {
+        let _ = scope;
+    }
+}
+
+impl fmt::Debug for dyn OperationObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OperationObserver")
+    }
+}
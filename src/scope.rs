@@ -1,4 +1,6 @@
 use crate::ScopedDbError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::borrow::Cow;
 use std::hash::Hasher;
 use twox_hash::XxHash32;
 
@@ -52,7 +54,12 @@ use twox_hash::XxHash32;
 /// When a hash collision occurs, you'll need to adjust one of the colliding scope names.
 /// This is a rare occurrence but important to understand if you're working with
 /// a very large number of scopes.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// # Ordering
+///
+/// Scopes are totally ordered so they can be kept in a `BTreeMap` or sorted for display:
+/// `Default` sorts before every named scope, and named scopes sort by name, then by hash.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Scope {
     /// The default (unscoped) database
     Default,
@@ -74,7 +81,10 @@ impl Scope {
     ///
     /// # Errors
     ///
-    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty.
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty, and
+    /// `ScopedDbError::InvalidInput` if the name hashes to `DEFAULT_SCOPE_HASH`, which is
+    /// reserved for the default scope. The check depends only on the name, so such a
+    /// name is rejected every time, before any database is touched.
     ///
     /// # Example
     ///
@@ -89,13 +99,65 @@ impl Scope {
             return Err(ScopedDbError::EmptyScopeDisallowed);
         }
 
-        let hash = compute_xxhash(name.as_bytes());
+        Self::named_with_hash(name, compute_xxhash(name.as_bytes()))
+    }
+
+    /// Build a named scope from a name and its hash, rejecting the reserved hash
+    fn named_with_hash(name: &str, hash: u32) -> Result<Self, ScopedDbError> {
+        if hash == DEFAULT_SCOPE_HASH {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Scope name '{}' hashes to {}, which is reserved for the default scope",
+                name, DEFAULT_SCOPE_HASH
+            )));
+        }
         Ok(Self::Named {
             name: name.to_string(),
             hash,
         })
     }
 
+    /// Create a scope from optional user input
+    ///
+    /// An empty name yields `Scope::Default`; any other name goes through `named`, so it
+    /// is subject to the same validation. Use `named` to reject empty names instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scoped_heed::Scope;
+    /// assert_eq!(Scope::try_named("").unwrap(), Scope::Default);
+    /// assert_eq!(Scope::try_named("tenant1").unwrap(), Scope::named("tenant1").unwrap());
+    /// ```
+    pub fn try_named(name: &str) -> Result<Self, ScopedDbError> {
+        if name.is_empty() {
+            Ok(Self::Default)
+        } else {
+            Self::named(name)
+        }
+    }
+
+    /// Create a named scope after checking the name against a set of rules
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty, or
+    /// `ScopedDbError::InvalidInput` naming the violated rule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scoped_heed::{Scope, ScopeNameRules};
+    /// let rules = ScopeNameRules::new()
+    ///     .max_len(64)
+    ///     .allowed_chars(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_:-".contains(c));
+    /// assert!(Scope::named_validated("tenant:42", &rules).is_ok());
+    /// assert!(Scope::named_validated("Tenant 42", &rules).is_err());
+    /// ```
+    pub fn named_validated(name: &str, rules: &ScopeNameRules) -> Result<Self, ScopedDbError> {
+        rules.validate(name)?;
+        Self::named(name)
+    }
+
     // Removed unused with_hash function
 
     /// Get the scope name if this is a named scope
@@ -120,6 +182,29 @@ impl Scope {
         }
     }
 
+    /// Check that the hash of a named scope is the hash of its name
+    ///
+    /// Scopes built with `Scope::named`, `From<&str>`, a registry or deserialization
+    /// are always consistent. A hand-built `Scope::Named` whose hash does not match its
+    /// name would read and write another scope's prefix; databases built with the
+    /// `verify_scope_hashes` option reject such scopes. The default scope is always
+    /// consistent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scoped_heed::Scope;
+    /// assert!(Scope::named("tenant1").unwrap().is_consistent());
+    /// let forged = Scope::Named { name: "tenant1".to_string(), hash: 42 };
+    /// assert!(!forged.is_consistent());
+    /// ```
+    pub fn is_consistent(&self) -> bool {
+        match self {
+            Self::Default => true,
+            Self::Named { name, hash } => *hash == compute_xxhash(name.as_bytes()),
+        }
+    }
+
     /// Check if this is the default scope
     ///
     /// Returns `true` for the default scope, `false` for named scopes.
@@ -127,17 +212,85 @@ impl Scope {
     pub fn is_default(&self) -> bool {
         matches!(self, Self::Default)
     }
+
+    /// The scope under which unified databases store default-scope entries
+    pub(crate) fn unified_default() -> Self {
+        Self::Named {
+            name: String::new(),
+            hash: DEFAULT_SCOPE_HASH,
+        }
+    }
+
+    /// Check if this is the storage scope of default-scope entries in unified databases
+    pub(crate) fn is_unified_default(&self) -> bool {
+        matches!(self, Self::Named { name, hash } if name.is_empty() && *hash == DEFAULT_SCOPE_HASH)
+    }
+
+    /// Reject a named scope whose hash is not the hash of its name
+    pub(crate) fn check_hash(&self) -> Result<(), ScopedDbError> {
+        match self {
+            Self::Named { name, hash } if !self.is_consistent() => {
+                Err(ScopedDbError::InvalidInput(format!(
+                    "Scope '{}' carries hash {:#010x}, but its name hashes to {:#010x}",
+                    name,
+                    hash,
+                    compute_xxhash(name.as_bytes())
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject the default scope, for databases built with `deny_default_scope`
+    pub(crate) fn check_not_default(&self) -> Result<(), ScopedDbError> {
+        match self {
+            Self::Default => Err(ScopedDbError::InvalidInput(
+                "default scope is disabled for this database".into(),
+            )),
+            Self::Named { .. } => Ok(()),
+        }
+    }
+
+    /// Resolve the scope whose key prefix holds this scope's entries
+    ///
+    /// In unified databases the default scope is stored under `DEFAULT_SCOPE_HASH`,
+    /// so named scopes whose hash equals the reserved value cannot be stored there.
+    pub(crate) fn storage_scope(&self, unified: bool) -> Result<Cow<'_, Scope>, ScopedDbError> {
+        if !unified {
+            return Ok(Cow::Borrowed(self));
+        }
+        match self {
+            Self::Default => Ok(Cow::Owned(Self::unified_default())),
+            Self::Named { name, hash } if *hash == DEFAULT_SCOPE_HASH && !name.is_empty() => {
+                Err(ScopedDbError::InvalidInput(format!(
+                    "Scope '{}' hashes to {}, which unified databases reserve for the default scope",
+                    name, DEFAULT_SCOPE_HASH
+                )))
+            }
+            Self::Named { .. } => Ok(Cow::Borrowed(self)),
+        }
+    }
 }
 
+/// Converts a name into a scope, with an empty name meaning the default scope.
+///
+/// The empty name is the only input that maps to `Scope::Default`; every other name
+/// becomes `Scope::Named` with exactly that name and its hash, and no validation runs
+/// here. Naming rules are applied when the scope is registered, so an invalid name is
+/// rejected on write instead of being silently redirected to the shared default scope.
+///
+/// The conversion cannot fail, so unlike `Scope::named` it does not reject a name that
+/// hashes to `DEFAULT_SCOPE_HASH`: such a scope keeps the reserved hash, and unified
+/// databases reject it when it is used. Use `Scope::try_named` to catch it up front.
 impl From<&str> for Scope {
     #[inline]
     fn from(name: &str) -> Self {
         if name.is_empty() {
             Self::Default
         } else {
-            match Self::named(name) {
-                Ok(scope) => scope,
-                Err(_) => Self::Default, // This shouldn't happen for non-empty strings
+            Self::Named {
+                name: name.to_string(),
+                hash: compute_xxhash(name.as_bytes()),
             }
         }
     }
@@ -153,6 +306,95 @@ impl<'a> From<Option<&'a str>> for Scope {
     }
 }
 
+/// Opt-in naming rules for scope names
+///
+/// By default only empty names are rejected. A `ScopeNameRules` value adds further
+/// checks, and can be used either directly through `Scope::named_validated` or by
+/// attaching it to a `GlobalScopeRegistry` so that every `register_scope` call
+/// (including the implicit ones made by write operations) enforces it.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeNameRules {
+    max_len: Option<usize>,
+    allowed_chars: Option<fn(char) -> bool>,
+}
+
+impl ScopeNameRules {
+    /// Create a rule set that only rejects empty names
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject names longer than `max_len` bytes
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Reject names containing any character for which `allowed` returns `false`
+    pub fn allowed_chars(mut self, allowed: fn(char) -> bool) -> Self {
+        self.allowed_chars = Some(allowed);
+        self
+    }
+
+    /// Check a scope name against these rules
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` for an empty name and
+    /// `ScopedDbError::InvalidInput` describing the first violated rule otherwise.
+    pub fn validate(&self, name: &str) -> Result<(), ScopedDbError> {
+        if name.is_empty() {
+            return Err(ScopedDbError::EmptyScopeDisallowed);
+        }
+
+        if let Some(max_len) = self.max_len
+            && name.len() > max_len
+        {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Scope name '{}' exceeds the maximum length of {} bytes",
+                name, max_len
+            )));
+        }
+
+        if let Some(allowed) = self.allowed_chars
+            && let Some(c) = name.chars().find(|c| !allowed(*c))
+        {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Scope name '{}' contains disallowed character '{}'",
+                name, c
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Scopes serialize as an optional name: `None` for the default scope and the scope
+/// name otherwise. The hash is not part of the serialized form; it is recomputed from
+/// the name on deserialization, so a stale or tampered hash can never desync from it.
+impl Serialize for Scope {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.name().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scope {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            None => Ok(Self::Default),
+            Some(name) => Self::named(&name).map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Scope hash reserved for the default scope
+///
+/// Hash-prefixed code paths use it to treat the default scope like a named one, e.g.
+/// databases created with the builder's `unified()` option keep default-scope entries
+/// in the same heed database as named scopes, prefixed with this hash. `Scope::named`
+/// rejects names that hash to it, so no named scope can share the prefix.
+pub const DEFAULT_SCOPE_HASH: u32 = 0;
+
 /// Compute a 32-bit xxHash value for the given bytes
 ///
 /// This uses the xxHash32 algorithm, which is significantly faster than
@@ -199,6 +441,24 @@ mod tests {
 
         let default: Scope = "".into();
         assert!(default.is_default());
+
+        // Only the empty name maps to the default scope; names that naming rules would
+        // reject stay named and keep their exact spelling
+        let rules = ScopeNameRules::new()
+            .max_len(4)
+            .allowed_chars(|c| c.is_ascii_lowercase());
+        for name in [" ", "Tenant", "tenant 1", "a/b", "\0", "much too long"] {
+            let scope: Scope = name.into();
+            assert_eq!(
+                scope,
+                Scope::Named {
+                    name: name.to_string(),
+                    hash: compute_xxhash(name.as_bytes()),
+                }
+            );
+            assert_eq!(scope, Scope::try_named(name).unwrap());
+            assert!(Scope::named_validated(name, &rules).is_err());
+        }
     }
 
     #[test]
@@ -222,5 +482,123 @@ mod tests {
         assert_ne!(hash1, hash3);
     }
 
+    #[test]
+    fn test_scope_name_rules() {
+        let rules = ScopeNameRules::new()
+            .max_len(8)
+            .allowed_chars(|c| c.is_ascii_lowercase() || c == '_');
+
+        assert!(Scope::named_validated("tenant_a", &rules).is_ok());
+        assert!(matches!(
+            Scope::named_validated("", &rules),
+            Err(ScopedDbError::EmptyScopeDisallowed)
+        ));
+        assert!(matches!(
+            Scope::named_validated("tenant_abc", &rules),
+            Err(ScopedDbError::InvalidInput(msg)) if msg.contains("maximum length")
+        ));
+        assert!(matches!(
+            Scope::named_validated("Tenant", &rules),
+            Err(ScopedDbError::InvalidInput(msg)) if msg.contains("'T'")
+        ));
+
+        // Default rules only reject empty names
+        assert!(ScopeNameRules::new().validate("Any Name!").is_ok());
+    }
+
+    #[test]
+    fn test_scope_serde_roundtrip() {
+        let named = Scope::named("tenant").unwrap();
+        let bytes = bincode::serialize(&named).unwrap();
+        assert_eq!(bincode::deserialize::<Scope>(&bytes).unwrap(), named);
+
+        let bytes = bincode::serialize(&Scope::Default).unwrap();
+        assert_eq!(bytes, vec![0]);
+        assert_eq!(
+            bincode::deserialize::<Scope>(&bytes).unwrap(),
+            Scope::Default
+        );
+
+        // The hash is recomputed from the name rather than trusted
+        let tampered = Scope::Named {
+            name: "tenant".to_string(),
+            hash: 42,
+        };
+        let bytes = bincode::serialize(&tampered).unwrap();
+        assert_eq!(bincode::deserialize::<Scope>(&bytes).unwrap(), named);
+
+        // Empty names are rejected like in Scope::named
+        let bytes = bincode::serialize(&Some("")).unwrap();
+        assert!(bincode::deserialize::<Scope>(&bytes).is_err());
+    }
+
     // Test for with_hash removed since the function is no longer used
+
+    #[test]
+    fn test_scope_ordering() {
+        let mut scopes = vec![
+            Scope::named("beta").unwrap(),
+            Scope::Default,
+            Scope::named("alpha").unwrap(),
+            Scope::Named {
+                name: "alpha".to_string(),
+                hash: 0,
+            },
+        ];
+        scopes.sort();
+
+        assert_eq!(scopes[0], Scope::Default);
+        assert_eq!(scopes[1].hash(), Some(0));
+        assert_eq!(scopes[2], Scope::named("alpha").unwrap());
+        assert_eq!(scopes[3], Scope::named("beta").unwrap());
+
+        let set: std::collections::BTreeSet<Scope> = scopes.into_iter().collect();
+        assert_eq!(set.len(), 4);
+    }
+
+    #[test]
+    fn test_reserved_hash_rejected() {
+        // No short name is known to hash to the reserved value, so exercise the check
+        // with the hash supplied directly
+        assert!(matches!(
+            Scope::named_with_hash("colliding", DEFAULT_SCOPE_HASH),
+            Err(ScopedDbError::InvalidInput(_))
+        ));
+        assert_eq!(
+            Scope::named_with_hash("tenant", compute_xxhash(b"tenant")).unwrap(),
+            Scope::named("tenant").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scope_is_consistent() {
+        assert!(Scope::Default.is_consistent());
+        assert!(Scope::named("tenant").unwrap().is_consistent());
+        assert!(Scope::from("tenant").is_consistent());
+
+        let forged = Scope::Named {
+            name: "tenant".to_string(),
+            hash: compute_xxhash(b"other"),
+        };
+        assert!(!forged.is_consistent());
+        assert!(matches!(
+            forged.check_hash(),
+            Err(ScopedDbError::InvalidInput(_))
+        ));
+        assert!(Scope::named("tenant").unwrap().check_hash().is_ok());
+    }
+
+    #[test]
+    fn test_scope_try_named() {
+        assert_eq!(Scope::try_named("").unwrap(), Scope::Default);
+        assert_eq!(
+            Scope::try_named("tenant1").unwrap(),
+            Scope::named("tenant1").unwrap()
+        );
+        // The strict constructor keeps rejecting empty names
+        assert!(matches!(
+            Scope::named(""),
+            Err(ScopedDbError::EmptyScopeDisallowed)
+        ));
+    }
 }
@@ -0,0 +1,136 @@
+//! Caching of parsed scopes.
+//!
+//! Servers typically build a `Scope` from a tenant name on every request, which
+//! allocates the name and hashes it each time even though the same few tenants recur.
+//! `ScopeInterner` keeps one shared `Scope` per name so repeated lookups only cost a
+//! map lookup and a reference count increment.
+
+use crate::{Scope, ScopedDbError};
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A concurrent cache from scope names to shared `Scope` values.
+///
+/// `intern` validates and hashes a name the first time it is seen and hands out clones
+/// of the same `Arc<Scope>` afterwards. `&Arc<Scope>` coerces to `&Scope`, so interned
+/// scopes can be passed to every database method directly. The interner is `Send + Sync`
+/// and is meant to be shared, e.g. in an `Arc` or a `static`.
+///
+/// Entries are never evicted. Call `clear` periodically if the set of names is unbounded,
+/// e.g. when names come straight from untrusted input.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{ScopeInterner, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let interner = ScopeInterner::new();
+///
+/// // Per request
+/// let scope = interner.intern(tenant_name)?;
+/// let value = db.get(&rtxn, &scope, &key)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ScopeInterner {
+    scopes: RwLock<HashMap<String, Arc<Scope>>>,
+}
+
+impl ScopeInterner {
+    /// Create an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the shared scope for `name`, building and caching it on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty, like
+    /// `Scope::named`. Failed names are not cached.
+    pub fn intern(&self, name: &str) -> Result<Arc<Scope>, ScopedDbError> {
+        if let Some(scope) = self
+            .scopes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+        {
+            return Ok(Arc::clone(scope));
+        }
+
+        let scope = Arc::new(Scope::named(name)?);
+        let mut scopes = self.scopes.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have interned the same name in the meantime
+        let scope = scopes.entry(name.to_owned()).or_insert(scope);
+        Ok(Arc::clone(scope))
+    }
+
+    /// Number of cached scopes
+    pub fn len(&self) -> usize {
+        self.scopes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no scope is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached scopes. Scopes handed out earlier stay valid.
+    pub fn clear(&self) {
+        self.scopes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_intern_reuses_scopes() {
+        let interner = ScopeInterner::new();
+        assert!(interner.is_empty());
+
+        let first = interner.intern("tenant1").unwrap();
+        let second = interner.intern("tenant1").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(*first, Scope::named("tenant1").unwrap());
+
+        let other = interner.intern("tenant2").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(interner.len(), 2);
+
+        assert!(matches!(
+            interner.intern(""),
+            Err(ScopedDbError::EmptyScopeDisallowed)
+        ));
+        assert_eq!(interner.len(), 2);
+
+        interner.clear();
+        assert!(interner.is_empty());
+        assert_eq!(*first, Scope::named("tenant1").unwrap());
+    }
+
+    #[test]
+    fn test_intern_concurrently() {
+        let interner = Arc::new(ScopeInterner::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || interner.intern("shared").unwrap())
+            })
+            .collect();
+        let scopes: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(interner.len(), 1);
+        let cached = interner.intern("shared").unwrap();
+        assert!(scopes.iter().all(|scope| Arc::ptr_eq(scope, &cached)));
+    }
+}
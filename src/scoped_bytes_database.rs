@@ -1,10 +1,23 @@
-use heed::types::Bytes;
-use heed::{Database as HeedDatabase, Env, RoTxn, RwTxn};
-use std::ops::RangeBounds;
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{Database as HeedDatabase, DatabaseFlags, DatabaseStat, Env, RoTxn, RwTxn};
+use std::borrow::Cow;
+use std::ops::{Bound, RangeBounds};
 use std::sync::Arc;
 
-use crate::global_registry::{GlobalScopeRegistry, ScopeEmptinessChecker};
-use crate::{BytesIterResult, Scope, ScopedBytesCodec, ScopedDbError, utils::HeedRangeAdapter};
+use crate::global_registry::{
+    GlobalScopeRegistry, ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear,
+};
+use crate::observer::OperationObserver;
+use crate::scoped_view::{ScopedReadView, ScopedView};
+use crate::snapshot::ScopeSnapshot;
+#[cfg(feature = "test-util")]
+use crate::snapshot::{ScopeDiff, diff_snapshots};
+use crate::utils::{self, HeedRangeAdapter, Neighbor};
+use crate::{
+    BatchOp, BytesBatchOp, BytesEntryResult, BytesHashIterResult, BytesIterResult,
+    BytesMultiScopeIterResult, DEFAULT_SCOPE_HASH, Scope, ScopeHashIterResult, ScopedBytesCodec,
+    ScopedDbError,
+};
 
 /// Maximum performance scoped database for pure byte operations with Redis-like isolation.
 ///
@@ -16,6 +29,14 @@ pub struct ScopedBytesDatabase {
     db_scoped: HeedDatabase<ScopedBytesCodec, Bytes>,
     db_default: HeedDatabase<Bytes, Bytes>,
     global_registry: Arc<GlobalScopeRegistry>,
+    env: Env,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    /// heed flags of the default-scope database, empty for unified databases
+    default_flags: DatabaseFlags,
 }
 
 impl ScopedBytesDatabase {
@@ -29,18 +50,47 @@ impl ScopedBytesDatabase {
         txn: &mut RwTxn,
         registry: Arc<GlobalScopeRegistry>,
         use_unnamed_for_default: bool,
+        unified: bool,
+        default_flags: DatabaseFlags,
     ) -> Result<Self, ScopedDbError> {
+        if unified {
+            // A single database holds every scope; default-scope keys use the reserved hash
+            let db_scoped = env
+                .database_options()
+                .types::<ScopedBytesCodec, Bytes>()
+                .name(&format!("{}_unified", name))
+                .create(txn)?;
+
+            return Ok(Self {
+                db_scoped,
+                // Never read: default-scope operations are routed to `db_scoped`
+                db_default: db_scoped.remap_key_type::<Bytes>(),
+                global_registry: registry,
+                env: env.clone(),
+                unified,
+                observer: None,
+                require_registered_scopes: false,
+                verify_scope_hashes: false,
+                deny_default_scope: false,
+                default_flags: DatabaseFlags::empty(),
+            });
+        }
+
         // Create database names from base name
         let scoped_name = format!("{}_scoped", name);
 
         let db_default = if use_unnamed_for_default {
             // Use unnamed database for default scope (backward compatibility)
-            env.database_options().types::<Bytes, Bytes>().create(txn)?
+            env.database_options()
+                .types::<Bytes, Bytes>()
+                .flags(default_flags)
+                .create(txn)?
         } else {
             // Use named database for default scope
             env.database_options()
                 .types::<Bytes, Bytes>()
                 .name(name)
+                .flags(default_flags)
                 .create(txn)?
         };
 
@@ -54,9 +104,52 @@ impl ScopedBytesDatabase {
             db_scoped,
             db_default,
             global_registry: registry,
+            env: env.clone(),
+            unified,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            default_flags,
         })
     }
 
+    /// Attach the observer configured on the builder
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn OperationObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Reject writes to unregistered scopes instead of registering them
+    pub(crate) fn with_require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// Check the hash of every scope passed to this database
+    pub(crate) fn with_verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// Reject every operation on the default scope
+    pub(crate) fn with_deny_default_scope(mut self, deny: bool) -> Self {
+        self.deny_default_scope = deny;
+        self
+    }
+
+    /// Resolve the storage scope of `scope`, after the checks enabled on the builder:
+    /// `deny_default_scope` and `verify_scope_hashes`
+    fn storage_scope<'s>(&self, scope: &'s Scope) -> Result<Cow<'s, Scope>, ScopedDbError> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.verify_scope_hashes {
+            scope.check_hash()?;
+        }
+        scope.storage_scope(self.unified)
+    }
+
     /// Registers a scope in the global registry.
     ///
     /// This method is automatically called during write operations (put, delete, clear)
@@ -67,7 +160,10 @@ impl ScopedBytesDatabase {
     ///
     /// Returns an error if there's a hash collision between different scope names.
     pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
-        if let Scope::Named { name: _, hash: _ } = scope {
+        // The storage scope of unified default-scope entries is never registered
+        if let Scope::Named { .. } = scope
+            && !(self.unified && scope.is_unified_default())
+        {
             self.global_registry.register_scope(txn, scope)
         } else {
             // Default scope doesn't need registration
@@ -75,6 +171,27 @@ impl ScopedBytesDatabase {
         }
     }
 
+    /// Registers the scope of a write, or checks that it is already registered when the
+    /// database was built with `require_registered_scopes`
+    fn register_written_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        if !self.require_registered_scopes {
+            return self.register_scope(txn, scope);
+        }
+        if self.unified && scope.is_unified_default() {
+            return Ok(());
+        }
+        self.global_registry.ensure_registered(txn, scope)
+    }
+
+    /// Bytes the scope header adds to keys written to `scope`
+    fn key_overhead(&self, scope: &Scope) -> usize {
+        if self.unified || !scope.is_default() {
+            ScopedBytesCodec::HEADER_LEN
+        } else {
+            0
+        }
+    }
+
     /// Lists all known scopes in the database.
     ///
     /// Returns a list of all scopes that have been registered by this database,
@@ -107,10 +224,49 @@ impl ScopedBytesDatabase {
         self.global_registry.list_all_scopes(txn)
     }
 
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.global_registry.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns a view of this database bound to a single scope.
+    ///
+    /// The view's methods omit the scope argument and forward to this database.
+    pub fn scoped(&self, scope: Scope) -> ScopedView<'_, Self> {
+        ScopedView::new(self, scope)
+    }
+
+    /// Returns a read-only view of this database bound to a scope and a transaction.
+    ///
+    /// The view's methods omit both the transaction and the scope, see `ScopedReadView`.
+    pub fn read_view<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: Scope,
+    ) -> ScopedReadView<'_, 'txn, Self> {
+        ScopedReadView::new(self, txn, scope)
+    }
+
     /// Checks if a scope is empty (contains no data).
     ///
     /// This is a helper method used by find_empty_scopes.
     fn is_scope_empty(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
         match scope {
             Scope::Default => {
                 // Count entries in the default database
@@ -175,6 +331,147 @@ impl ScopedBytesDatabase {
         Ok(empty_count)
     }
 
+    /// Unregister the scopes that hold no data in this database.
+    ///
+    /// See `ScopedDatabase::prune_own_empty_scopes`.
+    pub fn prune_own_empty_scopes(&self, txn: &mut RwTxn) -> Result<usize, ScopedDbError> {
+        self.global_registry
+            .prune_globally_unused_scopes(txn, &[self])
+    }
+
+    /// Returns LMDB B-tree statistics for the underlying databases.
+    ///
+    /// The first element describes the default database and the second the scoped
+    /// database, which holds the entries of all named scopes in a single B-tree.
+    /// Useful for diagnostics such as total entry counts, tree depth and page usage.
+    /// In unified databases both elements describe the single underlying database.
+    pub fn heed_stats(&self, txn: &RoTxn) -> Result<(DatabaseStat, DatabaseStat), ScopedDbError> {
+        let default_stat = self.db_default.stat(txn)?;
+        let scoped_stat = self.db_scoped.stat(txn)?;
+        Ok((default_stat, scoped_stat))
+    }
+
+    /// Returns the number of entries in every scope of this database.
+    ///
+    /// The default scope comes first, followed by each named scope holding at least one
+    /// entry, ordered by hash. The scoped database is walked once without decoding values,
+    /// which is much cheaper than calling `len` per scope. Scope names are resolved through
+    /// the registry; hashes without a registered name are reported with a hexadecimal
+    /// placeholder name.
+    pub fn per_scope_counts(&self, txn: &RoTxn) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        // Unified databases count default-scope entries along with the named scopes
+        let default_count = if self.unified {
+            None
+        } else {
+            Some(self.db_default.len(txn)?)
+        };
+        utils::count_entries_per_scope(
+            self.db_scoped.remap_types::<Bytes, DecodeIgnore>(),
+            txn,
+            &self.global_registry,
+            default_count,
+        )
+    }
+
+    /// Returns the number of entries in every scope, sorted by entry count.
+    ///
+    /// See `ScopedDatabase::scopes_by_size`.
+    pub fn scopes_by_size(
+        &self,
+        txn: &RoTxn,
+        descending: bool,
+    ) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        let mut counts = self.per_scope_counts(txn)?;
+        utils::sort_by_count(&mut counts, descending);
+        Ok(counts)
+    }
+
+    /// Lists the distinct hashes of named scopes holding data in this database.
+    ///
+    /// Hashes are returned in ascending order. Each scope costs a single cursor seek, so
+    /// this is cheap even for large scopes. Unlike `list_scopes`, which reports the
+    /// registry, this reflects the data itself and also finds unregistered scopes.
+    pub fn distinct_scope_hashes(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        let mut hashes =
+            utils::distinct_scope_hashes(self.db_scoped.remap_types::<Bytes, DecodeIgnore>(), txn)?;
+        if self.unified {
+            // Default-scope entries are stored under the reserved hash
+            hashes.retain(|hash| *hash != DEFAULT_SCOPE_HASH);
+        }
+        Ok(hashes)
+    }
+
+    /// Lazily iterates over the hashes of named scopes holding data in this database.
+    ///
+    /// Hashes come in storage order, which follows the little-endian hash bytes. Each
+    /// step seeks straight past the current scope instead of reading its entries, so
+    /// callers can stop early without touching the rest of the database.
+    pub fn scope_hashes_present<'txn>(&self, txn: &'txn RoTxn<'txn>) -> ScopeHashIterResult<'txn> {
+        let unified = self.unified;
+        let iter =
+            utils::scope_hashes_present(self.db_scoped.remap_types::<Bytes, DecodeIgnore>(), txn)
+                // Default-scope entries of unified databases are stored under the reserved hash
+                .filter(move |result| !(unified && matches!(result, Ok(DEFAULT_SCOPE_HASH))));
+        Ok(Box::new(iter))
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    ///
+    /// Unlike `GlobalScopeRegistry::scope_exists`, which only consults the registry,
+    /// this looks at the data itself and stops at the first entry it finds.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        Ok(!self.is_scope_empty(txn, scope)?)
+    }
+
+    /// Returns the longest key that can be written to `scope`.
+    ///
+    /// LMDB limits keys to `Env::max_key_size` bytes (511 with the default build).
+    /// Keys of named scopes, and of every scope in the unified layout, carry the
+    /// `ScopedBytesCodec::HEADER_LEN`-byte scope header, which is subtracted here. Writes
+    /// with longer keys fail with `ScopedDbError::InvalidInput`.
+    pub fn max_key_len(&self, scope: &Scope) -> usize {
+        self.env
+            .max_key_size()
+            .saturating_sub(self.key_overhead(scope))
+    }
+
+    /// Returns how many bytes of key can be used in `scope`.
+    ///
+    /// Same as `max_key_len`. The limit is queried from the environment rather than
+    /// assumed to be 511, as it depends on how LMDB was built. Note that the scope
+    /// header of named scopes is 12 bytes, the 4-byte hash followed by the 8-byte key
+    /// length, so a named scope leaves 12 bytes less than the default scope.
+    pub fn usable_key_bytes(&self, scope: &Scope) -> usize {
+        self.max_key_len(scope)
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    ///
+    /// The handle is retained so that convenience methods can open their own
+    /// short-lived transactions; cloning an `Env` is cheap.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Whether default-scope entries share the scoped database.
+    ///
+    /// See the builder's `unified` option.
+    pub fn is_unified(&self) -> bool {
+        self.unified
+    }
+
+    /// Clear a scope in its own write transaction and commit it.
+    ///
+    /// Returns the number of entries removed. This must not be called while another
+    /// write transaction is open on the same thread; use `clear` to clear a scope as
+    /// part of a larger transaction.
+    pub fn clear_and_commit(&self, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let mut wtxn = self.env.write_txn()?;
+        let count = self.clear(&mut wtxn, scope)?;
+        wtxn.commit()?;
+        Ok(count)
+    }
+
     /// Insert a key-value pair into the database with a Scope enum.
     pub fn put(
         &self,
@@ -183,20 +480,30 @@ impl ScopedBytesDatabase {
         key: &[u8],
         value: &[u8],
     ) -> Result<(), ScopedDbError> {
-        match scope {
+        utils::check_key_size(&self.env, key.len(), self.key_overhead(scope))?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self
                 .db_default
                 .put(txn, key, value)
                 .map_err(ScopedDbError::from),
             Scope::Named { hash, .. } => {
                 // Register scope in global registry
-                self.register_scope(txn, scope)?;
+                self.register_written_scope(txn, scope)?;
 
                 self.db_scoped
                     .put(txn, &(*hash, key), value)
                     .map_err(ScopedDbError::from)
             }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
         }
+        result
     }
 
     /// Insert a key-value pair into the database with an Option<&str> scope name.
@@ -233,6 +540,217 @@ impl ScopedBytesDatabase {
         self.put(txn, &scope, key, value)
     }
 
+    /// Insert a key-value pair and return the value it replaced, if any.
+    ///
+    /// The key is encoded once and the same encoded key is used for reading the previous
+    /// value and for writing the new one, saving a separate `get`. The previous value is
+    /// copied because the write may invalidate the memory it was read from.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let previous = db.put_returning(&mut wtxn, &tenant, b"leader", b"node-2")?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn put_returning(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<Option<Vec<u8>>, ScopedDbError> {
+        utils::check_key_size(&self.env, key.len(), self.key_overhead(scope))?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let swap = |txn: &mut RwTxn<'_>, db: HeedDatabase<Bytes, Bytes>, key: &[u8]| {
+            let previous = db.get(txn, key)?.map(<[u8]>::to_vec);
+            db.put(txn, key, value)?;
+            Ok::<_, ScopedDbError>(previous)
+        };
+        let result = match scope {
+            Scope::Default => swap(txn, self.db_default, key),
+            Scope::Named { hash, .. } => {
+                // Register scope in global registry
+                self.register_written_scope(txn, scope)?;
+
+                let encoded = ScopedBytesCodec::encode(*hash, key);
+                swap(txn, self.db_scoped.remap_key_type::<Bytes>(), &encoded)
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
+        }
+        result
+    }
+
+    /// Replace the value of a key with a value computed from the current one.
+    ///
+    /// The scoped key is encoded once for both the read and the write. Returns the
+    /// value that was written.
+    pub(crate) fn update<T, F>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        f: F,
+    ) -> Result<T, ScopedDbError>
+    where
+        T: AsRef<[u8]>,
+        F: FnOnce(Option<&[u8]>) -> Result<T, ScopedDbError>,
+    {
+        utils::check_key_size(&self.env, key.len(), self.key_overhead(scope))?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let update = |txn: &mut RwTxn<'_>, db: HeedDatabase<Bytes, Bytes>, key: &[u8]| {
+            let value = f(db.get(txn, key)?)?;
+            db.put(txn, key, value.as_ref())?;
+            Ok::<_, ScopedDbError>(value)
+        };
+        let result = match scope {
+            Scope::Default => update(txn, self.db_default, key),
+            Scope::Named { hash, .. } => {
+                self.register_written_scope(txn, scope)?;
+
+                let encoded = ScopedBytesCodec::encode(*hash, key);
+                update(txn, self.db_scoped.remap_key_type::<Bytes>(), &encoded)
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
+        }
+        result
+    }
+
+    /// Allocate the next id of a per-scope sequence.
+    ///
+    /// The sequence is a counter stored under `counter_key` in `scope`; the first call
+    /// returns 1 and each later call returns one more than the previous. The read and the
+    /// write happen in `txn`, and LMDB allows a single write transaction at a time, so
+    /// concurrent callers never receive the same id. Ids handed out by a transaction that
+    /// is aborted are handed out again.
+    ///
+    /// The counter is stored as 8 native-endian bytes, like the values of
+    /// `ScopedU64Database`, so it can be read or seeded through that type. Keep sequence
+    /// keys apart from data keys, e.g. with a dedicated prefix such as `b"seq:orders"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if the sequence is exhausted, and
+    /// `ScopedDbError::Encoding` if `counter_key` holds a value that is not 8 bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut wtxn = env.write_txn()?;
+    /// let order_id = db.next_id(&mut wtxn, &tenant, b"seq:orders")?;
+    /// db.put(&mut wtxn, &tenant, format!("order:{order_id:020}").as_bytes(), &order)?;
+    /// wtxn.commit()?;
+    /// ```
+    pub fn next_id(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        counter_key: &[u8],
+    ) -> Result<u64, ScopedDbError> {
+        self.reserve_ids(txn, scope, counter_key, 1)
+    }
+
+    /// Allocate `n` consecutive ids of a per-scope sequence and return the first.
+    ///
+    /// The ids `first..first + n` belong to the caller, and the next allocation starts
+    /// after them. See `next_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if `n` is 0 or the sequence cannot provide
+    /// `n` more ids; the counter is left unchanged.
+    pub fn reserve_ids(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        counter_key: &[u8],
+        n: u64,
+    ) -> Result<u64, ScopedDbError> {
+        if n == 0 {
+            return Err(ScopedDbError::InvalidInput(
+                "reserve_ids requires at least one id".into(),
+            ));
+        }
+        let last = self.update(txn, scope, counter_key, |current| {
+            let current = match current {
+                Some(bytes) => u64::from_ne_bytes(bytes.try_into().map_err(|_| {
+                    ScopedDbError::Encoding(format!(
+                        "Expected an 8-byte sequence counter, found {} bytes",
+                        bytes.len()
+                    ))
+                })?),
+                None => 0,
+            };
+            let last = current.checked_add(n).ok_or_else(|| {
+                ScopedDbError::InvalidInput(format!(
+                    "Sequence at {} cannot provide {} more ids",
+                    current, n
+                ))
+            })?;
+            Ok(last.to_ne_bytes())
+        })?;
+        Ok(u64::from_ne_bytes(last) - n + 1)
+    }
+
+    /// Replace the value of a key only if it currently equals `expected`.
+    ///
+    /// `expected` and `new` use `None` for an absent key: `expected: None` only matches a
+    /// missing key, and `new: None` deletes the key instead of writing a value. Returns
+    /// whether the swap happened; on a mismatch nothing is written.
+    ///
+    /// LMDB allows a single write transaction at a time, so no other writer can change
+    /// the value between the comparison and the write.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// // Take the lock only if nobody holds it
+    /// let acquired = db.compare_and_swap(&mut wtxn, &tenant, b"lock", None, Some(b"worker-1"))?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compare_and_swap(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new: Option<&[u8]>,
+    ) -> Result<bool, ScopedDbError> {
+        if self.get(txn, scope, key)? != expected {
+            return Ok(false);
+        }
+        match new {
+            Some(value) => self.put(txn, scope, key, value)?,
+            None => {
+                self.delete(txn, scope, key)?;
+            }
+        }
+        Ok(true)
+    }
+
     /// Get a value from the database with a Scope enum.
     pub fn get<'txn>(
         &self,
@@ -240,13 +758,66 @@ impl ScopedBytesDatabase {
         scope: &Scope,
         key: &[u8],
     ) -> Result<Option<&'txn [u8]>, ScopedDbError> {
-        match scope {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self.db_default.get(txn, key).map_err(ScopedDbError::from),
             Scope::Named { hash, .. } => self
                 .db_scoped
                 .get(txn, &(*hash, key))
                 .map_err(ScopedDbError::from),
+        };
+        if let (Some(observer), Ok(value)) = (&self.observer, &result) {
+            observer.on_get(requested_scope, value.is_some());
+        }
+        result
+    }
+
+    /// Get a value that is expected to be present.
+    ///
+    /// See `ScopedDatabase::get_expect`.
+    pub fn get_expect<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> Result<&'txn [u8], ScopedDbError> {
+        self.get(txn, scope, key)?.ok_or(ScopedDbError::NotFound)
+    }
+
+    /// Check which of several keys exist in a scope.
+    ///
+    /// See `ScopedDatabase::contains_many`.
+    pub fn contains_many(
+        &self,
+        txn: &RoTxn,
+        scope: &Scope,
+        keys: &[&[u8]],
+    ) -> Result<Vec<bool>, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let mut present = Vec::with_capacity(keys.len());
+        for key in keys {
+            let found = match scope {
+                Scope::Default => self
+                    .db_default
+                    .remap_data_type::<DecodeIgnore>()
+                    .get(txn, key)?
+                    .is_some(),
+                Scope::Named { hash, .. } => self
+                    .db_scoped
+                    .remap_data_type::<DecodeIgnore>()
+                    .get(txn, &(*hash, *key))?
+                    .is_some(),
+            };
+            if let Some(observer) = &self.observer {
+                observer.on_get(requested_scope, found);
+            }
+            present.push(found);
         }
+        Ok(present)
     }
 
     /// Get a value from the database using an Option<&str> scope name.
@@ -281,6 +852,90 @@ impl ScopedBytesDatabase {
         self.get(txn, &scope, key)
     }
 
+    /// Returns the entry with the smallest key greater than or equal to `key` in a scope.
+    ///
+    /// Neighbors follow the order in which `iter` and `range` return keys. In the default
+    /// scope that is plain bytewise order; in named scopes the encoded key length precedes
+    /// the key bytes, so keys of equal length compare bytewise. The lookup is a single
+    /// cursor seek and never returns an entry from a neighboring scope.
+    pub fn ceiling<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> BytesEntryResult<'txn> {
+        self.neighbor(txn, scope, key, Neighbor::Ceiling)
+    }
+
+    /// Returns the entry with the smallest key strictly greater than `key` in a scope.
+    pub fn higher<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> BytesEntryResult<'txn> {
+        self.neighbor(txn, scope, key, Neighbor::Higher)
+    }
+
+    /// Returns the entry with the largest key less than or equal to `key` in a scope.
+    pub fn floor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> BytesEntryResult<'txn> {
+        self.neighbor(txn, scope, key, Neighbor::Floor)
+    }
+
+    /// Returns the entry with the largest key strictly less than `key` in a scope.
+    pub fn lower<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> BytesEntryResult<'txn> {
+        self.neighbor(txn, scope, key, Neighbor::Lower)
+    }
+
+    fn neighbor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+        neighbor: Neighbor,
+    ) -> BytesEntryResult<'txn> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                let bounds = neighbor.bounds(key);
+                let entry = if neighbor.is_forward() {
+                    self.db_default.range(txn, &bounds)?.next()
+                } else {
+                    self.db_default.rev_range(txn, &bounds)?.next()
+                };
+                entry.transpose().map_err(ScopedDbError::from)
+            }
+            Scope::Named { hash, .. } => {
+                let scoped_key = (*hash, key);
+                let bounds = neighbor.bounds(&scoped_key);
+                let entry = if neighbor.is_forward() {
+                    self.db_scoped.range(txn, &bounds)?.next()
+                } else {
+                    self.db_scoped.rev_range(txn, &bounds)?.next()
+                };
+
+                // The first entry past the probe may belong to an adjacent scope
+                match entry.transpose()? {
+                    Some(((found_hash, found_key), value)) if found_hash == *hash => {
+                        Ok(Some((found_key, value)))
+                    }
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
     /// Delete a key-value pair from the database with a Scope enum.
     pub fn delete(
         &self,
@@ -288,7 +943,10 @@ impl ScopedBytesDatabase {
         scope: &Scope,
         key: &[u8],
     ) -> Result<bool, ScopedDbError> {
-        match scope {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self
                 .db_default
                 .delete(txn, key)
@@ -297,7 +955,11 @@ impl ScopedBytesDatabase {
                 .db_scoped
                 .delete(txn, &(*hash, key))
                 .map_err(ScopedDbError::from),
+        };
+        if let (Some(observer), Ok(deleted)) = (&self.observer, &result) {
+            observer.on_delete(requested_scope, *deleted);
         }
+        result
     }
 
     /// Delete a key-value pair from the database using an Option<&str> scope name.
@@ -333,30 +995,142 @@ impl ScopedBytesDatabase {
         self.delete(txn, &scope, key)
     }
 
-    /// Clear all entries within a specific scope or the default database.
-    ///
-    /// This is a highly optimized operation that efficiently removes all data for a specific scope,
-    /// without affecting data in other scopes.
-    ///
-    /// # Performance
-    ///
-    /// This method uses LMDB's efficient `delete_range` operation to:
-    /// - Clear all entries with a matching scope hash in a single operation
-    /// - Avoid the O(N) cost of iterating and collecting keys before deletion
-    /// - Skip deserialization overhead for keys and values
+    /// Move the entry stored under `key` from one scope to another.
     ///
-    /// For large datasets, this provides orders of magnitude better performance compared
-    /// to iterating through entries one by one.
-    ///
-    /// # Special Cases
-    ///
-    /// - For the `Default` scope, this delegates to heed's built-in `clear` method
-    /// - For scopes with a hash of `u32::MAX`, special handling ensures all entries are properly cleared
+    /// Reads the value under `from`, deletes it there and writes it under `to` within
+    /// the supplied transaction, registering `to` like any other write. Returns whether
+    /// the key existed in `from`; nothing is written otherwise. An existing value under
+    /// the key in `to` is overwritten.
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// # use scoped_heed::{ScopedBytesDatabase, Scope, ScopedDbError};
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let mut wtxn = env.write_txn()?;
+    /// let moved = db.move_key(&mut wtxn, &Scope::named("acme")?, &Scope::named("globex")?, b"account-42")?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_key(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        to: &Scope,
+        key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        self.move_key_as(txn, from, key, to, key)
+    }
+
+    /// Move the entry stored under `from_key` in `from` to `to_key` in `to`.
+    ///
+    /// Like `move_key`, but the entry may also change its key. Moving an entry onto
+    /// itself leaves it in place and returns `true`.
+    pub fn move_key_as(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        from_key: &[u8],
+        to: &Scope,
+        to_key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        // The value borrows from the transaction, so copy it before deleting
+        let Some(value) = self.get(txn, from, from_key)?.map(<[u8]>::to_vec) else {
+            return Ok(false);
+        };
+        // Deleting first keeps the entry when source and destination are the same
+        self.delete(txn, from, from_key)?;
+        self.put(txn, to, to_key, &value)?;
+        Ok(true)
+    }
+
+    /// Delete all keys starting with a byte prefix within a scope.
+    ///
+    /// For the default scope this is a range deletion over the raw keys. For named
+    /// scopes the entries of the scope are scanned and matching keys are deleted, since
+    /// the scoped key encoding stores the key length ahead of the key bytes. Entries in
+    /// other scopes are never touched. An empty prefix removes every entry of the scope.
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` for the default scope when the database was
+    /// created with `heed_flags`, whose key order does not keep a prefix contiguous.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let removed = db.delete_prefix(&mut wtxn, &tenant, b"users/42/")?;
+    /// wtxn.commit()?;
+    /// ```
+    pub fn delete_prefix(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        prefix: &[u8],
+    ) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                utils::check_prefix_scan_flags(self.default_flags)?;
+                utils::delete_key_prefix(self.db_default.remap_types::<Bytes, Bytes>(), txn, prefix)
+            }
+            Scope::Named { hash, .. } => {
+                // Register the scope (ensures it's in the registry)
+                self.register_written_scope(txn, scope)?;
+
+                utils::delete_scoped_key_prefix(
+                    self.db_scoped.remap_types::<Bytes, Bytes>(),
+                    txn,
+                    *hash,
+                    prefix,
+                )
+            }
+        }
+    }
+
+    /// Delete all keys starting with a byte prefix using an Option<&str> scope name.
+    pub fn delete_prefix_with_name(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope_name: Option<&str>,
+        prefix: &[u8],
+    ) -> Result<usize, ScopedDbError> {
+        let scope = Scope::from(scope_name);
+        self.delete_prefix(txn, &scope, prefix)
+    }
+
+    /// Clear all entries within a specific scope or the default database.
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// This is a highly optimized operation that efficiently removes all data for a specific scope,
+    /// without affecting data in other scopes.
+    ///
+    /// # Performance
+    ///
+    /// This method uses LMDB's efficient `delete_range` operation to:
+    /// - Clear all entries with a matching scope hash in a single operation
+    /// - Avoid the O(N) cost of iterating and collecting keys before deletion
+    /// - Skip deserialization overhead for keys and values
+    ///
+    /// For large datasets, this provides orders of magnitude better performance compared
+    /// to iterating through entries one by one.
+    ///
+    /// # Special Cases
+    ///
+    /// - For the `Default` scope, this delegates to heed's built-in `clear` method
+    /// - For scopes with a hash of `u32::MAX`, whose prefix sorts last, the range extends to the end of the database
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use scoped_heed::{ScopedBytesDatabase, Scope, ScopedDbError};
     /// # use heed::EnvOpenOptions;
     /// # fn main() -> Result<(), ScopedDbError> {
     /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
@@ -371,12 +1145,18 @@ impl ScopedBytesDatabase {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<(), ScopedDbError> {
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
         match scope {
-            Scope::Default => self.db_default.clear(txn).map_err(ScopedDbError::from),
+            Scope::Default => {
+                let count = self.db_default.len(txn)? as usize;
+                self.db_default.clear(txn)?;
+                Ok(count)
+            }
             Scope::Named { hash, .. } => {
                 // Register the scope (ensures it's in the registry)
-                self.register_scope(txn, scope)?;
+                self.register_written_scope(txn, scope)?;
 
                 // Use delete_range to efficiently remove all keys with the specified hash prefix
                 // Create a range that covers all entries for this scope hash
@@ -385,25 +1165,22 @@ impl ScopedBytesDatabase {
                 // Start from the beginning of this scope (hash + empty key)
                 let start_bound = Bound::Included((*hash, &[][..]));
 
-                // End just before the next scope hash would begin, handling u32::MAX safely
-                let end_bound = if *hash == u32::MAX {
-                    // Special case - use maximum possible key value
-                    Bound::Included((*hash, &[0xFF][..]))
-                } else {
-                    // Normal case - use the next hash with empty key as exclusive upper bound
-                    Bound::Excluded((hash.wrapping_add(1), &[][..]))
+                // End just before the next scope's prefix, or at the end of the database
+                let end_bound = match utils::next_scope_bound(*hash) {
+                    Some(next_hash) => Bound::Excluded((next_hash, &[][..])),
+                    None => Bound::Unbounded,
                 };
 
                 let range = (start_bound, end_bound);
 
                 // Use delete_range which is much more efficient than collecting and deleting
-                self.db_scoped.delete_range(txn, &range)?;
+                let count = self.db_scoped.delete_range(txn, &range)?;
 
                 // Note: We don't unregister the scope here automatically
                 // That should be a separate operation as other databases might use the same scope
                 // The user can call unregister_scope manually if needed
 
-                Ok(())
+                Ok(count)
             }
         }
     }
@@ -435,14 +1212,211 @@ impl ScopedBytesDatabase {
         &self,
         txn: &mut RwTxn<'_>,
         scope_name: Option<&str>,
-    ) -> Result<(), ScopedDbError> {
+    ) -> Result<usize, ScopedDbError> {
         let scope = Scope::from(scope_name);
         self.clear(txn, &scope)
     }
 
+    /// Clear a scope and unregister it once no database holds its data anymore.
+    ///
+    /// This bundles tearing down a tenant in an application with a single database,
+    /// where `clear` alone leaves the scope registered until a prune pass. After clearing,
+    /// the scope is removed from the shared registry unless it still holds entries in one
+    /// of the databases in `also_empty_in`. Pass every other database sharing the registry
+    /// there, or an empty slice if this database is the only one; databases that are not
+    /// listed are not checked.
+    ///
+    /// Clearing the default scope never touches the registry.
+    ///
+    /// Returns the number of entries removed from this database.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let removed = db.clear_and_unregister(&mut wtxn, &tenant, &[&audit_log_db])?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_and_unregister(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        also_empty_in: &[&dyn ScopeEmptinessChecker],
+    ) -> Result<usize, ScopedDbError> {
+        let removed = self.clear(txn, scope)?;
+        self.global_registry
+            .unregister_if_unused(txn, scope, also_empty_in)?;
+        Ok(removed)
+    }
+
+    /// Remove every entry of this database and consume the handle.
+    ///
+    /// Both underlying heed databases (the default and the scoped one, or the single
+    /// database of the unified layout) are emptied, in every scope. Taking `self` by value
+    /// prevents accidental use of the handle afterwards.
+    ///
+    /// LMDB can only unlink a named database with `mdb_drop`, which heed does not expose,
+    /// so the now empty databases stay in the environment and keep occupying their
+    /// `max_dbs` slots until the environment is recreated. The global scope registry is
+    /// left unchanged, since other databases may still use its scopes; call
+    /// `GlobalScopeRegistry::prune_globally_unused_scopes` afterwards if needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::ScopedDbError;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let mut wtxn = env.write_txn()?;
+    /// db.destroy(&mut wtxn)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn destroy(self, txn: &mut RwTxn<'_>) -> Result<(), ScopedDbError> {
+        self.db_scoped.clear(txn)?;
+        if !self.unified {
+            self.db_default.clear(txn)?;
+        }
+        Ok(())
+    }
+
+    /// Atomically swap the contents of two named scopes.
+    ///
+    /// After this call, every entry previously stored under `a` is stored under `b`
+    /// and vice versa. Both scopes are registered in the global registry, and the swap
+    /// is only visible to other transactions once `txn` is committed.
+    ///
+    /// # Memory
+    ///
+    /// Entries are re-prefixed one at a time through a temporary, unused scope hash,
+    /// so memory usage is bounded by the largest single key/value pair rather than the
+    /// size of either scope. Entries of `a` are rewritten twice and entries of `b` once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if either scope is the default scope, or if
+    /// registering either scope detects a hash collision.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let blue = Scope::named("tenant_blue")?;
+    /// let green = Scope::named("tenant_green")?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// db.swap_scopes(&mut wtxn, &blue, &green)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn swap_scopes(
+        &self,
+        txn: &mut RwTxn<'_>,
+        a: &Scope,
+        b: &Scope,
+    ) -> Result<(), ScopedDbError> {
+        if self.verify_scope_hashes {
+            a.check_hash()?;
+            b.check_hash()?;
+        }
+        let (Some(hash_a), Some(hash_b)) = (a.hash(), b.hash()) else {
+            return Err(ScopedDbError::InvalidInput(
+                "swap_scopes requires two named scopes".into(),
+            ));
+        };
+
+        // Both scopes must remain registered after the swap
+        self.register_written_scope(txn, a)?;
+        self.register_written_scope(txn, b)?;
+
+        utils::swap_scope_entries(
+            self.db_scoped.remap_types::<Bytes, Bytes>(),
+            txn,
+            hash_a,
+            hash_b,
+        )
+    }
+
+    /// Copy every entry of `src_scope` in `src` into `scope` of this database.
+    ///
+    /// See `ScopedDatabase::extend_from`.
+    pub fn extend_from(
+        &self,
+        dst_txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        src: &Self,
+        src_txn: &RoTxn,
+        src_scope: &Scope,
+    ) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        self.register_written_scope(dst_txn, storage_scope.as_ref())?;
+
+        let mut copied = 0;
+        for entry in src.iter(src_txn, src_scope)? {
+            let (key, value) = entry?;
+            self.put(dst_txn, scope, key, value)?;
+            copied += 1;
+        }
+        Ok(copied)
+    }
+
+    /// Apply a batch of puts and deletes spanning any number of scopes.
+    ///
+    /// The operations are sorted into storage order before they are applied, so the
+    /// writes of each scope are contiguous and in key order instead of jumping between
+    /// scopes as they were queued. Bulk loads that interleave many scopes then touch each
+    /// B-tree page once rather than repeatedly. The sort is stable: operations on the
+    /// same key are applied in the order given, so a `Put` followed by a `Delete` of the
+    /// same key leaves it absent. Each operation goes through `put` or `delete`, so named
+    /// scopes are registered and observers notified as usual.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first operation that fails. Operations applied before it are not
+    /// rolled back; abort `txn`, or run the batch in `with_child_txn`, to discard them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use scoped_heed::BatchOp;
+    ///
+    /// let ops = vec![
+    ///     (tenant_b.clone(), BatchOp::Put(b"k1".to_vec(), b"v1".to_vec())),
+    ///     (tenant_a.clone(), BatchOp::Put(b"k1".to_vec(), b"v1".to_vec())),
+    ///     (tenant_b.clone(), BatchOp::Delete(b"k0".to_vec())),
+    /// ];
+    /// db.apply_batch(&mut wtxn, ops)?;
+    /// ```
+    pub fn apply_batch(
+        &self,
+        txn: &mut RwTxn<'_>,
+        mut ops: Vec<(Scope, BytesBatchOp)>,
+    ) -> Result<(), ScopedDbError> {
+        utils::sort_batch_ops(&mut ops, self.unified);
+        for (scope, op) in &ops {
+            match op {
+                BatchOp::Put(key, value) => self.put(txn, scope, key, value)?,
+                BatchOp::Delete(key) => {
+                    self.delete(txn, scope, key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Iterate over entries in a specific scope or the default database.
     pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> BytesIterResult<'txn> {
-        match scope {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result: BytesIterResult<'txn> = match scope {
             Scope::Default => {
                 let iter = self
                     .db_default
@@ -467,7 +1441,13 @@ impl ScopedBytesDatabase {
                     });
                 Ok(Box::new(iter))
             }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_iter(requested_scope);
         }
+        result
     }
 
     /// Iterate over entries in a specific scope or the default database using an Option<&str> scope name.
@@ -507,6 +1487,82 @@ impl ScopedBytesDatabase {
         self.iter(txn, &scope)
     }
 
+    /// Iterate over entries in a specific scope, also yielding the scope hash stored
+    /// with each entry.
+    ///
+    /// This is intended for diagnosing suspected hash collisions or isolation issues
+    /// without dropping down to raw heed. The hash is read from the stored key rather
+    /// than copied from `scope`, and is `None` for entries in the default scope.
+    pub fn iter_with_hash<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+    ) -> BytesHashIterResult<'txn> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.unified && scope.is_default() {
+            // Report default-scope entries without a hash, as in the two-database layout
+            let iter = self
+                .iter_with_hash(txn, &Scope::unified_default())?
+                .map(|result| result.map(|(_, key, value)| (None, key, value)));
+            return Ok(Box::new(iter));
+        }
+        match scope {
+            Scope::Default => {
+                let iter = self.db_default.iter(txn)?.map(|result| match result {
+                    Ok((key, value)) => Ok((None, key, value)),
+                    Err(e) => Err(ScopedDbError::from(e)),
+                });
+                Ok(Box::new(iter))
+            }
+            Scope::Named { hash, .. } => {
+                let scope_hash = *hash;
+                use std::ops::Bound;
+
+                // Create a range that covers only entries with this scope hash
+                let start_bound = Bound::Included((scope_hash, &[][..]));
+                let end_bound = match utils::next_scope_bound(scope_hash) {
+                    Some(next_hash) => Bound::Excluded((next_hash, &[][..])),
+                    None => Bound::Unbounded,
+                };
+                let range = (start_bound, end_bound);
+
+                let iter =
+                    self.db_scoped
+                        .range(txn, &range)?
+                        .filter_map(move |result| match result {
+                            Ok(((entry_scope_hash, key), value)) => {
+                                if entry_scope_hash == scope_hash {
+                                    Some(Ok((Some(entry_scope_hash), key, value)))
+                                } else {
+                                    None
+                                }
+                            }
+                            Err(e) => Some(Err(ScopedDbError::from(e))),
+                        });
+                Ok(Box::new(iter))
+            }
+        }
+    }
+
+    /// Iterate over the entries of several scopes, tagging each entry with its scope.
+    ///
+    /// See `ScopedDatabase::iter_scopes`.
+    pub fn iter_scopes<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scopes: &[Scope],
+    ) -> BytesMultiScopeIterResult<'txn> {
+        let mut iters = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let tag = scope.clone();
+            let iter = self.iter(txn, scope)?;
+            iters.push(iter.map(move |entry| entry.map(|(key, value)| (tag.clone(), key, value))));
+        }
+        Ok(Box::new(iters.into_iter().flatten()))
+    }
+
     /// Iterate over a range of entries in a specific scope or the default database.
     pub fn range<'sbd_ref, 'txn_ref, 'bounds_ref, R>(
         &'sbd_ref self,
@@ -517,6 +1573,8 @@ impl ScopedBytesDatabase {
     where
         R: RangeBounds<&'bounds_ref [u8]> + 'bounds_ref,
     {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
         match scope {
             Scope::Default => {
                 // Use adapter to convert RangeBounds<&[u8]> to RangeBounds<[u8]>
@@ -546,15 +1604,10 @@ impl ScopedBytesDatabase {
                     Bound::Excluded(key) => Bound::Excluded((scope_hash, *key)),
                     // For unbounded end, we use the next scope hash to ensure we don't
                     // include keys from other scopes
-                    Bound::Unbounded => {
-                        // Special case for u32::MAX to avoid overflow
-                        if scope_hash == u32::MAX {
-                            // Use a different approach for u32::MAX
-                            Bound::Included((scope_hash, &[0xFF][..]))
-                        } else {
-                            Bound::Excluded((scope_hash + 1, [].as_slice()))
-                        }
-                    }
+                    Bound::Unbounded => match utils::next_scope_bound(scope_hash) {
+                        Some(next_hash) => Bound::Excluded((next_hash, [].as_slice())),
+                        None => Bound::Unbounded,
+                    },
                 };
 
                 let transformed_range = (transformed_start, transformed_end);
@@ -571,6 +1624,64 @@ impl ScopedBytesDatabase {
         }
     }
 
+    /// Iterate over the entries of a scope that come after `after`, in key order.
+    ///
+    /// The cursor seeks straight to `after` within the scope, so the last key of one
+    /// page can be handed back by a client as a continuation token and the next page
+    /// starts there without rescanning. With `inclusive`, an entry stored under `after`
+    /// is yielded as well. `after` does not need to exist, and iteration stops at the end
+    /// of the scope.
+    ///
+    /// Keys of named scopes sort by length first, see `ScopedBytesCodec`, and
+    /// continuation follows that same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let page: Vec<_> = db.iter_from(&rtxn, &tenant, last_seen, false)?.take(20).collect();
+    /// ```
+    pub fn iter_from<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        after: &[u8],
+        inclusive: bool,
+    ) -> BytesIterResult<'txn> {
+        let start = if inclusive {
+            Bound::Included(after)
+        } else {
+            Bound::Excluded(after)
+        };
+        self.range(txn, scope, &(start, Bound::Unbounded))
+    }
+
+    /// Iterate over a page of a range: skip the first `offset` entries, then yield at
+    /// most `limit` entries.
+    ///
+    /// Scope boundaries are honored exactly as in `range`. The cursor stops as soon as
+    /// the page is complete, so later entries of the range are never read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Third page of 20 entries
+    /// let page: Vec<_> = db.range_limited(&rtxn, &tenant, &(&b"a"[..]..), 40, 20)?.collect();
+    /// ```
+    pub fn range_limited<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        range: &'bounds R,
+        offset: usize,
+        limit: usize,
+    ) -> BytesIterResult<'txn>
+    where
+        R: RangeBounds<&'bounds [u8]> + 'bounds,
+    {
+        let iter = self.range(txn, scope, range)?;
+        Ok(Box::new(iter.skip(offset).take(limit)))
+    }
+
     /// Iterate over a range of entries in a specific scope or the default database
     /// using an Option<&str> scope name.
     ///
@@ -616,6 +1727,153 @@ impl ScopedBytesDatabase {
         let scope = Scope::from(scope_name);
         self.range(txn, &scope, range)
     }
+
+    /// Iterate over a range of entries given as explicit start and end bounds.
+    ///
+    /// Equivalent to `range`, but avoids building a `RangeBounds<&[u8]>` value, which
+    /// often requires awkward conversions such as `b"a".as_slice()..=b"z".as_slice()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # use std::ops::Bound;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let rtxn = env.read_txn()?;
+    /// for result in db.range_keys(&rtxn, &tenant, Bound::Included(&b"a"[..]), Bound::Excluded(&b"m"[..]))? {
+    ///     let (key, value) = result?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn range_keys<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        start: Bound<&[u8]>,
+        end: Bound<&[u8]>,
+    ) -> BytesIterResult<'txn> {
+        self.range(txn, scope, &(start, end))
+    }
+
+    /// Iterate over the keys of a scope that start with `prefix` and whose remainder lies
+    /// in `suffix_range`.
+    ///
+    /// The prefix and suffix bounds are combined into key bounds, so only matching keys
+    /// are read instead of filtering a prefix scan. Both ends of `suffix_range` may be
+    /// inclusive, exclusive or unbounded; an unbounded end stops after the last key
+    /// starting with `prefix`. Returned keys include the prefix.
+    ///
+    /// Keys of named scopes sort by length first, see `ScopedBytesCodec`, so matches are
+    /// only contiguous among keys of equal length. The scope is walked one key length at
+    /// a time with a single seek per length, and matches are yielded in that storage
+    /// order, as from `range`.
+    ///
+    /// The default scope of a database created with `heed_flags` is rejected with
+    /// `ScopedDbError::InvalidInput`, as its keys are not in lexicographic order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Orders of March 2024: keys "order:2024-03-01" through "order:2024-03-31"
+    /// let range = (Bound::Included(&b"2024-03"[..]), Bound::Excluded(&b"2024-04"[..]));
+    /// for result in db.prefix_range(&rtxn, &tenant, b"order:", &range)? {
+    ///     let (key, value) = result?;
+    /// }
+    /// ```
+    pub fn prefix_range<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        prefix: &[u8],
+        suffix_range: &'bounds R,
+    ) -> BytesIterResult<'txn>
+    where
+        R: RangeBounds<&'bounds [u8]>,
+    {
+        let storage_scope = self.storage_scope(scope)?;
+        match storage_scope.as_ref() {
+            Scope::Default => {
+                utils::check_prefix_scan_flags(self.default_flags)?;
+                utils::prefix_range_entries(self.db_default, txn, None, prefix, suffix_range)
+            }
+            Scope::Named { hash, .. } => utils::prefix_range_entries(
+                self.db_scoped.remap_key_type::<Bytes>(),
+                txn,
+                Some(*hash),
+                prefix,
+                suffix_range,
+            ),
+        }
+    }
+
+    /// Copy every entry of a scope into an owned, ordered map.
+    ///
+    /// The snapshot outlives the transaction, so two snapshots taken at different times
+    /// can be compared with `diff_snapshots` to find what changed in between. The whole
+    /// scope is held in memory, which makes this suitable for small to medium scopes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{diff_snapshots, Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let before = db.snapshot_scope(&env.read_txn()?, &tenant)?;
+    /// // ... writes happen ...
+    /// let after = db.snapshot_scope(&env.read_txn()?, &tenant)?;
+    /// let diff = diff_snapshots(&before, &after);
+    /// println!("{} keys added", diff.added.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot_scope<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+    ) -> Result<ScopeSnapshot, ScopedDbError> {
+        self.iter(txn, scope)?
+            .map(|entry| entry.map(|(key, value)| (key.to_vec(), value.to_vec())))
+            .collect()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl ScopedBytesDatabase {
+    /// Returns whether `scope` holds exactly the entries of `expected`.
+    ///
+    /// Available with the `test-util` feature. Use `scope_diff` to find out what differs.
+    pub fn scope_eq<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        expected: &ScopeSnapshot,
+    ) -> Result<bool, ScopedDbError> {
+        Ok(self.snapshot_scope(txn, scope)? == *expected)
+    }
+
+    /// Compares the contents of `scope` with `expected`.
+    ///
+    /// Available with the `test-util` feature. The diff turns `expected` into the actual
+    /// contents: `added` holds the unexpected keys, `removed` the expected keys that are
+    /// missing, and `changed` the keys whose value differs, as `(expected, actual)`. An
+    /// empty diff means the scope matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let expected = ScopeSnapshot::from([(b"alice".to_vec(), b"admin".to_vec())]);
+    /// let diff = db.scope_diff(&rtxn, &tenant, &expected)?;
+    /// assert!(diff.is_empty(), "unexpected contents: {diff:?}");
+    /// ```
+    pub fn scope_diff<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        expected: &ScopeSnapshot,
+    ) -> Result<ScopeDiff, ScopedDbError> {
+        Ok(diff_snapshots(expected, &self.snapshot_scope(txn, scope)?))
+    }
 }
 
 impl Clone for ScopedBytesDatabase {
@@ -624,6 +1882,13 @@ impl Clone for ScopedBytesDatabase {
             db_scoped: self.db_scoped,
             db_default: self.db_default,
             global_registry: self.global_registry.clone(),
+            env: self.env.clone(),
+            unified: self.unified,
+            observer: self.observer.clone(),
+            require_registered_scopes: self.require_registered_scopes,
+            verify_scope_hashes: self.verify_scope_hashes,
+            deny_default_scope: self.deny_default_scope,
+            default_flags: self.default_flags,
         }
     }
 }
@@ -633,3 +1898,15 @@ impl ScopeEmptinessChecker for ScopedBytesDatabase {
         self.is_scope_empty(txn, scope)
     }
 }
+
+impl ScopeHashEnumerator for ScopedBytesDatabase {
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.distinct_scope_hashes(txn)
+    }
+}
+
+impl ScopedClear for ScopedBytesDatabase {
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.clear(txn, scope)
+    }
+}
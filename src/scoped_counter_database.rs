@@ -0,0 +1,270 @@
+use heed::{Env, RoTxn, RwTxn};
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::global_registry::{ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear};
+use crate::{BytesKeyIterResult, Scope, ScopedBytesDatabase, ScopedDbError};
+
+/// Integer types that can be stored as counter values.
+///
+/// Values are stored as 8 native-endian bytes. Implemented for `u64` and `i64`.
+pub trait CounterValue: Copy + fmt::Display + 'static {
+    /// Type name used in error messages
+    const TYPE_NAME: &'static str;
+
+    /// Value of a key that does not exist yet
+    const ZERO: Self;
+
+    /// Encode the value as stored in the database
+    fn to_bytes(self) -> [u8; 8];
+
+    /// Decode a stored value
+    fn from_bytes(bytes: [u8; 8]) -> Self;
+
+    /// Add two values, returning `None` on overflow
+    fn checked_add(self, delta: Self) -> Option<Self>;
+}
+
+macro_rules! impl_counter_value {
+    ($($ty:ty),+) => {
+        $(
+            impl CounterValue for $ty {
+                const TYPE_NAME: &'static str = stringify!($ty);
+                const ZERO: Self = 0;
+
+                fn to_bytes(self) -> [u8; 8] {
+                    self.to_ne_bytes()
+                }
+
+                fn from_bytes(bytes: [u8; 8]) -> Self {
+                    <$ty>::from_ne_bytes(bytes)
+                }
+
+                fn checked_add(self, delta: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, delta)
+                }
+            }
+        )+
+    };
+}
+
+impl_counter_value!(u64, i64);
+
+/// Scoped database with raw byte keys and `u64` values, see `ScopedCounterDatabase`
+pub type ScopedU64Database = ScopedCounterDatabase<u64>;
+
+/// Scoped database with raw byte keys and `i64` values, see `ScopedCounterDatabase`
+pub type ScopedI64Database = ScopedCounterDatabase<i64>;
+
+/// Scoped database with raw byte keys and integer values, for counters.
+///
+/// Values are stored as 8 native-endian bytes instead of going through bincode, so
+/// reading and writing a value is a plain copy. LMDB files are tied to the byte order of
+/// the machine that wrote them anyway, so this does not make the data less portable.
+/// Use `ScopedU64Database` for counters that only grow and `ScopedI64Database` when
+/// they can also be decremented.
+///
+/// The underlying storage is a `ScopedBytesDatabase`, so scoping, the unified layout and
+/// observers behave exactly as there, and the data can also be read through `inner`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{scoped_database_options, Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let mut wtxn = env.write_txn()?;
+/// let counters = scoped_database_options(&env, registry.clone())
+///     .u64_values()
+///     .name("page_views")
+///     .create(&mut wtxn)?;
+///
+/// let tenant = Scope::named("tenant1")?;
+/// counters.increment(&mut wtxn, &tenant, b"/home", 1)?;
+/// let views = counters.increment(&mut wtxn, &tenant, b"/home", 1)?; // 2
+/// wtxn.commit()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedCounterDatabase<N: CounterValue> {
+    inner: ScopedBytesDatabase,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: CounterValue> ScopedCounterDatabase<N> {
+    /// Wrap an existing raw bytes database whose values are 8-byte counters.
+    pub fn new(inner: ScopedBytesDatabase) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Access the underlying raw bytes database.
+    pub fn inner(&self) -> &ScopedBytesDatabase {
+        &self.inner
+    }
+
+    /// Registers a scope in the global registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a hash collision between different scope names.
+    pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        self.inner.register_scope(txn, scope)
+    }
+
+    /// Lists all known scopes in the database, including the Default scope.
+    pub fn list_scopes(&self, txn: &RoTxn) -> Result<Vec<Scope>, ScopedDbError> {
+        self.inner.list_scopes(txn)
+    }
+
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.inner.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.inner.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        self.inner.has_data(txn, scope)
+    }
+
+    /// Returns the longest key that can be written to `scope`.
+    ///
+    /// See `ScopedBytesDatabase::max_key_len`.
+    pub fn max_key_len(&self, scope: &Scope) -> usize {
+        self.inner.max_key_len(scope)
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    pub fn env(&self) -> &Env {
+        self.inner.env()
+    }
+
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        value: N,
+    ) -> Result<(), ScopedDbError> {
+        self.inner.put(txn, scope, key, &value.to_bytes())
+    }
+
+    /// Get a value from the database.
+    pub fn get(&self, txn: &RoTxn, scope: &Scope, key: &[u8]) -> Result<Option<N>, ScopedDbError> {
+        self.inner
+            .get(txn, scope, key)?
+            .map(decode_value)
+            .transpose()
+    }
+
+    /// Get a value that is expected to be present.
+    ///
+    /// See `ScopedDatabase::get_expect`.
+    pub fn get_expect(&self, txn: &RoTxn, scope: &Scope, key: &[u8]) -> Result<N, ScopedDbError> {
+        self.get(txn, scope, key)?.ok_or(ScopedDbError::NotFound)
+    }
+
+    /// Add `delta` to the value of a key and return the new value.
+    ///
+    /// A missing key counts as 0, so the first increment creates it. The read and the
+    /// write happen in `txn` with a single encoding of the scoped key, and concurrent
+    /// increments are serialized by LMDB's single writer. With `ScopedI64Database` a
+    /// negative `delta` decrements the counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if the new value would overflow; the
+    /// stored value is left unchanged.
+    pub fn increment(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        delta: N,
+    ) -> Result<N, ScopedDbError> {
+        let bytes = self.inner.update(txn, scope, key, |current| {
+            let current = current.map(decode_value).transpose()?.unwrap_or(N::ZERO);
+            let value = current.checked_add(delta).ok_or_else(|| {
+                ScopedDbError::InvalidInput(format!(
+                    "Incrementing {} by {} overflows {}",
+                    current,
+                    delta,
+                    N::TYPE_NAME
+                ))
+            })?;
+            Ok(value.to_bytes())
+        })?;
+        Ok(N::from_bytes(bytes))
+    }
+
+    /// Delete a key from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        self.inner.delete(txn, scope, key)
+    }
+
+    /// Clear all entries within a scope.
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.inner.clear(txn, scope)
+    }
+
+    /// Iterate over all entries in a scope.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> BytesKeyIterResult<'txn, N> {
+        let iter = self.inner.iter(txn, scope)?.map(|result| {
+            let (key, value) = result?;
+            Ok((key, decode_value(value)?))
+        });
+        Ok(Box::new(iter))
+    }
+}
+
+/// Decode a stored counter, rejecting values that are not 8 bytes long
+fn decode_value<N: CounterValue>(bytes: &[u8]) -> Result<N, ScopedDbError> {
+    let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+        ScopedDbError::Encoding(format!(
+            "Expected an 8-byte {} value, found {} bytes",
+            N::TYPE_NAME,
+            bytes.len()
+        ))
+    })?;
+    Ok(N::from_bytes(bytes))
+}
+
+impl<N: CounterValue> ScopeEmptinessChecker for ScopedCounterDatabase<N> {
+    fn is_scope_empty_in_db(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        self.inner.is_scope_empty_in_db(txn, scope)
+    }
+}
+
+impl<N: CounterValue> ScopeHashEnumerator for ScopedCounterDatabase<N> {
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.inner.distinct_scope_hashes(txn)
+    }
+}
+
+impl<N: CounterValue> ScopedClear for ScopedCounterDatabase<N> {
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.inner.clear(txn, scope)
+    }
+}
@@ -1,12 +1,25 @@
-use heed::types::SerdeBincode;
-use heed::{Database as HeedDatabase, Env, RoTxn, RwTxn};
+use heed::types::{Bytes, DecodeIgnore, SerdeBincode};
+use heed::{BytesDecode, BytesEncode, Database as HeedDatabase, DatabaseStat, Env, RoTxn, RwTxn};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+#[cfg(feature = "json")]
+use std::io::{self, BufRead};
 use std::marker::PhantomData;
-use std::ops::RangeBounds;
+use std::ops::{Bound, RangeBounds};
 use std::sync::Arc;
 
-use crate::global_registry::{GlobalScopeRegistry, ScopeEmptinessChecker};
-use crate::{IterResult, Scope, ScopedDbError, ScopedKey, utils};
+use crate::global_registry::{
+    GlobalScopeRegistry, ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear,
+};
+use crate::observer::OperationObserver;
+#[cfg(feature = "json")]
+use crate::scope::compute_xxhash;
+use crate::scoped_view::{ScopedReadView, ScopedView};
+use crate::utils::Neighbor;
+use crate::{
+    DEFAULT_SCOPE_HASH, HashIterResult, IterResult, MultiScopeIterResult, OrderPreservingKey,
+    Scope, ScopeHashIterResult, ScopedBytesCodec, ScopedDbError, ScopedKey, utils,
+};
 
 /// A scoped database providing Redis-like isolation between scopes.
 ///
@@ -59,6 +72,12 @@ where
     db_scoped: HeedDatabase<SerdeBincode<ScopedKey<K>>, SerdeBincode<V>>,
     db_default: HeedDatabase<SerdeBincode<K>, SerdeBincode<V>>,
     global_registry: Arc<GlobalScopeRegistry>,
+    env: Env,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
     _phantom: PhantomData<(K, V)>,
 }
 
@@ -76,7 +95,31 @@ where
         name: &str,
         txn: &mut RwTxn,
         registry: Arc<GlobalScopeRegistry>,
+        unified: bool,
     ) -> Result<Self, ScopedDbError> {
+        if unified {
+            // A single database holds every scope; default-scope keys use the reserved hash
+            let db_scoped = env
+                .database_options()
+                .types::<SerdeBincode<ScopedKey<K>>, SerdeBincode<V>>()
+                .name(&format!("{}_unified", name))
+                .create(txn)?;
+
+            return Ok(Self {
+                db_scoped,
+                // Never read: default-scope operations are routed to `db_scoped`
+                db_default: db_scoped.remap_key_type::<SerdeBincode<K>>(),
+                global_registry: registry,
+                env: env.clone(),
+                unified,
+                observer: None,
+                require_registered_scopes: false,
+                verify_scope_hashes: false,
+                deny_default_scope: false,
+                _phantom: PhantomData,
+            });
+        }
+
         // Create database names from base name
         // Use the original name for default database (backward compatibility)
         let default_name = name.to_string();
@@ -99,10 +142,52 @@ where
             db_scoped,
             db_default,
             global_registry: registry,
+            env: env.clone(),
+            unified,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
             _phantom: PhantomData,
         })
     }
 
+    /// Attach the observer configured on the builder
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn OperationObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Reject writes to unregistered scopes instead of registering them
+    pub(crate) fn with_require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// Check the hash of every scope passed to this database
+    pub(crate) fn with_verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// Reject every operation on the default scope
+    pub(crate) fn with_deny_default_scope(mut self, deny: bool) -> Self {
+        self.deny_default_scope = deny;
+        self
+    }
+
+    /// Resolve the storage scope of `scope`, after the checks enabled on the builder:
+    /// `deny_default_scope` and `verify_scope_hashes`
+    fn storage_scope<'s>(&self, scope: &'s Scope) -> Result<Cow<'s, Scope>, ScopedDbError> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.verify_scope_hashes {
+            scope.check_hash()?;
+        }
+        scope.storage_scope(self.unified)
+    }
+
     /// Registers a scope in the global registry.
     ///
     /// This method is automatically called during write operations (put, delete, clear)
@@ -113,13 +198,44 @@ where
     ///
     /// Returns an error if there's a hash collision between different scope names.
     pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
-        if let Scope::Named { name: _, hash: _ } = scope {
+        // The storage scope of unified default-scope entries is never registered
+        if let Scope::Named { .. } = scope
+            && !(self.unified && scope.is_unified_default())
+        {
             self.global_registry.register_scope(txn, scope)
         } else {
             Ok(())
         }
     }
 
+    /// Registers the scope of a write, or checks that it is already registered when the
+    /// database was built with `require_registered_scopes`
+    fn register_written_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        if !self.require_registered_scopes {
+            return self.register_scope(txn, scope);
+        }
+        if self.unified && scope.is_unified_default() {
+            return Ok(());
+        }
+        self.global_registry.ensure_registered(txn, scope)
+    }
+
+    /// Bytes the scope hash adds to the encoded keys of `scope`
+    fn key_overhead(&self, scope: &Scope) -> usize {
+        if self.unified || !scope.is_default() {
+            std::mem::size_of::<u32>()
+        } else {
+            0
+        }
+    }
+
+    /// Rejects keys whose bincode encoding is longer than `max_key_len` allows
+    fn check_key_size(&self, scope: &Scope, key: &K) -> Result<(), ScopedDbError> {
+        let key_len =
+            bincode::serialized_size(key).map_err(|e| ScopedDbError::Encoding(e.to_string()))?;
+        utils::check_key_size(&self.env, key_len as usize, self.key_overhead(scope))
+    }
+
     /// Lists all known scopes in the database.
     ///
     /// Returns a list of all scopes that have been registered by this database,
@@ -152,6 +268,43 @@ where
         self.global_registry.list_all_scopes(txn)
     }
 
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.global_registry.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns a view of this database bound to a single scope.
+    ///
+    /// The view's methods omit the scope argument and forward to this database.
+    pub fn scoped(&self, scope: Scope) -> ScopedView<'_, Self> {
+        ScopedView::new(self, scope)
+    }
+
+    /// Returns a read-only view of this database bound to a scope and a transaction.
+    ///
+    /// The view's methods omit both the transaction and the scope, see `ScopedReadView`.
+    pub fn read_view<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: Scope,
+    ) -> ScopedReadView<'_, 'txn, Self> {
+        ScopedReadView::new(self, txn, scope)
+    }
+
     /// Insert a key-value pair into the database.
     ///
     /// Uses the Scope enum to represent scopes, which provides better
@@ -169,14 +322,18 @@ where
         key: &K,
         value: &V,
     ) -> Result<(), ScopedDbError> {
-        match scope {
+        self.check_key_size(scope, key)?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self
                 .db_default
                 .put(txn, key, value)
                 .map_err(ScopedDbError::from),
             Scope::Named { hash, .. } => {
                 // Register scope in global registry
-                self.register_scope(txn, scope)?;
+                self.register_written_scope(txn, scope)?;
 
                 // Use ScopedKey tuple
                 let scoped_key = ScopedKey {
@@ -187,7 +344,13 @@ where
                     .put(txn, &scoped_key, value)
                     .map_err(ScopedDbError::from)
             }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
         }
+        result
     }
 
     /// Insert a key-value pair into the database with an Option<&str> scope name.
@@ -224,6 +387,201 @@ where
         self.put(txn, &scope, key, value)
     }
 
+    /// Insert a key-value pair and return the value it replaced, if any.
+    ///
+    /// The key is encoded once and the same encoded key is used for reading the previous
+    /// value and for writing the new one, saving a separate `get`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let previous = db.put_returning(&mut wtxn, &tenant, &"leader".to_string(), &"node-2".to_string())?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn put_returning(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+        value: &V,
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.check_key_size(scope, key)?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let swap = |txn: &mut RwTxn<'_>, db: HeedDatabase<Bytes, SerdeBincode<V>>, key: &[u8]| {
+            let previous = db.get(txn, key)?;
+            db.put(txn, key, value)?;
+            Ok::<_, ScopedDbError>(previous)
+        };
+        let result = match scope {
+            Scope::Default => {
+                let encoded = SerdeBincode::<K>::bytes_encode(key)?;
+                swap(txn, self.db_default.remap_key_type::<Bytes>(), &encoded)
+            }
+            Scope::Named { hash, .. } => {
+                // Register scope in global registry
+                self.register_written_scope(txn, scope)?;
+
+                let scoped_key = ScopedKey {
+                    scope_hash: *hash,
+                    key: key.clone(),
+                };
+                let encoded = SerdeBincode::<ScopedKey<K>>::bytes_encode(&scoped_key)?;
+                swap(txn, self.db_scoped.remap_key_type::<Bytes>(), &encoded)
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
+        }
+        result
+    }
+
+    /// Merge a delta into the value stored under a key.
+    ///
+    /// Reads the current value (if any), passes it to `combine` together with `delta`,
+    /// and writes the result back, all under the supplied transaction. The scoped key
+    /// is encoded once and reused for both the read and the write.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// db.merge(&mut wtxn, &tenant, &"counter".to_string(), 5u64, |current, delta| {
+    ///     current.unwrap_or(0) + delta
+    /// })?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge<F>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+        delta: V,
+        combine: F,
+    ) -> Result<(), ScopedDbError>
+    where
+        F: Fn(Option<V>, V) -> V,
+    {
+        self.check_key_size(scope, key)?;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                let current = self.db_default.get(txn, key)?;
+                let merged = combine(current, delta);
+                self.db_default
+                    .put(txn, key, &merged)
+                    .map_err(ScopedDbError::from)
+            }
+            Scope::Named { hash, .. } => {
+                // Register scope in global registry
+                self.register_written_scope(txn, scope)?;
+
+                let scoped_key = ScopedKey {
+                    scope_hash: *hash,
+                    key: key.clone(),
+                };
+                let current = self.db_scoped.get(txn, &scoped_key)?;
+                let merged = combine(current, delta);
+                self.db_scoped
+                    .put(txn, &scoped_key, &merged)
+                    .map_err(ScopedDbError::from)
+            }
+        }
+    }
+
+    /// Merge a delta into the value stored under a key with an Option<&str> scope name.
+    pub fn merge_with_name<F>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope_name: Option<&str>,
+        key: &K,
+        delta: V,
+        combine: F,
+    ) -> Result<(), ScopedDbError>
+    where
+        F: Fn(Option<V>, V) -> V,
+    {
+        let scope = Scope::from(scope_name);
+        self.merge(txn, &scope, key, delta, combine)
+    }
+
+    /// Get a handle to the value stored under a key, in the style of `HashMap::entry`.
+    ///
+    /// The current value is read once and the scoped key is encoded once; both are cached
+    /// in the returned `Entry`. Nothing is written until a terminal method such as
+    /// `Entry::or_insert` is called, and dropping the entry without calling one leaves the
+    /// database untouched. The entry holds the write transaction until it is consumed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let visits = db
+    ///     .entry(&mut wtxn, &tenant, &"visits".to_string())?
+    ///     .and_modify(|count| *count += 1)
+    ///     .or_insert(1)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entry<'a, 'txn>(
+        &'a self,
+        txn: &'a mut RwTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Entry<'a, 'txn, K, V>, ScopedDbError> {
+        self.check_key_size(scope, key)?;
+        let storage_scope = self.storage_scope(scope)?;
+        let (target, encoded) = match storage_scope.as_ref() {
+            Scope::Default => (
+                self.db_default.remap_key_type::<Bytes>(),
+                SerdeBincode::<K>::bytes_encode(key)?.into_owned(),
+            ),
+            Scope::Named { hash, .. } => {
+                let scoped_key = ScopedKey {
+                    scope_hash: *hash,
+                    key: key.clone(),
+                };
+                (
+                    self.db_scoped.remap_key_type::<Bytes>(),
+                    SerdeBincode::<ScopedKey<K>>::bytes_encode(&scoped_key)?.into_owned(),
+                )
+            }
+        };
+        let value = target.get(txn, &encoded)?;
+        if let Some(observer) = &self.observer {
+            observer.on_get(scope, value.is_some());
+        }
+        Ok(Entry {
+            db: self,
+            txn,
+            scope: storage_scope.into_owned(),
+            requested_scope: scope.clone(),
+            target,
+            key: encoded,
+            value,
+            modified: false,
+        })
+    }
+
     /// Get a value from the database.
     ///
     /// Uses the Scope enum to represent scopes, which provides better
@@ -234,13 +592,22 @@ where
     /// For named scopes, this method clones the key to create a `ScopedKey<K>` structure.
     /// If your key type is very large or expensive to clone, consider using
     /// `ScopedBytesKeyDatabase<V>` instead for better performance.
+    ///
+    /// # Borrowed Values
+    ///
+    /// The value is deserialized into an owned `V`, in every scope. `get_raw` borrows the
+    /// stored bincode bytes from the transaction instead, for callers that only need to
+    /// hash, compare or forward them.
     pub fn get<'txn>(
         &self,
         txn: &'txn RoTxn<'txn>,
         scope: &Scope,
         key: &K,
     ) -> Result<Option<V>, ScopedDbError> {
-        match scope {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self.db_default.get(txn, key).map_err(ScopedDbError::from),
             Scope::Named { hash, .. } => {
                 // Use ScopedKey tuple with the hash directly
@@ -252,7 +619,76 @@ where
                     .get(txn, &scoped_key)
                     .map_err(ScopedDbError::from)
             }
+        };
+        if let (Some(observer), Ok(value)) = (&self.observer, &result) {
+            observer.on_get(requested_scope, value.is_some());
+        }
+        result
+    }
+
+    /// Get a value that is expected to be present.
+    ///
+    /// Like `get`, but a missing key is reported as `ScopedDbError::NotFound` instead of
+    /// `Ok(None)`, so it can be propagated with `?` where absence is an error.
+    pub fn get_expect<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<V, ScopedDbError> {
+        self.get(txn, scope, key)?.ok_or(ScopedDbError::NotFound)
+    }
+
+    /// Check which of several keys exist in a scope.
+    ///
+    /// Returns one flag per key, in the order of `keys`. Each scoped key is encoded once
+    /// and looked up without decoding its value, which makes this cheaper than `get` for
+    /// permission checks and similar existence tests. The observer sees one `on_get` per
+    /// key.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let keys = ["read".to_string(), "write".to_string()];
+    /// let granted = db.contains_many(&rtxn, &tenant, &keys)?; // e.g. [true, false]
+    /// ```
+    pub fn contains_many(
+        &self,
+        txn: &RoTxn,
+        scope: &Scope,
+        keys: &[K],
+    ) -> Result<Vec<bool>, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let mut present = Vec::with_capacity(keys.len());
+        for key in keys {
+            let found = match scope {
+                Scope::Default => {
+                    let encoded = SerdeBincode::<K>::bytes_encode(key)?;
+                    self.db_default
+                        .remap_types::<Bytes, DecodeIgnore>()
+                        .get(txn, &encoded)?
+                        .is_some()
+                }
+                Scope::Named { hash, .. } => {
+                    let scoped_key = ScopedKey {
+                        scope_hash: *hash,
+                        key: key.clone(),
+                    };
+                    let encoded = SerdeBincode::<ScopedKey<K>>::bytes_encode(&scoped_key)?;
+                    self.db_scoped
+                        .remap_types::<Bytes, DecodeIgnore>()
+                        .get(txn, &encoded)?
+                        .is_some()
+                }
+            };
+            if let Some(observer) = &self.observer {
+                observer.on_get(requested_scope, found);
+            }
+            present.push(found);
         }
+        Ok(present)
     }
 
     /// Get a value from the database using an Option<&str> scope name.
@@ -287,20 +723,38 @@ where
         self.get(txn, &scope, key)
     }
 
-    /// Delete a key-value pair from the database.
+    /// Get the raw stored bytes of a value without deserializing it.
     ///
-    /// Uses the Scope enum to represent scopes, which provides better
-    /// performance by pre-computing and caching scope hashes.
-    pub fn delete(
+    /// The returned slice borrows directly from the memory map for the lifetime of the
+    /// transaction and holds the bincode encoding of `V`. This is useful to checksum,
+    /// compare or proxy stored values without paying the deserialization cost.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let rtxn = env.read_txn()?;
+    /// if let Some(bytes) = db.get_raw(&rtxn, &tenant, &key)? {
+    ///     let checksum = crc32fast::hash(bytes);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_raw<'txn>(
         &self,
-        txn: &mut RwTxn<'_>,
+        txn: &'txn RoTxn<'txn>,
         scope: &Scope,
         key: &K,
-    ) -> Result<bool, ScopedDbError> {
-        match scope {
+    ) -> Result<Option<&'txn [u8]>, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
             Scope::Default => self
                 .db_default
-                .delete(txn, key)
+                .remap_data_type::<Bytes>()
+                .get(txn, key)
                 .map_err(ScopedDbError::from),
             Scope::Named { hash, .. } => {
                 let scoped_key = ScopedKey {
@@ -308,152 +762,519 @@ where
                     key: key.clone(),
                 };
                 self.db_scoped
-                    .delete(txn, &scoped_key)
+                    .remap_data_type::<Bytes>()
+                    .get(txn, &scoped_key)
                     .map_err(ScopedDbError::from)
             }
+        };
+        if let (Some(observer), Ok(value)) = (&self.observer, &result) {
+            observer.on_get(requested_scope, value.is_some());
         }
+        result
     }
 
-    /// Delete a key-value pair from the database using an Option<&str> scope name.
+    /// Get a value together with the size of its stored bytes.
     ///
-    /// This is a convenience method that converts the scope name to a Scope enum
-    /// and then calls the main delete method.
+    /// The size is the length of the encoded value, which is what the entry adds to the
+    /// database beyond its key, so summing it over a scope gives a per-scope byte count
+    /// for quota accounting. It does not include the key or LMDB's page overhead, hence
+    /// approximate. The value is read once, with `get_raw`, and decoded from the same
+    /// bytes.
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// # use scoped_heed::{ScopedDatabase, ScopedDbError};
-    /// # use heed::EnvOpenOptions;
-    /// # fn main() -> Result<(), ScopedDbError> {
-    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
-    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
-    /// # let mut wtxn = env.write_txn()?;
-    /// // Use the convenience method with Option<&str>
-    /// let was_deleted = db.delete_with_name(&mut wtxn, Some("tenant1"), &"key1".to_string())?;
-    ///
-    /// // Use None for the default scope
-    /// let was_deleted = db.delete_with_name(&mut wtxn, None, &"key2".to_string())?;
-    /// # wtxn.commit()?;
-    /// # Ok(())
-    /// # }
+    /// if let Some((document, size)) = db.get_with_size(&rtxn, &tenant, &key)? {
+    ///     quota.charge(&tenant, size);
+    /// }
     /// ```
-    pub fn delete_with_name(
+    pub fn get_with_size(
         &self,
-        txn: &mut RwTxn<'_>,
-        scope_name: Option<&str>,
+        txn: &RoTxn,
+        scope: &Scope,
         key: &K,
-    ) -> Result<bool, ScopedDbError> {
-        let scope = Scope::from(scope_name);
-        self.delete(txn, &scope, key)
+    ) -> Result<Option<(V, usize)>, ScopedDbError> {
+        self.get_raw(txn, scope, key)?
+            .map(|bytes| Ok((SerdeBincode::<V>::bytes_decode(bytes)?, bytes.len())))
+            .transpose()
     }
 
-    /// Clear all entries within a specific scope or the default database.
-    ///
-    /// This is a highly optimized operation that efficiently removes all data for a specific scope,
-    /// without affecting data in other scopes.
-    ///
-    /// # Performance
-    ///
-    /// This method uses an optimized cursor-based approach to:
-    /// - Clear all entries for a specific scope hash in a single pass
-    /// - Avoid collecting keys into memory before deletion
-    /// - Skip deserialization of values during the deletion process
-    ///
-    /// For large datasets, this provides significantly better performance compared
-    /// to iterating and collecting entries before deletion.
-    ///
-    /// # Implementation Details
-    ///
-    /// For the generic `ScopedDatabase<K,V>`, this uses:
-    /// - A range-based cursor approach similar to heed's internal implementation
-    /// - The `DecodeIgnore` type to avoid deserializing values
-    /// - Direct cursor deletion to minimize memory overhead
-    ///
-    /// # Special Cases
-    ///
-    /// - For the `Default` scope, this delegates to heed's built-in `clear` method
-    /// - For scopes with a hash of `u32::MAX`, special handling ensures all entries are properly cleared
-    /// - Ensures correct behavior with generic key types through the `Default` trait
+    /// Returns the entry with the smallest key greater than or equal to `key` in a scope.
     ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// # use scoped_heed::{ScopedDatabase, Scope, ScopedDbError};
-    /// # use heed::EnvOpenOptions;
-    /// # fn main() -> Result<(), ScopedDbError> {
-    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
-    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
-    /// # let mut wtxn = env.write_txn()?;
-    /// // Create a scope
-    /// let tenant_scope = Scope::named("tenant1")?;
-    ///
-    /// // Clear all data in the tenant scope
-    /// db.clear(&mut wtxn, &tenant_scope)?;
-    /// # wtxn.commit()?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<(), ScopedDbError> {
-        match scope {
-            Scope::Default => self.db_default.clear(txn).map_err(ScopedDbError::from),
-            Scope::Named { hash, .. } => {
-                // Register the scope before clearing (ensures it's in the registry)
-                self.register_scope(txn, scope)?;
+    /// Like `range`, this relies on the encoded key order and therefore requires an
+    /// `OrderPreservingKey`. The lookup is a single cursor seek and never returns an
+    /// entry from a neighboring scope.
+    pub fn ceiling<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError>
+    where
+        K: OrderPreservingKey,
+    {
+        self.neighbor(txn, scope, key, Neighbor::Ceiling)
+    }
+
+    /// Returns the entry with the smallest key strictly greater than `key` in a scope.
+    pub fn higher<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError>
+    where
+        K: OrderPreservingKey,
+    {
+        self.neighbor(txn, scope, key, Neighbor::Higher)
+    }
 
-                // For generic ScopedDatabase<K,V>, using delete_range is trickier because we
-                // need to define a range of ScopedKey<K> objects. For efficiency, we'll use
-                // a cursor-based approach similar to heed's own delete_range implementation,
-                // which avoids collecting all keys into a Vec first.
+    /// Returns the entry with the largest key less than or equal to `key` in a scope.
+    pub fn floor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError>
+    where
+        K: OrderPreservingKey,
+    {
+        self.neighbor(txn, scope, key, Neighbor::Floor)
+    }
 
-                // Create a mutable iterator with DecodeIgnore for the data part to save deserializing
-                // values we're just going to delete anyway
-                use heed::types::DecodeIgnore;
+    /// Returns the entry with the largest key strictly less than `key` in a scope.
+    pub fn lower<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError>
+    where
+        K: OrderPreservingKey,
+    {
+        self.neighbor(txn, scope, key, Neighbor::Lower)
+    }
 
-                // Create a range_mut that covers all entries in this scope
-                // We'll create a minimum viable key for range start and end
-                // We can't use open-ended ranges here since we need to constrain by scope_hash
-                let min_key_start: ScopedKey<K> = ScopedKey {
+    fn neighbor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+        neighbor: Neighbor,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                let bounds = neighbor.bounds(key);
+                let entry = if neighbor.is_forward() {
+                    self.db_default.range(txn, &bounds)?.next()
+                } else {
+                    self.db_default.rev_range(txn, &bounds)?.next()
+                };
+                entry.transpose().map_err(ScopedDbError::from)
+            }
+            Scope::Named { hash, .. } => {
+                let scoped_key = ScopedKey {
                     scope_hash: *hash,
-                    // We need a "minimum" key value - use Default if K implements it
-                    // If K is not Default, we'll fall back to the old approach
-                    key: utils::get_key_default(),
+                    key: key.clone(),
                 };
-
-                let min_key_end = if *hash == u32::MAX {
-                    // Special case for MAX scope hash to avoid overflow
-                    ScopedKey {
-                        scope_hash: *hash,
-                        // Use "maximum" possible key instead
-                        key: min_key_start.key.clone(), // We rely on lexicographic ordering of scope_hash first
-                    }
+                let bounds = neighbor.bounds(&scoped_key);
+                let entry = if neighbor.is_forward() {
+                    self.db_scoped.range(txn, &bounds)?.next()
                 } else {
-                    ScopedKey {
-                        // For the end bound we use the next scope hash to exclude all keys from other scopes
-                        scope_hash: hash.wrapping_add(1),
-                        // The same minimum key works for the end bound
-                        key: min_key_start.key.clone(),
+                    self.db_scoped.rev_range(txn, &bounds)?.next()
+                };
+
+                // The first entry past the probe may belong to an adjacent scope
+                match entry.transpose()? {
+                    Some((found, value)) if found.scope_hash == *hash => {
+                        Ok(Some((found.key, value)))
                     }
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Delete a key-value pair from the database.
+    ///
+    /// Uses the Scope enum to represent scopes, which provides better
+    /// performance by pre-computing and caching scope hashes.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
+            Scope::Default => self
+                .db_default
+                .delete(txn, key)
+                .map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => {
+                let scoped_key = ScopedKey {
+                    scope_hash: *hash,
+                    key: key.clone(),
                 };
+                self.db_scoped
+                    .delete(txn, &scoped_key)
+                    .map_err(ScopedDbError::from)
+            }
+        };
+        if let (Some(observer), Ok(deleted)) = (&self.observer, &result) {
+            observer.on_delete(requested_scope, *deleted);
+        }
+        result
+    }
 
-                // Set up our bounds to get all keys in this scope
-                use std::ops::Bound;
-                let range = (Bound::Included(min_key_start), Bound::Excluded(min_key_end));
+    /// Delete a key-value pair from the database using an Option<&str> scope name.
+    ///
+    /// This is a convenience method that converts the scope name to a Scope enum
+    /// and then calls the main delete method.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{ScopedDatabase, ScopedDbError};
+    /// # use heed::EnvOpenOptions;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
+    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let mut wtxn = env.write_txn()?;
+    /// // Use the convenience method with Option<&str>
+    /// let was_deleted = db.delete_with_name(&mut wtxn, Some("tenant1"), &"key1".to_string())?;
+    ///
+    /// // Use None for the default scope
+    /// let was_deleted = db.delete_with_name(&mut wtxn, None, &"key2".to_string())?;
+    /// # wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_with_name(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope_name: Option<&str>,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        let scope = Scope::from(scope_name);
+        self.delete(txn, &scope, key)
+    }
+
+    /// Move the entry stored under `key` from one scope to another.
+    ///
+    /// Reads the value under `from`, deletes it there and writes it under `to` within
+    /// the supplied transaction, registering `to` like any other write. Returns whether
+    /// the key existed in `from`; nothing is written otherwise. An existing value under
+    /// the key in `to` is overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let mut wtxn = env.write_txn()?;
+    /// let moved = db.move_key(&mut wtxn, &Scope::named("acme")?, &Scope::named("globex")?, &"account-42".to_string())?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_key(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        to: &Scope,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        self.move_key_as(txn, from, key, to, key)
+    }
+
+    /// Move the entry stored under `from_key` in `from` to `to_key` in `to`.
+    ///
+    /// Like `move_key`, but the entry may also change its key. Moving an entry onto
+    /// itself leaves it in place and returns `true`.
+    pub fn move_key_as(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        from_key: &K,
+        to: &Scope,
+        to_key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        let Some(value) = self.get(txn, from, from_key)? else {
+            return Ok(false);
+        };
+        // Deleting first keeps the entry when source and destination are the same
+        self.delete(txn, from, from_key)?;
+        self.put(txn, to, to_key, &value)?;
+        Ok(true)
+    }
+
+    /// Delete all entries whose keys fall within a range in a specific scope or the default database.
+    ///
+    /// Only entries belonging to the requested scope are removed; identical keys in
+    /// other scopes are left untouched. Returns the number of deleted entries.
+    ///
+    /// As with `range`, the bounds are compared by LMDB on the bincode-encoded keys, so
+    /// the key type must implement `OrderPreservingKey`. For string keys sharing a
+    /// prefix, collect the keys with `iter_prefix` and delete them one by one.
+    ///
+    /// # Performance
+    ///
+    /// This uses the same scope-aware bound transformation as `range`, then walks the
+    /// matching entries with a mutable cursor and deletes them in place, skipping value
+    /// deserialization. No keys are collected into memory before deletion.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{ScopedDatabase, Scope, ScopedDbError};
+    /// # use heed::EnvOpenOptions;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
+    /// # let db: ScopedDatabase<char, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let mut wtxn = env.write_txn()?;
+    /// let tenant = Scope::named("tenant1")?;
+    ///
+    /// // Remove all keys between 'a' and 'm' in the tenant scope
+    /// let deleted = db.delete_range(&mut wtxn, &tenant, &('a'..='m'))?;
+    /// println!("Deleted {} entries", deleted);
+    /// # wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_range<R>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        range: &R,
+    ) -> Result<usize, ScopedDbError>
+    where
+        K: PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K>,
+    {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => self
+                .db_default
+                .delete_range(txn, range)
+                .map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => {
+                let scope_hash = *hash;
+                let transformed_range = Self::scoped_range_bounds(scope_hash, range);
 
-                // Use a remap_data_type to avoid deserializing values we're just deleting
+                // Only decode keys; values are never needed for deletion
                 let mut iter = self
                     .db_scoped
                     .remap_data_type::<DecodeIgnore>()
-                    .range_mut(txn, &range)?;
+                    .range_mut(txn, &transformed_range)?;
 
-                // For each item in range, delete it right from the cursor without collecting
-                while iter.next().is_some() {
-                    // Safety: No references to cursor data are kept after deletion
-                    unsafe { iter.del_current()? };
+                let mut deleted = 0;
+                while let Some(result) = iter.next() {
+                    let (scoped_key, ()) = result?;
+                    // Apply the same scope and original range checks as `range`
+                    if scoped_key.scope_hash == scope_hash && range.contains(&scoped_key.key) {
+                        // Safety: No references to cursor data are kept after deletion
+                        unsafe { iter.del_current()? };
+                        deleted += 1;
+                    }
                 }
 
+                Ok(deleted)
+            }
+        }
+    }
+
+    /// Rewrite or delete every entry whose key falls within a range in a scope.
+    ///
+    /// `f` receives each key and its current value and returns the replacement, or
+    /// `None` to delete the entry. The range is walked once with a mutable cursor that
+    /// replaces or deletes each entry in place, so nothing is collected into memory
+    /// first, which suits bulk transformations such as re-encrypting a large scope.
+    /// Returns the number of entries that were rewritten or deleted. The range follows
+    /// the same scope-aware bounds as `range` and `delete_range`, so the key type must
+    /// implement `OrderPreservingKey`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// // Re-encrypt every session, dropping the ones that no longer decrypt
+    /// let touched = db.update_range(&mut wtxn, &tenant, &(..), |_key, value| {
+    ///     old_cipher.decrypt(&value).map(|plain| new_cipher.encrypt(&plain))
+    /// })?;
+    /// wtxn.commit()?;
+    /// ```
+    pub fn update_range<R, F>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        range: &R,
+        mut f: F,
+    ) -> Result<usize, ScopedDbError>
+    where
+        K: PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K>,
+        F: FnMut(&K, V) -> Option<V>,
+    {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let mut updated = 0;
+        match scope {
+            Scope::Default => {
+                let mut iter = self.db_default.range_mut(txn, range)?;
+                while let Some(result) = iter.next() {
+                    let (key, value) = result?;
+                    match f(&key, value) {
+                        // Safety: the key and value are owned, no references to cursor
+                        // data are kept across the write
+                        Some(value) => unsafe { iter.put_current(&key, &value)? },
+                        None => unsafe { iter.del_current()? },
+                    };
+                    updated += 1;
+                }
+            }
+            Scope::Named { hash, .. } => {
+                let scope_hash = *hash;
+                let transformed_range = Self::scoped_range_bounds(scope_hash, range);
+                let mut iter = self.db_scoped.range_mut(txn, &transformed_range)?;
+                while let Some(result) = iter.next() {
+                    let (scoped_key, value) = result?;
+                    // Apply the same scope and original range checks as `range`
+                    if scoped_key.scope_hash != scope_hash || !range.contains(&scoped_key.key) {
+                        continue;
+                    }
+                    match f(&scoped_key.key, value) {
+                        // Safety: the key and value are owned, no references to cursor
+                        // data are kept across the write
+                        Some(value) => unsafe { iter.put_current(&scoped_key, &value)? },
+                        None => unsafe { iter.del_current()? },
+                    };
+                    updated += 1;
+                }
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Delete all entries within a range using an Option<&str> scope name.
+    ///
+    /// This is a convenience method that converts the scope name to a Scope enum
+    /// and then calls the main delete_range method.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{ScopedDatabase, ScopedDbError};
+    /// # use heed::EnvOpenOptions;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
+    /// # let db: ScopedDatabase<char, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let mut wtxn = env.write_txn()?;
+    /// let deleted = db.delete_range_with_name(&mut wtxn, Some("tenant1"), &('a'..='m'))?;
+    /// # wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_range_with_name<R>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope_name: Option<&str>,
+        range: &R,
+    ) -> Result<usize, ScopedDbError>
+    where
+        K: PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K>,
+    {
+        let scope = Scope::from(scope_name);
+        self.delete_range(txn, &scope, range)
+    }
+
+    /// Clear all entries within a specific scope or the default database.
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// This is a highly optimized operation that efficiently removes all data for a specific scope,
+    /// without affecting data in other scopes.
+    ///
+    /// # Performance
+    ///
+    /// This method uses an optimized cursor-based approach to:
+    /// - Clear all entries for a specific scope hash in a single pass
+    /// - Avoid collecting keys into memory before deletion
+    /// - Skip deserialization of values during the deletion process
+    ///
+    /// For large datasets, this provides significantly better performance compared
+    /// to iterating and collecting entries before deletion.
+    ///
+    /// # Implementation Details
+    ///
+    /// For the generic `ScopedDatabase<K,V>`, this uses:
+    /// - A range-based cursor approach similar to heed's internal implementation
+    /// - The `DecodeIgnore` type to avoid deserializing values
+    /// - Direct cursor deletion to minimize memory overhead
+    ///
+    /// # Special Cases
+    ///
+    /// - For the `Default` scope, this delegates to heed's built-in `clear` method
+    /// - For scopes with a hash of `u32::MAX`, whose prefix sorts last, the range extends to the end of the database
+    /// - Ensures correct behavior with generic key types through the `Default` trait
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use scoped_heed::{ScopedDatabase, Scope, ScopedDbError};
+    /// # use heed::EnvOpenOptions;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
+    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let mut wtxn = env.write_txn()?;
+    /// // Create a scope
+    /// let tenant_scope = Scope::named("tenant1")?;
+    ///
+    /// // Clear all data in the tenant scope
+    /// db.clear(&mut wtxn, &tenant_scope)?;
+    /// # wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                let count = self.db_default.len(txn)? as usize;
+                self.db_default.clear(txn)?;
+                Ok(count)
+            }
+            Scope::Named { hash, .. } => {
+                // Register the scope before clearing (ensures it's in the registry)
+                self.register_written_scope(txn, scope)?;
+
+                // Every `ScopedKey<K>` starts with the bincode-encoded scope hash, so the
+                // scope is the contiguous block of raw keys from its 4-byte prefix up to the
+                // next scope's prefix; the last prefix (`u32::MAX`) extends to the end of the
+                // database. Bounding on raw bytes lets `delete_range` skip decoding each key
+                // and does not depend on `K::default()` being the smallest key.
+                let start = ScopedBytesCodec::hash_prefix(*hash);
+                let next = utils::next_scope_bound(*hash).map(ScopedBytesCodec::hash_prefix);
+                let end = match &next {
+                    Some(next) => Bound::Excluded(&next[..]),
+                    None => Bound::Unbounded,
+                };
+                let count = self
+                    .db_scoped
+                    .remap_types::<Bytes, DecodeIgnore>()
+                    .delete_range(txn, &(Bound::Included(&start[..]), end))?;
+
                 // The user can call unregister_scope manually if needed
 
-                Ok(())
+                Ok(count)
             }
         }
     }
@@ -485,16 +1306,188 @@ where
         &self,
         txn: &mut RwTxn<'_>,
         scope_name: Option<&str>,
-    ) -> Result<(), ScopedDbError> {
+    ) -> Result<usize, ScopedDbError> {
         let scope = Scope::from(scope_name);
         self.clear(txn, &scope)
     }
 
+    /// Clear a scope and unregister it once no database holds its data anymore.
+    ///
+    /// This bundles tearing down a tenant in an application with a single database,
+    /// where `clear` alone leaves the scope registered until a prune pass. After clearing,
+    /// the scope is removed from the shared registry unless it still holds entries in one
+    /// of the databases in `also_empty_in`. Pass every other database sharing the registry
+    /// there, or an empty slice if this database is the only one; databases that are not
+    /// listed are not checked.
+    ///
+    /// Clearing the default scope never touches the registry.
+    ///
+    /// Returns the number of entries removed from this database.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let removed = db.clear_and_unregister(&mut wtxn, &tenant, &[&audit_log_db])?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_and_unregister(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        also_empty_in: &[&dyn ScopeEmptinessChecker],
+    ) -> Result<usize, ScopedDbError> {
+        let removed = self.clear(txn, scope)?;
+        self.global_registry
+            .unregister_if_unused(txn, scope, also_empty_in)?;
+        Ok(removed)
+    }
+
+    /// Remove every entry of this database and consume the handle.
+    ///
+    /// Both underlying heed databases (the default and the scoped one, or the single
+    /// database of the unified layout) are emptied, in every scope. Taking `self` by value
+    /// prevents accidental use of the handle afterwards.
+    ///
+    /// LMDB can only unlink a named database with `mdb_drop`, which heed does not expose,
+    /// so the now empty databases stay in the environment and keep occupying their
+    /// `max_dbs` slots until the environment is recreated. The global scope registry is
+    /// left unchanged, since other databases may still use its scopes; call
+    /// `GlobalScopeRegistry::prune_globally_unused_scopes` afterwards if needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::ScopedDbError;
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let mut wtxn = env.write_txn()?;
+    /// db.destroy(&mut wtxn)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn destroy(self, txn: &mut RwTxn<'_>) -> Result<(), ScopedDbError> {
+        self.db_scoped.clear(txn)?;
+        if !self.unified {
+            self.db_default.clear(txn)?;
+        }
+        Ok(())
+    }
+
+    /// Atomically swap the contents of two named scopes.
+    ///
+    /// After this call, every entry previously stored under `a` is stored under `b`
+    /// and vice versa. Both scopes are registered in the global registry, and the swap
+    /// is only visible to other transactions once `txn` is committed.
+    ///
+    /// # Memory
+    ///
+    /// Entries are re-prefixed one at a time through a temporary, unused scope hash,
+    /// so memory usage is bounded by the largest single key/value pair rather than the
+    /// size of either scope. Entries of `a` are rewritten twice and entries of `b` once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if either scope is the default scope, or if
+    /// registering either scope detects a hash collision.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let blue = Scope::named("tenant_blue")?;
+    /// let green = Scope::named("tenant_green")?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// db.swap_scopes(&mut wtxn, &blue, &green)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn swap_scopes(
+        &self,
+        txn: &mut RwTxn<'_>,
+        a: &Scope,
+        b: &Scope,
+    ) -> Result<(), ScopedDbError> {
+        if self.verify_scope_hashes {
+            a.check_hash()?;
+            b.check_hash()?;
+        }
+        let (Some(hash_a), Some(hash_b)) = (a.hash(), b.hash()) else {
+            return Err(ScopedDbError::InvalidInput(
+                "swap_scopes requires two named scopes".into(),
+            ));
+        };
+
+        // Both scopes must remain registered after the swap
+        self.register_written_scope(txn, a)?;
+        self.register_written_scope(txn, b)?;
+
+        utils::swap_scope_entries(
+            self.db_scoped.remap_types::<Bytes, Bytes>(),
+            txn,
+            hash_a,
+            hash_b,
+        )
+    }
+
+    /// Copy every entry of `src_scope` in `src` into `scope` of this database.
+    ///
+    /// `src` can live in another environment, in which case `src_txn` is a read
+    /// transaction of that environment. Entries are streamed from the source iterator and
+    /// written with `put`, so existing keys in `scope` are overwritten and observers see
+    /// one `on_put` per entry. The destination scope is registered even when the source
+    /// scope is empty.
+    ///
+    /// Returns the number of entries copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use scoped_heed::{Scope, ScopedDbError};
+    /// # fn main() -> Result<(), ScopedDbError> {
+    /// let tenant = Scope::named("tenant1")?;
+    /// let src_rtxn = old_env.read_txn()?;
+    /// let mut wtxn = new_env.write_txn()?;
+    /// let copied = new_db.extend_from(&mut wtxn, &tenant, &old_db, &src_rtxn, &tenant)?;
+    /// wtxn.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extend_from(
+        &self,
+        dst_txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        src: &Self,
+        src_txn: &RoTxn,
+        src_scope: &Scope,
+    ) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        self.register_written_scope(dst_txn, storage_scope.as_ref())?;
+
+        let mut copied = 0;
+        for entry in src.iter(src_txn, src_scope)? {
+            let (key, value) = entry?;
+            self.put(dst_txn, scope, &key, &value)?;
+            copied += 1;
+        }
+        Ok(copied)
+    }
+
     /// Checks if a scope is empty (contains no data).
     ///
     /// This is a helper method used by `find_empty_scopes` and the `ScopeEmptinessChecker` implementation.
     /// It uses efficient ranged iteration to only examine entries for the specified scope.
     fn is_scope_empty(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
         match scope {
             Scope::Default => {
                 // Check if the default database has any entries
@@ -514,18 +1507,12 @@ where
                 };
 
                 // End at the beginning of the next scope (or at the end for u32::MAX)
-                let end_bound = if scope_hash == u32::MAX {
-                    // Special case for MAX scope hash to avoid overflow
-                    Bound::Included(ScopedKey {
-                        scope_hash,
+                let end_bound = match utils::next_scope_bound(scope_hash) {
+                    Some(next_hash) => Bound::Excluded(ScopedKey {
+                        scope_hash: next_hash,
                         key: utils::get_key_default(),
-                    })
-                } else {
-                    // For all other cases, use next hash value as exclusive upper bound
-                    Bound::Excluded(ScopedKey {
-                        scope_hash: scope_hash + 1,
-                        key: utils::get_key_default(),
-                    })
+                    }),
+                    None => Bound::Unbounded,
                 };
 
                 // Create the range that covers only this scope
@@ -586,7 +1573,168 @@ where
             }
         }
 
-        Ok(empty_count)
+        Ok(empty_count)
+    }
+
+    /// Unregister the scopes that hold no data in this database.
+    ///
+    /// Returns the number of scopes removed from the registry. This is
+    /// `GlobalScopeRegistry::prune_globally_unused_scopes` with this database as the
+    /// only one to check, for applications with a single scoped database. The default
+    /// scope is never unregistered.
+    ///
+    /// The registry is shared by every database created with it, and this only looks
+    /// at this one: a scope holding data solely in another database is unregistered
+    /// too. With several databases, call `prune_globally_unused_scopes` with all of them
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut wtxn = env.write_txn()?;
+    /// db.clear(&mut wtxn, &tenant)?;
+    /// let pruned = db.prune_own_empty_scopes(&mut wtxn)?;
+    /// wtxn.commit()?;
+    /// ```
+    pub fn prune_own_empty_scopes(&self, txn: &mut RwTxn) -> Result<usize, ScopedDbError> {
+        self.global_registry
+            .prune_globally_unused_scopes(txn, &[self])
+    }
+
+    /// Returns LMDB B-tree statistics for the underlying databases.
+    ///
+    /// The first element describes the default database and the second the scoped
+    /// database, which holds the entries of all named scopes in a single B-tree.
+    /// Useful for diagnostics such as total entry counts, tree depth and page usage.
+    /// In unified databases both elements describe the single underlying database.
+    pub fn heed_stats(&self, txn: &RoTxn) -> Result<(DatabaseStat, DatabaseStat), ScopedDbError> {
+        let default_stat = self.db_default.stat(txn)?;
+        let scoped_stat = self.db_scoped.stat(txn)?;
+        Ok((default_stat, scoped_stat))
+    }
+
+    /// Returns the number of entries in every scope of this database.
+    ///
+    /// The default scope comes first, followed by each named scope holding at least one
+    /// entry, ordered by hash. The scoped database is walked once without decoding values,
+    /// which is much cheaper than calling `len` per scope. Scope names are resolved through
+    /// the registry; hashes without a registered name are reported with a hexadecimal
+    /// placeholder name.
+    pub fn per_scope_counts(&self, txn: &RoTxn) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        // Unified databases count default-scope entries along with the named scopes
+        let default_count = if self.unified {
+            None
+        } else {
+            Some(self.db_default.len(txn)?)
+        };
+        utils::count_entries_per_scope(
+            self.db_scoped.remap_types::<Bytes, DecodeIgnore>(),
+            txn,
+            &self.global_registry,
+            default_count,
+        )
+    }
+
+    /// Returns the number of entries in every scope, sorted by entry count.
+    ///
+    /// The counts come from `per_scope_counts`, so the database is walked once and names
+    /// are resolved through the registry, with a hexadecimal placeholder for unregistered
+    /// hashes. With `descending` the largest scopes come first. Scopes with equal counts
+    /// keep the order of `Scope`, so reports are stable between calls. The default scope
+    /// is always included, even when it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let rtxn = env.read_txn()?;
+    /// for (scope, count) in db.scopes_by_size(&rtxn, true)?.into_iter().take(10) {
+    ///     println!("{}: {} entries", scope.name().unwrap_or("<default>"), count);
+    /// }
+    /// ```
+    pub fn scopes_by_size(
+        &self,
+        txn: &RoTxn,
+        descending: bool,
+    ) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        let mut counts = self.per_scope_counts(txn)?;
+        utils::sort_by_count(&mut counts, descending);
+        Ok(counts)
+    }
+
+    /// Lists the distinct hashes of named scopes holding data in this database.
+    ///
+    /// Hashes are returned in ascending order. Each scope costs a single cursor seek, so
+    /// this is cheap even for large scopes. Unlike `list_scopes`, which reports the
+    /// registry, this reflects the data itself and also finds unregistered scopes.
+    pub fn distinct_scope_hashes(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        let mut hashes =
+            utils::distinct_scope_hashes(self.db_scoped.remap_types::<Bytes, DecodeIgnore>(), txn)?;
+        if self.unified {
+            // Default-scope entries are stored under the reserved hash
+            hashes.retain(|hash| *hash != DEFAULT_SCOPE_HASH);
+        }
+        Ok(hashes)
+    }
+
+    /// Lazily iterates over the hashes of named scopes holding data in this database.
+    ///
+    /// Hashes come in storage order, which follows the little-endian hash bytes. Each
+    /// step seeks straight past the current scope instead of reading its entries, so
+    /// callers can stop early without touching the rest of the database.
+    pub fn scope_hashes_present<'txn>(&self, txn: &'txn RoTxn<'txn>) -> ScopeHashIterResult<'txn> {
+        let unified = self.unified;
+        let iter =
+            utils::scope_hashes_present(self.db_scoped.remap_types::<Bytes, DecodeIgnore>(), txn)
+                // Default-scope entries of unified databases are stored under the reserved hash
+                .filter(move |result| !(unified && matches!(result, Ok(DEFAULT_SCOPE_HASH))));
+        Ok(Box::new(iter))
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    ///
+    /// Unlike `GlobalScopeRegistry::scope_exists`, which only consults the registry,
+    /// this looks at the data itself and stops at the first entry it finds.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        Ok(!self.is_scope_empty(txn, scope)?)
+    }
+
+    /// Returns the longest bincode-encoded key that can be written to `scope`.
+    ///
+    /// LMDB limits keys to `Env::max_key_size` bytes (511 with the default build). Keys
+    /// of named scopes, and of every scope in the unified layout, are stored as a
+    /// `ScopedKey` whose 4-byte scope hash is subtracted here. Writes with longer keys
+    /// fail with `ScopedDbError::InvalidInput`.
+    pub fn max_key_len(&self, scope: &Scope) -> usize {
+        self.env
+            .max_key_size()
+            .saturating_sub(self.key_overhead(scope))
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    ///
+    /// The handle is retained so that convenience methods can open their own
+    /// short-lived transactions; cloning an `Env` is cheap.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Whether default-scope entries share the scoped database.
+    ///
+    /// See the builder's `unified` option.
+    pub fn is_unified(&self) -> bool {
+        self.unified
+    }
+
+    /// Clear a scope in its own write transaction and commit it.
+    ///
+    /// Returns the number of entries removed. This must not be called while another
+    /// write transaction is open on the same thread; use `clear` to clear a scope as
+    /// part of a larger transaction.
+    pub fn clear_and_commit(&self, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let mut wtxn = self.env.write_txn()?;
+        let count = self.clear(&mut wtxn, scope)?;
+        wtxn.commit()?;
+        Ok(count)
     }
 
     /// Iterate over entries in a specific scope or the default database.
@@ -594,7 +1742,10 @@ where
     /// This method efficiently uses ranged iteration to retrieve only the entries
     /// belonging to the requested scope, rather than scanning the entire database.
     pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> IterResult<'txn, K, V> {
-        match scope {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result: IterResult<'txn, K, V> = match scope {
             Scope::Default => {
                 let iter = self
                     .db_default
@@ -615,19 +1766,12 @@ where
                 };
 
                 // End at the beginning of the next scope (or at the end for u32::MAX)
-                let end_bound = if scope_hash == u32::MAX {
-                    // Special case for MAX scope hash to avoid overflow
-                    Bound::Included(ScopedKey {
-                        scope_hash,
-                        // We rely on lexicographic ordering of scope_hash first
-                        key: utils::get_key_default(),
-                    })
-                } else {
-                    // For all other cases, use next hash value as exclusive upper bound
-                    Bound::Excluded(ScopedKey {
-                        scope_hash: scope_hash + 1,
+                let end_bound = match utils::next_scope_bound(scope_hash) {
+                    Some(next_hash) => Bound::Excluded(ScopedKey {
+                        scope_hash: next_hash,
                         key: utils::get_key_default(),
-                    })
+                    }),
+                    None => Bound::Unbounded,
                 };
 
                 // Create the range that covers only this scope
@@ -639,7 +1783,7 @@ where
                         .range(txn, &range)?
                         .filter_map(move |result| match result {
                             Ok((scoped_key, value)) => {
-                                // Double-check the scope hash (important for u32::MAX case)
+                                // Double-check the scope hash
                                 if scoped_key.scope_hash == scope_hash {
                                     Some(Ok((scoped_key.key, value)))
                                 } else {
@@ -650,7 +1794,93 @@ where
                         });
                 Ok(Box::new(iter))
             }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_iter(requested_scope);
+        }
+        result
+    }
+
+    /// Iterate over the entries of a scope that come after `after`, in the order of `iter`.
+    ///
+    /// The cursor seeks straight to the encoded key, so the last key of one page can be
+    /// handed back by a client as a continuation token and the next page starts there
+    /// without rescanning the scope. With `inclusive`, an entry stored under `after` is
+    /// yielded as well. `after` does not need to exist, and iteration stops at the end of
+    /// the scope.
+    ///
+    /// Unlike `range` this does not require `OrderPreservingKey`: entries follow the
+    /// storage order of the encoded keys, which is the order `iter` yields them in.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let page: Vec<_> = db.iter_from(&rtxn, &tenant, &last_seen, false)?.take(20).collect();
+    /// ```
+    pub fn iter_from<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        after: &K,
+        inclusive: bool,
+    ) -> IterResult<'txn, K, V> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let start_bound = |encoded: &[u8]| {
+            if inclusive {
+                Bound::Included(encoded.to_vec())
+            } else {
+                Bound::Excluded(encoded.to_vec())
+            }
+        };
+        let result: IterResult<'txn, K, V> = match scope {
+            Scope::Default => {
+                let start = start_bound(&SerdeBincode::<K>::bytes_encode(after)?);
+                let range = (start.as_ref().map(Vec::as_slice), Bound::<&[u8]>::Unbounded);
+                let iter = self
+                    .db_default
+                    .remap_key_type::<Bytes>()
+                    .range(txn, &range)?
+                    .map(|result| {
+                        let (key, value) = result?;
+                        Ok((SerdeBincode::<K>::bytes_decode(key)?, value))
+                    });
+                Ok(Box::new(iter))
+            }
+            Scope::Named { hash, .. } => {
+                let scoped_key = ScopedKey {
+                    scope_hash: *hash,
+                    key: after.clone(),
+                };
+                let start = start_bound(&SerdeBincode::<ScopedKey<K>>::bytes_encode(&scoped_key)?);
+                // Raw-byte end bound at the next scope's prefix, see `clear`
+                let next = utils::next_scope_bound(*hash).map(ScopedBytesCodec::hash_prefix);
+                let end = match &next {
+                    Some(next) => Bound::Excluded(&next[..]),
+                    None => Bound::Unbounded,
+                };
+                let range = (start.as_ref().map(Vec::as_slice), end);
+                let iter = self
+                    .db_scoped
+                    .remap_key_type::<Bytes>()
+                    .range(txn, &range)?
+                    .map(|result| {
+                        let (key, value) = result?;
+                        let scoped_key = SerdeBincode::<ScopedKey<K>>::bytes_decode(key)?;
+                        Ok((scoped_key.key, value))
+                    });
+                Ok(Box::new(iter))
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_iter(requested_scope);
         }
+        result
     }
 
     /// Iterate over entries in a specific scope or the default database using an Option<&str> scope name.
@@ -690,11 +1920,155 @@ where
         self.iter(txn, &scope)
     }
 
+    /// Iterate over entries in a specific scope, also yielding the scope hash stored
+    /// with each entry.
+    ///
+    /// This is intended for diagnosing suspected hash collisions or isolation issues
+    /// without dropping down to raw heed. The hash is read from the stored key rather
+    /// than copied from `scope`, and is `None` for entries in the default scope.
+    pub fn iter_with_hash<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+    ) -> HashIterResult<'txn, K, V> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.unified && scope.is_default() {
+            // Report default-scope entries without a hash, as in the two-database layout
+            let iter = self
+                .iter_with_hash(txn, &Scope::unified_default())?
+                .map(|result| result.map(|(_, key, value)| (None, key, value)));
+            return Ok(Box::new(iter));
+        }
+        match scope {
+            Scope::Default => {
+                let iter = self.db_default.iter(txn)?.map(|result| match result {
+                    Ok((key, value)) => Ok((None, key, value)),
+                    Err(e) => Err(ScopedDbError::from(e)),
+                });
+                Ok(Box::new(iter))
+            }
+            Scope::Named { hash, .. } => {
+                let scope_hash = *hash;
+
+                // Same bounds as a fully unbounded range over this scope
+                let range = Self::scoped_range_bounds(scope_hash, &(..));
+
+                let iter =
+                    self.db_scoped
+                        .range(txn, &range)?
+                        .filter_map(move |result| match result {
+                            Ok((scoped_key, value)) => {
+                                if scoped_key.scope_hash == scope_hash {
+                                    Some(Ok((Some(scoped_key.scope_hash), scoped_key.key, value)))
+                                } else {
+                                    None
+                                }
+                            }
+                            Err(e) => Some(Err(ScopedDbError::from(e))),
+                        });
+                Ok(Box::new(iter))
+            }
+        }
+    }
+
+    /// Iterate over the entries of several scopes, tagging each entry with its scope.
+    ///
+    /// The scopes are visited one after another in the order given, each with the same
+    /// ranged iteration as `iter`, so only the listed scopes are read. Listing a scope
+    /// twice yields its entries twice. Every scope is checked and its iterator set up
+    /// before the first entry is returned, so an invalid scope fails the call rather
+    /// than the iteration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tenants = [Scope::named("tenant1")?, Scope::named("tenant2")?];
+    /// for entry in db.iter_scopes(&rtxn, &tenants)? {
+    ///     let (scope, key, value) = entry?;
+    ///     println!("{}: {:?} = {:?}", scope, key, value);
+    /// }
+    /// ```
+    pub fn iter_scopes<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scopes: &[Scope],
+    ) -> MultiScopeIterResult<'txn, K, V> {
+        let mut iters = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let tag = scope.clone();
+            let iter = self.iter(txn, scope)?;
+            iters.push(iter.map(move |entry| entry.map(|(key, value)| (tag.clone(), key, value))));
+        }
+        Ok(Box::new(iters.into_iter().flatten()))
+    }
+
+    /// Transform user-supplied range bounds into bounds over `ScopedKey<K>`.
+    ///
+    /// Bounded ends are paired with the scope hash; unbounded ends are replaced with
+    /// the first key of this scope (start) or the first key of the next scope (end),
+    /// so that the resulting range never extends beyond the requested scope.
+    fn scoped_range_bounds<R>(
+        scope_hash: u32,
+        range: &R,
+    ) -> (Bound<ScopedKey<K>>, Bound<ScopedKey<K>>)
+    where
+        R: RangeBounds<K>,
+    {
+        // For start bound: map the user's bound to a scoped bound
+        let transformed_start = match range.start_bound() {
+            Bound::Included(key) => Bound::Included(ScopedKey {
+                scope_hash,
+                key: key.clone(),
+            }),
+            Bound::Excluded(key) => Bound::Excluded(ScopedKey {
+                scope_hash,
+                key: key.clone(),
+            }),
+            Bound::Unbounded => {
+                // Start from the beginning of this scope with minimum key
+                Bound::Included(ScopedKey {
+                    scope_hash,
+                    key: utils::get_key_default(),
+                })
+            }
+        };
+
+        // For end bound: carefully handle the unbounded case
+        let transformed_end = match range.end_bound() {
+            // If user provided a bounded end, use it with the same scope hash
+            Bound::Included(key) => Bound::Included(ScopedKey {
+                scope_hash,
+                key: key.clone(),
+            }),
+            Bound::Excluded(key) => Bound::Excluded(ScopedKey {
+                scope_hash,
+                key: key.clone(),
+            }),
+            // For unbounded end, we use the next scope's prefix as the exclusive upper bound
+            // This efficiently restricts the range to only the current scope
+            Bound::Unbounded => match utils::next_scope_bound(scope_hash) {
+                Some(next_hash) => Bound::Excluded(ScopedKey {
+                    scope_hash: next_hash,
+                    key: utils::get_key_default(),
+                }),
+                None => Bound::Unbounded,
+            },
+        };
+
+        (transformed_start, transformed_end)
+    }
+
     /// Iterate over a range of entries in a specific scope or the default database.
     ///
     /// This method efficiently handles all range types, including unbounded ranges,
     /// by properly constructing scope-aware range bounds for the underlying database.
     ///
+    /// The bounds are compared by LMDB on the bincode-encoded keys, so the key type must
+    /// implement `OrderPreservingKey`. Integer and string keys do not; see the trait
+    /// documentation for alternatives.
+    ///
     /// # Example
     ///
     /// ```rust,ignore
@@ -703,26 +2077,26 @@ where
     /// # use std::ops::Bound;
     /// # fn main() -> Result<(), ScopedDbError> {
     /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
-    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let db: ScopedDatabase<char, String> = ScopedDatabase::new(&env, "test")?;
     /// # let rtxn = env.read_txn()?;
     /// // Using different range types
     /// let tenant = Scope::named("tenant1")?;
     ///
     /// // Bounded range
-    /// let bounded = ("a".to_string()..="z".to_string());
+    /// let bounded = ('a'..='z');
     /// for result in db.range(&rtxn, &tenant, &bounded)? {
     ///     let (key, value) = result?;
     ///     println!("{}: {}", key, value);
     /// }
     ///
     /// // Unbounded start
-    /// let from_start = (..="z".to_string());
+    /// let from_start = (..='z');
     /// for result in db.range(&rtxn, &tenant, &from_start)? {
     ///     // ...
     /// }
     ///
     /// // Unbounded end (efficient implementation)
-    /// let to_end = ("m".to_string()..);
+    /// let to_end = ('m'..);
     /// for result in db.range(&rtxn, &tenant, &to_end)? {
     ///     // ...
     /// }
@@ -742,10 +2116,12 @@ where
         range: &'bounds_ref R,
     ) -> IterResult<'txn_ref, K, V>
     where
-        K: Clone + PartialOrd,
+        K: Clone + PartialOrd + OrderPreservingKey,
         R: RangeBounds<K> + 'bounds_ref,
         'bounds_ref: 'txn_ref,
     {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
         match scope {
             Scope::Default => {
                 let iter = self
@@ -758,59 +2134,7 @@ where
                 let scope_hash = *hash;
 
                 // Transform the range bounds to work with our ScopedKey<K> structure
-                use std::ops::Bound;
-
-                // For start bound: map the user's bound to a scoped bound
-                let transformed_start = match range.start_bound() {
-                    Bound::Included(key) => Bound::Included(ScopedKey {
-                        scope_hash,
-                        key: key.clone(),
-                    }),
-                    Bound::Excluded(key) => Bound::Excluded(ScopedKey {
-                        scope_hash,
-                        key: key.clone(),
-                    }),
-                    Bound::Unbounded => {
-                        // Start from the beginning of this scope with minimum key
-                        Bound::Included(ScopedKey {
-                            scope_hash,
-                            key: utils::get_key_default(),
-                        })
-                    }
-                };
-
-                // For end bound: carefully handle the unbounded case
-                let transformed_end = match range.end_bound() {
-                    // If user provided a bounded end, use it with the same scope hash
-                    Bound::Included(key) => Bound::Included(ScopedKey {
-                        scope_hash,
-                        key: key.clone(),
-                    }),
-                    Bound::Excluded(key) => Bound::Excluded(ScopedKey {
-                        scope_hash,
-                        key: key.clone(),
-                    }),
-                    Bound::Unbounded => {
-                        // For unbounded end, we use the next scope hash as the exclusive upper bound
-                        // This efficiently restricts the range to only the current scope
-                        if scope_hash == u32::MAX {
-                            // Special case for u32::MAX to avoid overflow
-                            Bound::Included(ScopedKey {
-                                scope_hash,
-                                // Use "maximum" key value - we rely on lexicographic ordering of scope_hash first
-                                key: utils::get_key_default(),
-                            })
-                        } else {
-                            // Normal case - use next hash value as the exclusive upper bound
-                            Bound::Excluded(ScopedKey {
-                                scope_hash: scope_hash + 1,
-                                key: utils::get_key_default(),
-                            })
-                        }
-                    }
-                };
-
-                let transformed_range = (transformed_start, transformed_end);
+                let transformed_range = Self::scoped_range_bounds(scope_hash, range);
 
                 let iter =
                     self.db_scoped
@@ -818,7 +2142,7 @@ where
                         .filter_map(move |result| match result {
                             Ok((scoped_key, value)) => {
                                 // Double-check the scope hash to ensure we're only getting entries
-                                // from the requested scope (important for the u32::MAX case)
+                                // from the requested scope
                                 if scoped_key.scope_hash == scope_hash {
                                     // Apply the original range bounds to the key
                                     let in_original_range =
@@ -855,6 +2179,35 @@ where
         }
     }
 
+    /// Iterate over a page of a range: skip the first `offset` entries, then yield at
+    /// most `limit` entries.
+    ///
+    /// Scope boundaries are honored exactly as in `range`. The cursor stops as soon as
+    /// the page is complete, so later entries of the range are never read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Third page of 20 entries
+    /// let page: Vec<_> = db.range_limited(&rtxn, &tenant, &("a".to_string()..), 40, 20)?.collect();
+    /// ```
+    pub fn range_limited<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        range: &'bounds R,
+        offset: usize,
+        limit: usize,
+    ) -> IterResult<'txn, K, V>
+    where
+        K: Clone + PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K> + 'bounds,
+        'bounds: 'txn,
+    {
+        let iter = self.range(txn, scope, range)?;
+        Ok(Box::new(iter.skip(offset).take(limit)))
+    }
+
     /// Iterate over a range of entries in a specific scope or the default database
     /// using an Option<&str> scope name.
     ///
@@ -869,10 +2222,10 @@ where
     /// # use std::ops::Bound;
     /// # fn main() -> Result<(), ScopedDbError> {
     /// # let env = unsafe { EnvOpenOptions::new().map_size(10*1024*1024).max_dbs(3).open("./db")? };
-    /// # let db: ScopedDatabase<String, String> = ScopedDatabase::new(&env, "test")?;
+    /// # let db: ScopedDatabase<char, String> = ScopedDatabase::new(&env, "test")?;
     /// # let rtxn = env.read_txn()?;
     /// // Use a range with explicit bounds
-    /// let range = ("a".to_string()..="z".to_string());
+    /// let range = ('a'..='z');
     ///
     /// // Iterate over a range in a specific scope
     /// for result in db.range_with_name(&rtxn, Some("tenant1"), &range)? {
@@ -895,7 +2248,7 @@ where
         range: &'bounds_ref R,
     ) -> IterResult<'txn_ref, K, V>
     where
-        K: Clone + PartialOrd,
+        K: Clone + PartialOrd + OrderPreservingKey,
         R: RangeBounds<K> + 'bounds_ref,
         'bounds_ref: 'txn_ref,
     {
@@ -904,6 +2257,327 @@ where
     }
 }
 
+impl<V> ScopedDatabase<String, V>
+where
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Iterate over the entries of a scope whose key starts with a string prefix.
+    ///
+    /// Keys are bincode-encoded with a length prefix ahead of the string bytes, so keys
+    /// sharing a prefix are not contiguous in LMDB order and cannot be selected with a
+    /// single range. Instead this walks the keys of the scope and yields the matching
+    /// entries in storage order.
+    ///
+    /// # Performance
+    ///
+    /// The cost is proportional to the number of entries in the scope, not the number of
+    /// matches. Values are only decoded for matching keys. For large scopes with frequent
+    /// prefix scans, prefer `ScopedBytesKeyDatabase` with a hierarchical key scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tenant = Scope::named("tenant1")?;
+    /// let rtxn = env.read_txn()?;
+    /// for result in db.iter_prefix(&rtxn, &tenant, "order:2024:")? {
+    ///     let (key, value) = result?;
+    /// }
+    /// ```
+    pub fn iter_prefix<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        prefix: &str,
+    ) -> IterResult<'txn, String, V> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let prefix = prefix.to_string();
+        match scope {
+            Scope::Default => {
+                let iter =
+                    self.db_default
+                        .lazily_decode_data()
+                        .iter(txn)?
+                        .filter_map(move |result| match result {
+                            Ok((key, value)) if key.starts_with(&prefix) => Some(
+                                value
+                                    .decode()
+                                    .map(|value| (key, value))
+                                    .map_err(ScopedDbError::from),
+                            ),
+                            Ok(_) => None,
+                            Err(e) => Some(Err(ScopedDbError::from(e))),
+                        });
+                Ok(Box::new(iter))
+            }
+            Scope::Named { hash, .. } => {
+                // Every key of this scope starts with the encoded scope hash
+                let iter = self
+                    .db_scoped
+                    .remap_key_type::<Bytes>()
+                    .lazily_decode_data()
+                    .prefix_iter(txn, &ScopedBytesCodec::hash_prefix(*hash))?
+                    .filter_map(move |result| {
+                        let (raw_key, value) = match result {
+                            Ok(entry) => entry,
+                            Err(e) => return Some(Err(ScopedDbError::from(e))),
+                        };
+                        let scoped_key =
+                            match SerdeBincode::<ScopedKey<String>>::bytes_decode(raw_key) {
+                                Ok(scoped_key) => scoped_key,
+                                Err(e) => return Some(Err(ScopedDbError::from(e))),
+                            };
+                        if !scoped_key.key.starts_with(&prefix) {
+                            return None;
+                        }
+                        Some(
+                            value
+                                .decode()
+                                .map(|value| (scoped_key.key, value))
+                                .map_err(ScopedDbError::from),
+                        )
+                    });
+                Ok(Box::new(iter))
+            }
+        }
+    }
+
+    /// Iterate over entries whose key starts with a string prefix using an Option<&str> scope name.
+    pub fn iter_prefix_with_name<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope_name: Option<&str>,
+        prefix: &str,
+    ) -> IterResult<'txn, String, V> {
+        let scope = Scope::from(scope_name);
+        self.iter_prefix(txn, &scope, prefix)
+    }
+}
+
+/// One line of an NDJSON dump, see `ScopedDatabase::dump_ndjson`
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct DumpRecord<'a, K, V> {
+    scope: Option<&'a str>,
+    key: &'a K,
+    value: &'a V,
+}
+
+/// One line of an NDJSON dump as read back by `ScopedDatabase::load_ndjson`
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+struct LoadRecord<K, V> {
+    scope: Option<String>,
+    key: K,
+    value: V,
+}
+
+#[cfg(feature = "json")]
+impl<K, V> ScopedDatabase<K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Write every entry of every scope to `writer` as newline-delimited JSON.
+    ///
+    /// Available with the `json` feature. Each line is an object of the form
+    /// `{"scope": "tenant1", "key": ..., "value": ...}`, with `"scope": null` for the
+    /// default scope. Unlike a copy of the LMDB file, the dump does not depend on the
+    /// bincode layout of keys and values, so it can move data across incompatible
+    /// versions and be inspected with standard tools. Default-scope entries come first,
+    /// followed by the named scopes in storage order; databases built with
+    /// `deny_default_scope` only dump their named scopes. Returns the number of entries
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if a scope holds data but has no name in the
+    /// registry, or only the placeholder name given by `GlobalScopeRegistry::repair` or
+    /// `backfill_orphan_scopes`, since it could not be restored by name.
+    /// `GlobalScopeRegistry::audit` finds such scopes, and registering the real name of
+    /// a placeholder scope makes it dumpable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let rtxn = env.read_txn()?;
+    /// let file = std::io::BufWriter::new(std::fs::File::create("dump.ndjson")?);
+    /// let count = db.dump_ndjson(&rtxn, file)?;
+    /// ```
+    pub fn dump_ndjson<W: io::Write>(
+        &self,
+        txn: &RoTxn,
+        mut writer: W,
+    ) -> Result<usize, ScopedDbError> {
+        let mut scopes = Vec::new();
+        if !self.deny_default_scope {
+            scopes.push((None, Scope::Default));
+        }
+        for hash in self.distinct_scope_hashes(txn)? {
+            let name = self
+                .global_registry
+                .get_scope_name(txn, &hash)?
+                .ok_or_else(|| {
+                    ScopedDbError::InvalidInput(format!(
+                        "Scope {} holds data but has no registered name",
+                        utils::placeholder_scope_name(hash)
+                    ))
+                })?;
+            // A placeholder name does not hash to the scope, so loading it would put
+            // the entries in a different scope
+            if compute_xxhash(name.as_bytes()) != hash {
+                return Err(ScopedDbError::InvalidInput(format!(
+                    "Scope {} holds data but is registered under the placeholder name '{}'; register its real name before dumping",
+                    utils::placeholder_scope_name(hash),
+                    name
+                )));
+            }
+            let scope = Scope::Named {
+                name: name.clone(),
+                hash,
+            };
+            scopes.push((Some(name), scope));
+        }
+
+        let mut count = 0;
+        for (name, scope) in &scopes {
+            for result in self.iter(txn, scope)? {
+                let (key, value) = result?;
+                let record = DumpRecord {
+                    scope: name.as_deref(),
+                    key: &key,
+                    value: &value,
+                };
+                serde_json::to_writer(&mut writer, &record)
+                    .map_err(|e| ScopedDbError::Encoding(e.to_string()))?;
+                writer.write_all(b"\n").map_err(heed::Error::Io)?;
+                count += 1;
+            }
+        }
+        writer.flush().map_err(heed::Error::Io)?;
+        Ok(count)
+    }
+
+    /// Insert the entries of an NDJSON dump written by `dump_ndjson`.
+    ///
+    /// Available with the `json` feature. Entries go through `put`, so named scopes are
+    /// registered as they are encountered and existing keys are overwritten. Blank lines
+    /// are skipped. Returns the number of entries inserted; nothing is committed, so a
+    /// failed load can be discarded by aborting `txn`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::Encoding` naming the line number if a line is not a valid
+    /// record for this database's key and value types.
+    pub fn load_ndjson<R: io::Read>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        reader: R,
+    ) -> Result<usize, ScopedDbError> {
+        let mut count = 0;
+        for (index, line) in io::BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(heed::Error::Io)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: LoadRecord<K, V> = serde_json::from_str(&line).map_err(|e| {
+                ScopedDbError::Encoding(format!("Line {} of the dump: {}", index + 1, e))
+            })?;
+            let scope = match &record.scope {
+                Some(name) => Scope::named(name)?,
+                None => Scope::Default,
+            };
+            self.put(txn, &scope, &record.key, &record.value)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// A pending read-modify-write of a single key, returned by `ScopedDatabase::entry`.
+///
+/// The entry caches the value read when it was created. `and_modify` only changes the
+/// cached value; the terminal methods `or_insert`, `or_insert_with` and `or_default`
+/// write it back if it was inserted or modified and return the resulting value.
+pub struct Entry<'a, 'txn, K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    db: &'a ScopedDatabase<K, V>,
+    txn: &'a mut RwTxn<'txn>,
+    scope: Scope,
+    requested_scope: Scope,
+    target: HeedDatabase<Bytes, SerdeBincode<V>>,
+    key: Vec<u8>,
+    value: Option<V>,
+    modified: bool,
+}
+
+impl<K, V> Entry<'_, '_, K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Whether the key held a value when the entry was created
+    pub fn is_occupied(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// The cached value, including changes made by `and_modify`
+    pub fn get(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    /// Modify the value in place if the key is occupied. Vacant entries are unchanged.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Some(value) = self.value.as_mut() {
+            f(value);
+            self.modified = true;
+        }
+        self
+    }
+
+    /// Insert `default` if the key is vacant, and return the stored value.
+    pub fn or_insert(self, default: V) -> Result<V, ScopedDbError> {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if the key is vacant, and return the stored value.
+    ///
+    /// `default` is only called for vacant entries.
+    pub fn or_insert_with<F>(mut self, default: F) -> Result<V, ScopedDbError>
+    where
+        F: FnOnce() -> V,
+    {
+        match self.value.take() {
+            Some(value) if !self.modified => Ok(value),
+            Some(value) => self.write(value),
+            None => self.write(default()),
+        }
+    }
+
+    /// Insert `V::default()` if the key is vacant, and return the stored value.
+    pub fn or_default(self) -> Result<V, ScopedDbError>
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    fn write(self, value: V) -> Result<V, ScopedDbError> {
+        self.db.register_written_scope(self.txn, &self.scope)?;
+        self.target.put(self.txn, &self.key, &value)?;
+        if let Some(observer) = &self.db.observer {
+            observer.on_put(&self.requested_scope);
+        }
+        Ok(value)
+    }
+}
+
 impl<K, V> Clone for ScopedDatabase<K, V>
 where
     K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
@@ -914,6 +2588,12 @@ where
             db_scoped: self.db_scoped,
             db_default: self.db_default,
             global_registry: self.global_registry.clone(),
+            env: self.env.clone(),
+            unified: self.unified,
+            observer: self.observer.clone(),
+            require_registered_scopes: self.require_registered_scopes,
+            verify_scope_hashes: self.verify_scope_hashes,
+            deny_default_scope: self.deny_default_scope,
             _phantom: PhantomData,
         }
     }
@@ -928,3 +2608,23 @@ where
         self.is_scope_empty(txn, scope)
     }
 }
+
+impl<K, V> ScopeHashEnumerator for ScopedDatabase<K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.distinct_scope_hashes(txn)
+    }
+}
+
+impl<K, V> ScopedClear for ScopedDatabase<K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.clear(txn, scope)
+    }
+}
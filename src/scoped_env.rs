@@ -0,0 +1,185 @@
+//! An environment wrapper that grows the memory map when writes run out of room.
+//!
+//! LMDB fixes the map size when the environment is opened, and a write transaction that
+//! needs more space fails with `MDB_MAP_FULL`. `ScopedEnv` runs transactions on behalf
+//! of the application so that, with `auto_grow`, it can resize the map and retry a write
+//! that failed this way, instead of requiring the map size to be guessed up front.
+
+use heed::{Env, MdbError, RoTxn, RwTxn};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::{ScopedDbError, maintenance};
+
+/// An LMDB environment whose write transactions can grow the map on `MDB_MAP_FULL`.
+///
+/// `read` and `write` open a transaction, pass it to a closure and close it again. With
+/// `auto_grow` enabled, a `write` whose closure or commit fails with `MDB_MAP_FULL` is
+/// aborted, the map is grown by the configured factor and the closure runs once more in
+/// a new transaction. The closure may therefore run twice, and must not have effects
+/// outside the transaction that would be wrong to repeat.
+///
+/// Resizing requires that no transaction is open on the environment in this process.
+/// Every transaction opened through a `ScopedEnv`, or any of its clones, holds a shared
+/// lock for its lifetime, and a resize takes the lock exclusively, so it waits for
+/// running transactions to finish and keeps new ones from starting. Transactions opened
+/// directly on the underlying `Env` bypass the lock, which is why enabling `auto_grow`
+/// is unsafe.
+///
+/// Databases are created with the builder as usual, passing `env()`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{scoped_database_options, Scope, ScopedEnv, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// // Safety: all transactions go through `scoped_env`
+/// let scoped_env = unsafe { ScopedEnv::new(env).auto_grow(2.0)? };
+/// let db = scoped_env.write(|wtxn| {
+///     scoped_database_options(scoped_env.env(), registry.clone())
+///         .raw_bytes()
+///         .name("blobs")
+///         .create(wtxn)
+/// })?;
+///
+/// let tenant = Scope::named("tenant1")?;
+/// scoped_env.write(|wtxn| db.put(wtxn, &tenant, b"large", &payload))?;
+/// let len = scoped_env.read(|rtxn| Ok(db.get(rtxn, &tenant, b"large")?.map(<[u8]>::len)))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedEnv {
+    env: Env,
+    growth_factor: Option<f64>,
+    /// Held shared by every open transaction and exclusively while resizing
+    resize_lock: Arc<RwLock<()>>,
+}
+
+impl ScopedEnv {
+    /// Wrap an environment, without automatic growth.
+    pub fn new(env: Env) -> Self {
+        Self {
+            env,
+            growth_factor: None,
+            resize_lock: Arc::default(),
+        }
+    }
+
+    /// Grow the map by `factor` and retry once when a `write` fails with `MDB_MAP_FULL`.
+    ///
+    /// The new map size is the current one multiplied by `factor`, rounded up to whole
+    /// pages. The map size bounds the data file, which grows on demand, so a large factor
+    /// costs address space rather than disk space. If the retry also fails with
+    /// `MDB_MAP_FULL`, e.g. because a single transaction writes more than the grown map
+    /// can hold, its error is returned.
+    ///
+    /// # Safety
+    ///
+    /// Growing the map resizes the environment with `Env::resize`, which must not happen
+    /// while any transaction is open on it in this process. `ScopedEnv` only knows about
+    /// its own transactions: while automatic growth is enabled, every transaction on the
+    /// environment must be opened through `read` or `write` of this `ScopedEnv` or one
+    /// of its clones. The closures must not call `read` or `write` themselves, as a
+    /// resize waiting for the outer transaction would then never proceed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` unless `factor` is a finite number greater
+    /// than 1.
+    pub unsafe fn auto_grow(mut self, factor: f64) -> Result<Self, ScopedDbError> {
+        if !(factor.is_finite() && factor > 1.0) {
+            return Err(ScopedDbError::InvalidInput(format!(
+                "Map growth factor must be a finite number greater than 1, got {}",
+                factor
+            )));
+        }
+        self.growth_factor = Some(factor);
+        Ok(self)
+    }
+
+    /// The growth factor set with `auto_grow`, if any
+    pub fn growth_factor(&self) -> Option<f64> {
+        self.growth_factor
+    }
+
+    /// Returns the wrapped LMDB environment.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Run `f` in a read transaction.
+    pub fn read<T, F>(&self, f: F) -> Result<T, ScopedDbError>
+    where
+        F: FnOnce(&RoTxn) -> Result<T, ScopedDbError>,
+    {
+        let _guard = self
+            .resize_lock
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let rtxn = self.env.read_txn()?;
+        f(&rtxn)
+    }
+
+    /// Run `f` in a write transaction and commit it if `f` succeeds.
+    ///
+    /// If `f` fails the transaction is aborted and its error returned. With `auto_grow`,
+    /// a failure with `MDB_MAP_FULL`, from `f` or from the commit, grows the map and runs
+    /// `f` again in a new transaction, once.
+    pub fn write<T, F>(&self, mut f: F) -> Result<T, ScopedDbError>
+    where
+        F: FnMut(&mut RwTxn) -> Result<T, ScopedDbError>,
+    {
+        let map_size = self.env.info().map_size;
+        match self.write_once(&mut f) {
+            Err(error) if is_map_full(&error) && self.growth_factor.is_some() => {
+                self.grow_from(map_size)?;
+                self.write_once(&mut f)
+            }
+            result => result,
+        }
+    }
+
+    fn write_once<T, F>(&self, f: &mut F) -> Result<T, ScopedDbError>
+    where
+        F: FnMut(&mut RwTxn) -> Result<T, ScopedDbError>,
+    {
+        let _guard = self
+            .resize_lock
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut wtxn = self.env.write_txn()?;
+        let value = f(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok(value)
+    }
+
+    /// Grow the map by the growth factor, unless another writer already grew it past
+    /// `map_size`, the size seen when the failed write started
+    fn grow_from(&self, map_size: usize) -> Result<(), ScopedDbError> {
+        let Some(factor) = self.growth_factor else {
+            return Ok(());
+        };
+        let _guard = self
+            .resize_lock
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if self.env.info().map_size > map_size {
+            return Ok(());
+        }
+        let page_size = maintenance::page_size(&self.env)?;
+        let target = (map_size as f64 * factor) as usize;
+        let new_size = target.div_ceil(page_size).saturating_mul(page_size);
+        // Safety: The exclusive lock waits for every transaction opened through this
+        // `ScopedEnv`, and `auto_grow` requires that no other transactions exist
+        unsafe { self.env.resize(new_size)? };
+        Ok(())
+    }
+}
+
+/// Whether an error is LMDB running out of map space
+fn is_map_full(error: &ScopedDbError) -> bool {
+    matches!(
+        error,
+        ScopedDbError::Heed(heed::Error::Mdb(MdbError::MapFull))
+    )
+}
@@ -0,0 +1,552 @@
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{Database as HeedDatabase, DatabaseFlags, Env, RoTxn, RwTxn};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use crate::global_registry::{
+    GlobalScopeRegistry, ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear,
+};
+use crate::observer::OperationObserver;
+use crate::utils;
+use crate::{
+    BytesIterResult, BytesValueIterResult, DEFAULT_SCOPE_HASH, Scope, ScopedBytesCodec,
+    ScopedDbError,
+};
+
+/// Scoped raw bytes database holding several values per key.
+///
+/// Built on LMDB's `MDB_DUPSORT`: each key maps to a sorted set of distinct values,
+/// which suits adjacency lists, tags or secondary indexes. Keys are scoped exactly like
+/// in `ScopedBytesDatabase`, so scopes stay isolated and the same registry is shared.
+///
+/// `put` adds a value to the key's set, and adding a value that is already present is a
+/// no-op. `get_all` yields the values of a key in byte order, `delete_one` removes a
+/// single value and `delete` removes the key with all of its values.
+///
+/// LMDB stores duplicate values like keys, so each value is limited to the maximum key
+/// size of the environment (511 bytes by default). The underlying heed databases are
+/// created with `DUP_SORT`, and LMDB refuses to open them without that flag, so an
+/// existing single-value database cannot be reopened as a multi-value one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{scoped_database_options, Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let mut wtxn = env.write_txn()?;
+/// let followers = scoped_database_options(&env, registry.clone())
+///     .multi_bytes()
+///     .name("followers")
+///     .create(&mut wtxn)?;
+///
+/// let tenant = Scope::named("tenant1")?;
+/// followers.put(&mut wtxn, &tenant, b"alice", b"bob")?;
+/// followers.put(&mut wtxn, &tenant, b"alice", b"carol")?;
+/// wtxn.commit()?;
+///
+/// let rtxn = env.read_txn()?;
+/// for follower in followers.get_all(&rtxn, &tenant, b"alice")? {
+///     println!("{:?}", follower?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScopedMultiBytesDatabase {
+    db_scoped: HeedDatabase<ScopedBytesCodec, Bytes>,
+    db_default: HeedDatabase<Bytes, Bytes>,
+    global_registry: Arc<GlobalScopeRegistry>,
+    env: Env,
+    unified: bool,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+}
+
+impl ScopedMultiBytesDatabase {
+    /// Create a new ScopedMultiBytesDatabase with a provided transaction
+    ///
+    /// This method is intended to be called through the builder pattern.
+    pub(crate) fn create(
+        env: &Env,
+        name: &str,
+        txn: &mut RwTxn,
+        registry: Arc<GlobalScopeRegistry>,
+        unified: bool,
+    ) -> Result<Self, ScopedDbError> {
+        if unified {
+            // A single database holds every scope; default-scope keys use the reserved hash
+            let db_scoped = env
+                .database_options()
+                .types::<ScopedBytesCodec, Bytes>()
+                .flags(DatabaseFlags::DUP_SORT)
+                .name(&format!("{}_unified", name))
+                .create(txn)?;
+
+            return Ok(Self {
+                db_scoped,
+                // Never read: default-scope operations are routed to `db_scoped`
+                db_default: db_scoped.remap_key_type::<Bytes>(),
+                global_registry: registry,
+                env: env.clone(),
+                unified,
+                observer: None,
+                require_registered_scopes: false,
+                verify_scope_hashes: false,
+                deny_default_scope: false,
+            });
+        }
+
+        let db_default = env
+            .database_options()
+            .types::<Bytes, Bytes>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name(name)
+            .create(txn)?;
+
+        let db_scoped = env
+            .database_options()
+            .types::<ScopedBytesCodec, Bytes>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name(&format!("{}_scoped", name))
+            .create(txn)?;
+
+        Ok(Self {
+            db_scoped,
+            db_default,
+            global_registry: registry,
+            env: env.clone(),
+            unified,
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+        })
+    }
+
+    /// Attach the observer configured on the builder
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn OperationObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Reject writes to unregistered scopes instead of registering them
+    pub(crate) fn with_require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// Check the hash of every scope passed to this database
+    pub(crate) fn with_verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// Reject every operation on the default scope
+    pub(crate) fn with_deny_default_scope(mut self, deny: bool) -> Self {
+        self.deny_default_scope = deny;
+        self
+    }
+
+    /// Resolve the storage scope of `scope`, after the checks enabled on the builder:
+    /// `deny_default_scope` and `verify_scope_hashes`
+    fn storage_scope<'s>(&self, scope: &'s Scope) -> Result<Cow<'s, Scope>, ScopedDbError> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.verify_scope_hashes {
+            scope.check_hash()?;
+        }
+        scope.storage_scope(self.unified)
+    }
+
+    /// Registers a scope in the global registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a hash collision between different scope names.
+    pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        // The storage scope of unified default-scope entries is never registered
+        if let Scope::Named { .. } = scope
+            && !(self.unified && scope.is_unified_default())
+        {
+            self.global_registry.register_scope(txn, scope)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Registers the scope of a write, or checks that it is already registered when the
+    /// database was built with `require_registered_scopes`
+    fn register_written_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        if !self.require_registered_scopes {
+            return self.register_scope(txn, scope);
+        }
+        if self.unified && scope.is_unified_default() {
+            return Ok(());
+        }
+        self.global_registry.ensure_registered(txn, scope)
+    }
+
+    /// Bytes the scope header adds to keys written to `scope`
+    fn key_overhead(&self, scope: &Scope) -> usize {
+        if self.unified || !scope.is_default() {
+            ScopedBytesCodec::HEADER_LEN
+        } else {
+            0
+        }
+    }
+
+    /// Lists all known scopes in the database, including the Default scope.
+    pub fn list_scopes(&self, txn: &RoTxn) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_all_scopes(txn)
+    }
+
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.global_registry.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    ///
+    /// Unlike `GlobalScopeRegistry::scope_exists`, which only consults the registry,
+    /// this looks at the data itself and stops at the first entry it finds.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        Ok(!self.is_scope_empty(txn, scope)?)
+    }
+
+    /// Returns the longest key that can be written to `scope`.
+    ///
+    /// See `ScopedBytesDatabase::max_key_len`.
+    pub fn max_key_len(&self, scope: &Scope) -> usize {
+        self.env
+            .max_key_size()
+            .saturating_sub(self.key_overhead(scope))
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Returns whether this database stores every scope in a single heed database.
+    pub fn is_unified(&self) -> bool {
+        self.unified
+    }
+
+    /// Add a value to the set of values stored under a key.
+    ///
+    /// Adding a value that is already stored under the key leaves the set unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::InvalidInput` if the key does not fit in LMDB with its
+    /// scope prefix, or if the value exceeds the maximum key size of the environment.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), ScopedDbError> {
+        utils::check_key_size(&self.env, key.len(), self.key_overhead(scope))?;
+        utils::check_dup_value_size(&self.env, value.len())?;
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
+            Scope::Default => self
+                .db_default
+                .put(txn, key, value)
+                .map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => {
+                self.register_written_scope(txn, scope)?;
+                self.db_scoped
+                    .put(txn, &(*hash, key), value)
+                    .map_err(ScopedDbError::from)
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(requested_scope);
+        }
+        result
+    }
+
+    /// Iterate over all values stored under a key, in byte order.
+    ///
+    /// The iterator is empty if the key does not exist.
+    pub fn get_all<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> BytesValueIterResult<'txn> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result: BytesValueIterResult<'txn> = match scope {
+            Scope::Default => match self.db_default.get_duplicates(txn, key)? {
+                Some(iter) => Ok(Box::new(iter.map(|result| {
+                    result.map(|(_, value)| value).map_err(ScopedDbError::from)
+                }))),
+                None => Ok(Box::new(std::iter::empty())),
+            },
+            Scope::Named { hash, .. } => match self.db_scoped.get_duplicates(txn, &(*hash, key))? {
+                Some(iter) => Ok(Box::new(iter.map(|result| {
+                    result.map(|(_, value)| value).map_err(ScopedDbError::from)
+                }))),
+                None => Ok(Box::new(std::iter::empty())),
+            },
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_iter(requested_scope);
+        }
+        result
+    }
+
+    /// Check whether `value` is one of the values stored under a key.
+    pub fn contains(
+        &self,
+        txn: &RoTxn,
+        scope: &Scope,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        // Values are sorted, so stop at the first one past `value`
+        match scope {
+            Scope::Default => {
+                sorted_values_contain(self.db_default.get_duplicates(txn, key)?, value)
+            }
+            Scope::Named { hash, .. } => {
+                sorted_values_contain(self.db_scoped.get_duplicates(txn, &(*hash, key))?, value)
+            }
+        }
+    }
+
+    /// Remove a single value from the set stored under a key.
+    ///
+    /// Returns whether the value was present. The key disappears with its last value.
+    pub fn delete_one(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
+            Scope::Default => self
+                .db_default
+                .delete_one_duplicate(txn, key, value)
+                .map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => self
+                .db_scoped
+                .delete_one_duplicate(txn, &(*hash, key), value)
+                .map_err(ScopedDbError::from),
+        };
+        if let Ok(deleted) = result
+            && let Some(observer) = &self.observer
+        {
+            observer.on_delete(requested_scope, deleted);
+        }
+        result
+    }
+
+    /// Remove a key together with all of its values.
+    ///
+    /// Returns whether the key existed.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result = match scope {
+            Scope::Default => self
+                .db_default
+                .delete(txn, key)
+                .map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => self
+                .db_scoped
+                .delete(txn, &(*hash, key))
+                .map_err(ScopedDbError::from),
+        };
+        if let Ok(deleted) = result
+            && let Some(observer) = &self.observer
+        {
+            observer.on_delete(requested_scope, deleted);
+        }
+        result
+    }
+
+    /// Clear all entries within a scope.
+    ///
+    /// Returns the number of key/value pairs removed, counting every value of a key.
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        match scope {
+            Scope::Default => {
+                let count = self.db_default.len(txn)? as usize;
+                self.db_default.clear(txn)?;
+                Ok(count)
+            }
+            Scope::Named { hash, .. } => {
+                self.register_written_scope(txn, scope)?;
+                let start_bound = Bound::Included((*hash, &[][..]));
+                let end_bound = match utils::next_scope_bound(*hash) {
+                    Some(next_hash) => Bound::Excluded((next_hash, &[][..])),
+                    None => Bound::Unbounded,
+                };
+                let count = self
+                    .db_scoped
+                    .delete_range(txn, &(start_bound, end_bound))?;
+                Ok(count)
+            }
+        }
+    }
+
+    /// Iterate over every key/value pair of a scope.
+    ///
+    /// Keys with several values are yielded once per value, in key and then value order.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> BytesIterResult<'txn> {
+        let requested_scope = scope;
+        let storage_scope = self.storage_scope(scope)?;
+        let scope = storage_scope.as_ref();
+        let result: BytesIterResult<'txn> = match scope {
+            Scope::Default => {
+                let iter = self
+                    .db_default
+                    .iter(txn)?
+                    .map(|result| result.map_err(ScopedDbError::from));
+                Ok(Box::new(iter))
+            }
+            Scope::Named { hash, .. } => {
+                let start_bound = Bound::Included((*hash, &[][..]));
+                let end_bound = match utils::next_scope_bound(*hash) {
+                    Some(next_hash) => Bound::Excluded((next_hash, &[][..])),
+                    None => Bound::Unbounded,
+                };
+                let iter = self
+                    .db_scoped
+                    .range(txn, &(start_bound, end_bound))?
+                    .map(|result| {
+                        result
+                            .map(|((_, key), value)| (key, value))
+                            .map_err(ScopedDbError::from)
+                    });
+                Ok(Box::new(iter))
+            }
+        };
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_iter(requested_scope);
+        }
+        result
+    }
+
+    /// Lists the distinct hashes of named scopes holding data in this database.
+    pub fn distinct_scope_hashes(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        let mut hashes =
+            utils::distinct_scope_hashes(self.db_scoped.remap_types::<Bytes, DecodeIgnore>(), txn)?;
+        if self.unified {
+            // Default-scope entries are stored under the reserved hash
+            hashes.retain(|hash| *hash != DEFAULT_SCOPE_HASH);
+        }
+        Ok(hashes)
+    }
+
+    fn is_scope_empty(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        let storage_scope = self.storage_scope(scope)?;
+        match storage_scope.as_ref() {
+            Scope::Default => self.db_default.is_empty(txn).map_err(ScopedDbError::from),
+            Scope::Named { hash, .. } => {
+                let prefix = ScopedBytesCodec::hash_prefix(*hash);
+                let mut iter = self
+                    .db_scoped
+                    .remap_types::<Bytes, DecodeIgnore>()
+                    .prefix_iter(txn, &prefix)?;
+                Ok(iter.next().is_none())
+            }
+        }
+    }
+}
+
+impl Clone for ScopedMultiBytesDatabase {
+    fn clone(&self) -> Self {
+        Self {
+            db_scoped: self.db_scoped,
+            db_default: self.db_default,
+            global_registry: self.global_registry.clone(),
+            env: self.env.clone(),
+            unified: self.unified,
+            observer: self.observer.clone(),
+            require_registered_scopes: self.require_registered_scopes,
+            verify_scope_hashes: self.verify_scope_hashes,
+            deny_default_scope: self.deny_default_scope,
+        }
+    }
+}
+
+/// Whether a key's values, yielded in LMDB's sorted order, include `value`
+fn sorted_values_contain<'txn, K>(
+    values: Option<impl Iterator<Item = heed::Result<(K, &'txn [u8])>>>,
+    value: &[u8],
+) -> Result<bool, ScopedDbError> {
+    for entry in values.into_iter().flatten() {
+        let (_, stored) = entry?;
+        match stored.cmp(value) {
+            Ordering::Less => continue,
+            Ordering::Equal => return Ok(true),
+            Ordering::Greater => break,
+        }
+    }
+    Ok(false)
+}
+
+impl ScopeEmptinessChecker for ScopedMultiBytesDatabase {
+    fn is_scope_empty_in_db(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        self.is_scope_empty(txn, scope)
+    }
+}
+
+impl ScopeHashEnumerator for ScopedMultiBytesDatabase {
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.distinct_scope_hashes(txn)
+    }
+}
+
+impl ScopedClear for ScopedMultiBytesDatabase {
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.clear(txn, scope)
+    }
+}
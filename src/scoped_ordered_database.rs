@@ -0,0 +1,510 @@
+use heed::types::{Bytes, DecodeIgnore, SerdeBincode};
+use heed::{Database as HeedDatabase, Env, RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+use crate::global_registry::{
+    GlobalScopeRegistry, ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear,
+};
+use crate::observer::OperationObserver;
+use crate::utils::{self, Neighbor, SortableKey};
+use crate::{DEFAULT_SCOPE_HASH, IterResult, Scope, ScopedDbError};
+
+/// A scoped database whose keys sort in their logical order.
+///
+/// `ScopedDatabase` encodes keys with bincode, which writes integers little-endian and
+/// prefixes strings with their length, so range queries over most key types return
+/// entries in the wrong order. This database encodes keys with `SortableKey` instead:
+///
+/// ```text
+/// [scope_hash_be: 4 bytes][sortable key encoding]
+/// ```
+///
+/// The scope hash is big-endian, so the keys of a scope form one contiguous block that
+/// ends right before the block of `hash + 1`. Within a scope, `range`, `ceiling`,
+/// `floor`, `higher` and `lower` follow the numeric order of integer keys, including
+/// negative ones, and the byte order of `String` keys.
+///
+/// Like the unified layout, every entry lives in a single heed database, named
+/// `<name>_ordered`, and default-scope keys use the reserved `DEFAULT_SCOPE_HASH`.
+/// The distinct name keeps this format from being opened as a bincode-keyed database
+/// or the other way around. The format is chosen when the database is created with
+/// the builder's `ordered_keys` option.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{scoped_database_options, Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let mut wtxn = env.write_txn()?;
+/// let db = scoped_database_options(&env, registry.clone())
+///     .types::<i64, String>()
+///     .ordered_keys()
+///     .name("events")
+///     .create(&mut wtxn)?;
+///
+/// let tenant = Scope::named("tenant1")?;
+/// db.put(&mut wtxn, &tenant, &-5, &"before".to_string())?;
+/// db.put(&mut wtxn, &tenant, &300, &"after".to_string())?;
+/// wtxn.commit()?;
+///
+/// let rtxn = env.read_txn()?;
+/// for result in db.range(&rtxn, &tenant, &(-10..=1000))? {
+///     let (timestamp, event) = result?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    db: HeedDatabase<Bytes, SerdeBincode<V>>,
+    global_registry: Arc<GlobalScopeRegistry>,
+    env: Env,
+    observer: Option<Arc<dyn OperationObserver>>,
+    require_registered_scopes: bool,
+    verify_scope_hashes: bool,
+    deny_default_scope: bool,
+    _phantom: PhantomData<K>,
+}
+
+impl<K, V> ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Create a new ScopedOrderedDatabase with a provided transaction
+    ///
+    /// This method is intended to be called through the builder pattern.
+    pub(crate) fn create(
+        env: &Env,
+        name: &str,
+        txn: &mut RwTxn,
+        registry: Arc<GlobalScopeRegistry>,
+    ) -> Result<Self, ScopedDbError> {
+        let db = env
+            .database_options()
+            .types::<Bytes, SerdeBincode<V>>()
+            .name(&format!("{}_ordered", name))
+            .create(txn)?;
+
+        Ok(Self {
+            db,
+            global_registry: registry,
+            env: env.clone(),
+            observer: None,
+            require_registered_scopes: false,
+            verify_scope_hashes: false,
+            deny_default_scope: false,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Attach the observer configured on the builder
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn OperationObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Reject writes to unregistered scopes instead of registering them
+    pub(crate) fn with_require_registered_scopes(mut self, require: bool) -> Self {
+        self.require_registered_scopes = require;
+        self
+    }
+
+    /// Check the hash of every scope passed to this database
+    pub(crate) fn with_verify_scope_hashes(mut self, verify: bool) -> Self {
+        self.verify_scope_hashes = verify;
+        self
+    }
+
+    /// Reject every operation on the default scope
+    pub(crate) fn with_deny_default_scope(mut self, deny: bool) -> Self {
+        self.deny_default_scope = deny;
+        self
+    }
+
+    /// The hash whose prefix holds the entries of `scope`, after the checks enabled on
+    /// the builder: `deny_default_scope` and `verify_scope_hashes`
+    fn storage_hash(&self, scope: &Scope) -> Result<u32, ScopedDbError> {
+        if self.deny_default_scope {
+            scope.check_not_default()?;
+        }
+        if self.verify_scope_hashes {
+            scope.check_hash()?;
+        }
+        storage_hash(scope)
+    }
+
+    /// Registers a scope in the global registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a hash collision between different scope names.
+    pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        // The storage scope of default-scope entries is never registered
+        if let Scope::Named { .. } = scope
+            && !scope.is_unified_default()
+        {
+            self.global_registry.register_scope(txn, scope)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Registers the scope of a write, or checks that it is already registered when the
+    /// database was built with `require_registered_scopes`
+    fn register_written_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        if !self.require_registered_scopes {
+            return self.register_scope(txn, scope);
+        }
+        if scope.is_unified_default() {
+            return Ok(());
+        }
+        self.global_registry.ensure_registered(txn, scope)
+    }
+
+    /// Lists all known scopes in the database, including the Default scope.
+    pub fn list_scopes(&self, txn: &RoTxn) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_all_scopes(txn)
+    }
+
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.global_registry.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.global_registry.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    ///
+    /// Unlike `GlobalScopeRegistry::scope_exists`, which only consults the registry,
+    /// this looks at the data itself and stops at the first entry it finds.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        Ok(!self.is_scope_empty_in_db(txn, scope)?)
+    }
+
+    /// Returns the longest sortable key encoding that can be written to `scope`.
+    ///
+    /// Every key carries the 4-byte scope hash, which is subtracted from the LMDB limit
+    /// of `Env::max_key_size`. Writes with longer keys fail with
+    /// `ScopedDbError::InvalidInput`.
+    pub fn max_key_len(&self, _scope: &Scope) -> usize {
+        self.env.max_key_size().saturating_sub(HASH_PREFIX_LEN)
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+        value: &V,
+    ) -> Result<(), ScopedDbError> {
+        let hash = self.storage_hash(scope)?;
+        let encoded = encode_key(hash, key);
+        utils::check_key_size(&self.env, encoded.len() - HASH_PREFIX_LEN, HASH_PREFIX_LEN)?;
+        self.register_written_scope(txn, scope)?;
+        let result = self
+            .db
+            .put(txn, &encoded, value)
+            .map_err(ScopedDbError::from);
+        if result.is_ok()
+            && let Some(observer) = &self.observer
+        {
+            observer.on_put(scope);
+        }
+        result
+    }
+
+    /// Get a value from the database.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<V>, ScopedDbError> {
+        let hash = self.storage_hash(scope)?;
+        let result = self
+            .db
+            .get(txn, &encode_key(hash, key))
+            .map_err(ScopedDbError::from);
+        if let Ok(value) = &result
+            && let Some(observer) = &self.observer
+        {
+            observer.on_get(scope, value.is_some());
+        }
+        result
+    }
+
+    /// Get a value that is expected to be present.
+    ///
+    /// See `ScopedDatabase::get_expect`.
+    pub fn get_expect<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<V, ScopedDbError> {
+        self.get(txn, scope, key)?.ok_or(ScopedDbError::NotFound)
+    }
+
+    /// Delete a key-value pair from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        let hash = self.storage_hash(scope)?;
+        let result = self
+            .db
+            .delete(txn, &encode_key(hash, key))
+            .map_err(ScopedDbError::from);
+        if let Ok(deleted) = result
+            && let Some(observer) = &self.observer
+        {
+            observer.on_delete(scope, deleted);
+        }
+        result
+    }
+
+    /// Clear all entries of a scope, returning the number of entries removed.
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        let hash = self.storage_hash(scope)?;
+        self.register_written_scope(txn, scope)?;
+        utils::delete_key_prefix(self.db.remap_data_type::<Bytes>(), txn, &hash.to_be_bytes())
+    }
+
+    /// Iterate over the entries of a scope in key order.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> IterResult<'txn, K, V> {
+        self.range(txn, scope, &..)
+    }
+
+    /// Iterate over the entries of a scope whose keys fall within `range`, in key order.
+    pub fn range<'txn, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        range: &R,
+    ) -> IterResult<'txn, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let hash = self.storage_hash(scope)?;
+        let start = match range.start_bound() {
+            Bound::Included(key) => Bound::Included(encode_key(hash, key)),
+            Bound::Excluded(key) => Bound::Excluded(encode_key(hash, key)),
+            Bound::Unbounded => Bound::Included(hash.to_be_bytes().to_vec()),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(encode_key(hash, key)),
+            Bound::Excluded(key) => Bound::Excluded(encode_key(hash, key)),
+            Bound::Unbounded => match hash.checked_add(1) {
+                Some(next_hash) => Bound::Excluded(next_hash.to_be_bytes().to_vec()),
+                None => Bound::Unbounded,
+            },
+        };
+        let bounds = (
+            start.as_ref().map(Vec::as_slice),
+            end.as_ref().map(Vec::as_slice),
+        );
+        let iter = self.db.range(txn, &bounds)?.map(|result| {
+            let (encoded_key, value) = result?;
+            Ok((decode_key(encoded_key)?, value))
+        });
+        if let Some(observer) = &self.observer {
+            observer.on_iter(scope);
+        }
+        Ok(Box::new(iter))
+    }
+
+    /// Returns the entry with the smallest key greater than or equal to `key` in a scope.
+    pub fn ceiling<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        self.neighbor(txn, scope, key, Neighbor::Ceiling)
+    }
+
+    /// Returns the entry with the smallest key strictly greater than `key` in a scope.
+    pub fn higher<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        self.neighbor(txn, scope, key, Neighbor::Higher)
+    }
+
+    /// Returns the entry with the largest key less than or equal to `key` in a scope.
+    pub fn floor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        self.neighbor(txn, scope, key, Neighbor::Floor)
+    }
+
+    /// Returns the entry with the largest key strictly less than `key` in a scope.
+    pub fn lower<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        self.neighbor(txn, scope, key, Neighbor::Lower)
+    }
+
+    fn neighbor<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+        neighbor: Neighbor,
+    ) -> Result<Option<(K, V)>, ScopedDbError> {
+        let hash = self.storage_hash(scope)?;
+        let encoded = encode_key(hash, key);
+        let bounds = neighbor.bounds(encoded.as_slice());
+        let entry = if neighbor.is_forward() {
+            self.db.range(txn, &bounds)?.next()
+        } else {
+            self.db.rev_range(txn, &bounds)?.next()
+        };
+
+        // The first entry past the probe may belong to an adjacent scope
+        match entry.transpose()? {
+            Some((found, value)) if found.starts_with(&hash.to_be_bytes()) => {
+                Ok(Some((decode_key(found)?, value)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The hash whose big-endian prefix holds the entries of `scope`
+fn storage_hash(scope: &Scope) -> Result<u32, ScopedDbError> {
+    match scope.storage_scope(true)?.as_ref() {
+        Scope::Named { hash, .. } => Ok(*hash),
+        Scope::Default => Ok(DEFAULT_SCOPE_HASH),
+    }
+}
+
+/// Length of the big-endian scope hash in front of every key
+const HASH_PREFIX_LEN: usize = 4;
+
+fn encode_key<K: SortableKey>(hash: u32, key: &K) -> Vec<u8> {
+    let mut encoded = hash.to_be_bytes().to_vec();
+    key.encode_sortable(&mut encoded);
+    encoded
+}
+
+fn decode_hash(encoded: &[u8]) -> Result<u32, ScopedDbError> {
+    match encoded.first_chunk::<4>() {
+        Some(prefix) => Ok(u32::from_be_bytes(*prefix)),
+        None => Err(ScopedDbError::Encoding(
+            "Not enough bytes to decode scope hash".into(),
+        )),
+    }
+}
+
+fn decode_key<K: SortableKey>(encoded: &[u8]) -> Result<K, ScopedDbError> {
+    decode_hash(encoded)?;
+    K::decode_sortable(&encoded[4..])
+}
+
+impl<K, V> Clone for ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db,
+            global_registry: self.global_registry.clone(),
+            env: self.env.clone(),
+            observer: self.observer.clone(),
+            require_registered_scopes: self.require_registered_scopes,
+            verify_scope_hashes: self.verify_scope_hashes,
+            deny_default_scope: self.deny_default_scope,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> ScopeEmptinessChecker for ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn is_scope_empty_in_db(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        let prefix = self.storage_hash(scope)?.to_be_bytes();
+        let mut iter = self
+            .db
+            .remap_data_type::<DecodeIgnore>()
+            .prefix_iter(txn, &prefix)?;
+        Ok(iter.next().is_none())
+    }
+}
+
+impl<K, V> ScopeHashEnumerator for ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        let db = self.db.remap_data_type::<DecodeIgnore>();
+        let mut hashes = Vec::new();
+        let mut start = Some(0u32);
+        // Seek once per scope: every hash's keys end right before the prefix of hash + 1
+        while let Some(probe) = start {
+            let probe = probe.to_be_bytes();
+            let bounds = (Bound::Included(&probe[..]), Bound::Unbounded);
+            let Some(entry) = db.range(txn, &bounds)?.next() else {
+                break;
+            };
+            let (key, _) = entry?;
+            let hash = decode_hash(key)?;
+            if hash != DEFAULT_SCOPE_HASH {
+                hashes.push(hash);
+            }
+            start = hash.checked_add(1);
+        }
+        Ok(hashes)
+    }
+}
+
+impl<K, V> ScopedClear for ScopedOrderedDatabase<K, V>
+where
+    K: SortableKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.clear(txn, scope)
+    }
+}
@@ -0,0 +1,421 @@
+use heed::{DatabaseStat, Env, RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+use crate::global_registry::{ScopeEmptinessChecker, ScopeHashEnumerator, ScopedClear};
+use crate::utils::{self, OrderedKey};
+use crate::{IterResult, Scope, ScopeHashIterResult, ScopedBytesKeyDatabase, ScopedDbError};
+
+/// Typed facade over `ScopedBytesKeyDatabase` for fixed-width compound keys.
+///
+/// Keys are encoded with `OrderedKey`, which produces big-endian byte strings whose
+/// lexicographic order matches the numeric order of the key. This makes range queries
+/// over a key prefix work as expected within a scope, e.g. all documents of one user
+/// when keying by `(user_id, doc_id)`.
+///
+/// The underlying storage is identical to `ScopedBytesKeyDatabase<V>`, so the same
+/// databases can be opened through either type.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{scoped_database_options, Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let mut wtxn = env.write_txn()?;
+/// let db = scoped_database_options(&env, registry.clone())
+///     .tuple_keys::<(u64, u32), String>()
+///     .name("documents")
+///     .create(&mut wtxn)?;
+///
+/// let tenant = Scope::named("tenant1")?;
+/// db.put(&mut wtxn, &tenant, &(7, 1), &"first".to_string())?;
+/// db.put(&mut wtxn, &tenant, &(7, 300), &"second".to_string())?;
+/// wtxn.commit()?;
+///
+/// // All documents of user 7, in doc_id order
+/// let rtxn = env.read_txn()?;
+/// for result in db.range(&rtxn, &tenant, &((7, 0)..=(7, u32::MAX)))? {
+///     let ((user_id, doc_id), value) = result?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    inner: ScopedBytesKeyDatabase<V>,
+    _phantom: PhantomData<K>,
+}
+
+impl<K, V> ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Wrap an existing bytes key database with typed, order-preserving keys.
+    pub fn new(inner: ScopedBytesKeyDatabase<V>) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Access the underlying bytes key database.
+    pub fn inner(&self) -> &ScopedBytesKeyDatabase<V> {
+        &self.inner
+    }
+
+    /// Registers a scope in the global registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's a hash collision between different scope names.
+    pub fn register_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<(), ScopedDbError> {
+        self.inner.register_scope(txn, scope)
+    }
+
+    /// Lists all known scopes in the database, including the Default scope.
+    pub fn list_scopes(&self, txn: &RoTxn) -> Result<Vec<Scope>, ScopedDbError> {
+        self.inner.list_scopes(txn)
+    }
+
+    /// Lists the registered scopes whose name satisfies a predicate.
+    ///
+    /// See `GlobalScopeRegistry::list_scopes_where`.
+    pub fn list_scopes_where<F>(&self, txn: &RoTxn, pred: F) -> Result<Vec<Scope>, ScopedDbError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        self.inner.list_scopes_where(txn, pred)
+    }
+
+    /// Lists the registered named scopes whose name starts with `prefix`.
+    pub fn list_scopes_with_prefix(
+        &self,
+        txn: &RoTxn,
+        prefix: &str,
+    ) -> Result<Vec<Scope>, ScopedDbError> {
+        self.inner.list_scopes_with_prefix(txn, prefix)
+    }
+
+    /// Returns LMDB B-tree statistics for the default and scoped underlying databases.
+    pub fn heed_stats(&self, txn: &RoTxn) -> Result<(DatabaseStat, DatabaseStat), ScopedDbError> {
+        self.inner.heed_stats(txn)
+    }
+
+    /// Returns the number of entries in every scope of this database.
+    pub fn per_scope_counts(&self, txn: &RoTxn) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        self.inner.per_scope_counts(txn)
+    }
+
+    /// Returns the number of entries in every scope, sorted by entry count.
+    ///
+    /// See `ScopedDatabase::scopes_by_size`.
+    pub fn scopes_by_size(
+        &self,
+        txn: &RoTxn,
+        descending: bool,
+    ) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+        self.inner.scopes_by_size(txn, descending)
+    }
+
+    /// Lists the distinct hashes of named scopes holding data in this database.
+    pub fn distinct_scope_hashes(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.inner.distinct_scope_hashes(txn)
+    }
+
+    /// Lazily iterates over the hashes of named scopes holding data in this database.
+    pub fn scope_hashes_present<'txn>(&self, txn: &'txn RoTxn<'txn>) -> ScopeHashIterResult<'txn> {
+        self.inner.scope_hashes_present(txn)
+    }
+
+    /// Returns whether a scope holds at least one entry in this database.
+    pub fn has_data(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        self.inner.has_data(txn, scope)
+    }
+
+    /// Returns the longest encoded tuple key that can be written to `scope`.
+    ///
+    /// See `ScopedBytesDatabase::max_key_len`.
+    pub fn max_key_len(&self, scope: &Scope) -> usize {
+        self.inner.max_key_len(scope)
+    }
+
+    /// Returns the LMDB environment this database was created in.
+    pub fn env(&self) -> &Env {
+        self.inner.env()
+    }
+
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+        value: &V,
+    ) -> Result<(), ScopedDbError> {
+        self.inner.put(txn, scope, &utils::encode_tuple(key), value)
+    }
+
+    /// Get a value from the database.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.inner.get(txn, scope, &utils::encode_tuple(key))
+    }
+
+    /// Get a value that is expected to be present.
+    ///
+    /// See `ScopedDatabase::get_expect`.
+    pub fn get_expect<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<V, ScopedDbError> {
+        self.get(txn, scope, key)?.ok_or(ScopedDbError::NotFound)
+    }
+
+    /// Get the raw stored bytes of a value without deserializing it.
+    ///
+    /// See `ScopedBytesKeyDatabase::get_raw`.
+    pub fn get_raw<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<&'txn [u8]>, ScopedDbError> {
+        self.inner.get_raw(txn, scope, &utils::encode_tuple(key))
+    }
+
+    /// Get a value together with the size of its stored bytes.
+    ///
+    /// See `ScopedDatabase::get_with_size`.
+    pub fn get_with_size(
+        &self,
+        txn: &RoTxn,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<Option<(V, usize)>, ScopedDbError> {
+        self.inner
+            .get_with_size(txn, scope, &utils::encode_tuple(key))
+    }
+
+    /// Insert a key-value pair and return the value it replaced, if any.
+    ///
+    /// See `ScopedBytesKeyDatabase::put_returning`.
+    pub fn put_returning(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+        value: &V,
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.inner
+            .put_returning(txn, scope, &utils::encode_tuple(key), value)
+    }
+
+    /// Delete a key-value pair from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        self.inner.delete(txn, scope, &utils::encode_tuple(key))
+    }
+
+    /// Move the entry stored under `key` from one scope to another.
+    ///
+    /// See `ScopedBytesKeyDatabase::move_key`.
+    pub fn move_key(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        to: &Scope,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        self.inner
+            .move_key(txn, from, to, &utils::encode_tuple(key))
+    }
+
+    /// Move the entry stored under `from_key` in `from` to `to_key` in `to`.
+    ///
+    /// See `ScopedBytesKeyDatabase::move_key_as`.
+    pub fn move_key_as(
+        &self,
+        txn: &mut RwTxn<'_>,
+        from: &Scope,
+        from_key: &K,
+        to: &Scope,
+        to_key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        self.inner.move_key_as(
+            txn,
+            from,
+            &utils::encode_tuple(from_key),
+            to,
+            &utils::encode_tuple(to_key),
+        )
+    }
+
+    /// Clear all entries within a specific scope or the default database.
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear(&self, txn: &mut RwTxn<'_>, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.inner.clear(txn, scope)
+    }
+
+    /// Clear a scope and unregister it once no database holds its data anymore.
+    ///
+    /// See `ScopedBytesKeyDatabase::clear_and_unregister`.
+    pub fn clear_and_unregister(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &Scope,
+        also_empty_in: &[&dyn ScopeEmptinessChecker],
+    ) -> Result<usize, ScopedDbError> {
+        self.inner.clear_and_unregister(txn, scope, also_empty_in)
+    }
+
+    /// Unregister the scopes that hold no data in this database.
+    ///
+    /// See `ScopedDatabase::prune_own_empty_scopes`.
+    pub fn prune_own_empty_scopes(&self, txn: &mut RwTxn) -> Result<usize, ScopedDbError> {
+        self.inner.prune_own_empty_scopes(txn)
+    }
+
+    /// Remove every entry of this database and consume the handle.
+    ///
+    /// See `ScopedBytesKeyDatabase::destroy`.
+    pub fn destroy(self, txn: &mut RwTxn<'_>) -> Result<(), ScopedDbError> {
+        self.inner.destroy(txn)
+    }
+
+    /// Clear a scope in its own write transaction and commit it.
+    ///
+    /// See `ScopedBytesKeyDatabase::clear_and_commit`.
+    pub fn clear_and_commit(&self, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.inner.clear_and_commit(scope)
+    }
+
+    /// Iterate over entries in a specific scope or the default database, in key order.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>, scope: &Scope) -> IterResult<'txn, K, V> {
+        let iter = self.inner.iter(txn, scope)?.map(|result| {
+            let (key, value) = result?;
+            Ok((K::decode_from(key)?, value))
+        });
+        Ok(Box::new(iter))
+    }
+
+    /// Iterate over a range of entries in a specific scope or the default database.
+    ///
+    /// Because keys are encoded in an order-preserving way, the range is evaluated
+    /// by LMDB directly without any post-filtering.
+    pub fn range<'txn, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        range: &R,
+    ) -> IterResult<'txn, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let encode_bound = |bound: Bound<&K>| match bound {
+            Bound::Included(key) => Bound::Included(utils::encode_tuple(key)),
+            Bound::Excluded(key) => Bound::Excluded(utils::encode_tuple(key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let start = encode_bound(range.start_bound());
+        let end = encode_bound(range.end_bound());
+        let bytes_range = (
+            start.as_ref().map(Vec::as_slice),
+            end.as_ref().map(Vec::as_slice),
+        );
+
+        let iter = self.inner.range(txn, scope, &bytes_range)?.map(|result| {
+            let (key, value) = result?;
+            Ok((K::decode_from(key)?, value))
+        });
+        Ok(Box::new(iter))
+    }
+
+    /// Iterate over a page of a range: skip the first `offset` entries, then yield at
+    /// most `limit` entries.
+    ///
+    /// See `ScopedBytesKeyDatabase::range_limited`.
+    pub fn range_limited<'txn, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &Scope,
+        range: &R,
+        offset: usize,
+        limit: usize,
+    ) -> IterResult<'txn, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let iter = self.range(txn, scope, range)?;
+        Ok(Box::new(iter.skip(offset).take(limit)))
+    }
+}
+
+impl<K, V> Clone for ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> From<ScopedBytesKeyDatabase<V>> for ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn from(inner: ScopedBytesKeyDatabase<V>) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<K, V> ScopeEmptinessChecker for ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn is_scope_empty_in_db(&self, txn: &RoTxn, scope: &Scope) -> Result<bool, ScopedDbError> {
+        self.inner.is_scope_empty_in_db(txn, scope)
+    }
+}
+
+impl<K, V> ScopeHashEnumerator for ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn scope_hashes_in_db(&self, txn: &RoTxn) -> Result<Vec<u32>, ScopedDbError> {
+        self.inner.distinct_scope_hashes(txn)
+    }
+}
+
+impl<K, V> ScopedClear for ScopedTupleKeyDatabase<K, V>
+where
+    K: OrderedKey + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    fn clear_scope(&self, txn: &mut RwTxn, scope: &Scope) -> Result<usize, ScopedDbError> {
+        self.clear(txn, scope)
+    }
+}
@@ -0,0 +1,328 @@
+use heed::{RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeBounds;
+
+use crate::{
+    BytesIterResult, BytesKeyIterResult, IterResult, OrderPreservingKey, Scope,
+    ScopedBytesDatabase, ScopedBytesKeyDatabase, ScopedDatabase, ScopedDbError,
+};
+
+/// A lightweight view of a database bound to a single scope.
+///
+/// Created with `scoped` on any database type. The view borrows the database, holds the
+/// `Scope` by value, and forwards every call to the database with the captured scope, so
+/// per-tenant code no longer needs to thread `&scope` through each call.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let tenant = db.scoped(Scope::named("tenant1")?);
+///
+/// let mut wtxn = env.write_txn()?;
+/// tenant.put(&mut wtxn, &"key".to_string(), &"value".to_string())?;
+/// wtxn.commit()?;
+///
+/// let rtxn = env.read_txn()?;
+/// let value = tenant.get(&rtxn, &"key".to_string())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScopedView<'db, D> {
+    db: &'db D,
+    scope: Scope,
+}
+
+impl<'db, D> ScopedView<'db, D> {
+    pub(crate) fn new(db: &'db D, scope: Scope) -> Self {
+        Self { db, scope }
+    }
+
+    /// The scope this view is bound to
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    /// The underlying database
+    pub fn database(&self) -> &'db D {
+        self.db
+    }
+}
+
+impl<D> Clone for ScopedView<'_, D> {
+    fn clone(&self) -> Self {
+        Self::new(self.db, self.scope.clone())
+    }
+}
+
+impl<K, V> ScopedView<'_, ScopedDatabase<K, V>>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Insert a key-value pair into the bound scope.
+    pub fn put(&self, txn: &mut RwTxn<'_>, key: &K, value: &V) -> Result<(), ScopedDbError> {
+        self.db.put(txn, &self.scope, key, value)
+    }
+
+    /// Get a value from the bound scope.
+    pub fn get<'txn>(&self, txn: &'txn RoTxn<'txn>, key: &K) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(txn, &self.scope, key)
+    }
+
+    /// Delete a key-value pair from the bound scope.
+    pub fn delete(&self, txn: &mut RwTxn<'_>, key: &K) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, &self.scope, key)
+    }
+
+    /// Clear the bound scope, returning the number of entries removed.
+    pub fn clear(&self, txn: &mut RwTxn<'_>) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, &self.scope)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>) -> IterResult<'txn, K, V> {
+        self.db.iter(txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        range: &'bounds R,
+    ) -> IterResult<'txn, K, V>
+    where
+        K: PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K> + 'bounds,
+        'bounds: 'txn,
+    {
+        self.db.range(txn, &self.scope, range)
+    }
+}
+
+impl<V> ScopedView<'_, ScopedBytesKeyDatabase<V>>
+where
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Insert a key-value pair into the bound scope.
+    pub fn put(&self, txn: &mut RwTxn<'_>, key: &[u8], value: &V) -> Result<(), ScopedDbError> {
+        self.db.put(txn, &self.scope, key, value)
+    }
+
+    /// Get a value from the bound scope.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        key: &[u8],
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(txn, &self.scope, key)
+    }
+
+    /// Delete a key-value pair from the bound scope.
+    pub fn delete(&self, txn: &mut RwTxn<'_>, key: &[u8]) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, &self.scope, key)
+    }
+
+    /// Clear the bound scope, returning the number of entries removed.
+    pub fn clear(&self, txn: &mut RwTxn<'_>) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, &self.scope)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>) -> BytesKeyIterResult<'txn, V> {
+        self.db.iter(txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        range: &'bounds R,
+    ) -> BytesKeyIterResult<'txn, V>
+    where
+        R: RangeBounds<&'bounds [u8]> + 'bounds,
+    {
+        self.db.range(txn, &self.scope, range)
+    }
+}
+
+impl ScopedView<'_, ScopedBytesDatabase> {
+    /// Insert a key-value pair into the bound scope.
+    pub fn put(&self, txn: &mut RwTxn<'_>, key: &[u8], value: &[u8]) -> Result<(), ScopedDbError> {
+        self.db.put(txn, &self.scope, key, value)
+    }
+
+    /// Get a value from the bound scope.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        key: &[u8],
+    ) -> Result<Option<&'txn [u8]>, ScopedDbError> {
+        self.db.get(txn, &self.scope, key)
+    }
+
+    /// Delete a key-value pair from the bound scope.
+    pub fn delete(&self, txn: &mut RwTxn<'_>, key: &[u8]) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, &self.scope, key)
+    }
+
+    /// Clear the bound scope, returning the number of entries removed.
+    pub fn clear(&self, txn: &mut RwTxn<'_>) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, &self.scope)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn<'txn>) -> BytesIterResult<'txn> {
+        self.db.iter(txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'txn, 'bounds, R>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        range: &'bounds R,
+    ) -> BytesIterResult<'txn>
+    where
+        R: RangeBounds<&'bounds [u8]> + 'bounds,
+    {
+        self.db.range(txn, &self.scope, range)
+    }
+}
+
+/// A read-only view of a database bound to a single scope and read transaction.
+///
+/// Created with `read_view` on any database type. The view borrows both the database
+/// and the transaction, so its `get`, `iter` and `range` take neither a transaction nor
+/// a scope and a handler doing many reads for one tenant reads like code over a plain
+/// map. Values borrowed from the transaction live as long as the transaction, not the
+/// view. A view over a write transaction sees its uncommitted writes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let rtxn = env.read_txn()?;
+/// let tenant = db.read_view(&rtxn, Scope::named("tenant1")?);
+///
+/// let profile = tenant.get(&"profile".to_string())?;
+/// let settings = tenant.get(&"settings".to_string())?;
+/// for entry in tenant.iter()? {
+///     let (key, value) = entry?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ScopedReadView<'db, 'txn, D> {
+    db: &'db D,
+    txn: &'txn RoTxn<'txn>,
+    scope: Scope,
+}
+
+impl<'db, 'txn, D> ScopedReadView<'db, 'txn, D> {
+    pub(crate) fn new(db: &'db D, txn: &'txn RoTxn<'txn>, scope: Scope) -> Self {
+        Self { db, txn, scope }
+    }
+
+    /// The scope this view is bound to
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    /// The underlying database
+    pub fn database(&self) -> &'db D {
+        self.db
+    }
+
+    /// The transaction this view reads from
+    pub fn txn(&self) -> &'txn RoTxn<'txn> {
+        self.txn
+    }
+}
+
+impl<D> Clone for ScopedReadView<'_, '_, D> {
+    fn clone(&self) -> Self {
+        Self::new(self.db, self.txn, self.scope.clone())
+    }
+}
+
+impl<D: fmt::Debug> fmt::Debug for ScopedReadView<'_, '_, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedReadView")
+            .field("db", self.db)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'txn, K, V> ScopedReadView<'_, 'txn, ScopedDatabase<K, V>>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Get a value from the bound scope.
+    pub fn get(&self, key: &K) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(self.txn, &self.scope, key)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter(&self) -> IterResult<'txn, K, V> {
+        self.db.iter(self.txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'bounds, R>(&self, range: &'bounds R) -> IterResult<'txn, K, V>
+    where
+        K: PartialOrd + OrderPreservingKey,
+        R: RangeBounds<K> + 'bounds,
+        'bounds: 'txn,
+    {
+        self.db.range(self.txn, &self.scope, range)
+    }
+}
+
+impl<'txn, V> ScopedReadView<'_, 'txn, ScopedBytesKeyDatabase<V>>
+where
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Get a value from the bound scope.
+    pub fn get(&self, key: &[u8]) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(self.txn, &self.scope, key)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter(&self) -> BytesKeyIterResult<'txn, V> {
+        self.db.iter(self.txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'bounds, R>(&self, range: &'bounds R) -> BytesKeyIterResult<'txn, V>
+    where
+        R: RangeBounds<&'bounds [u8]> + 'bounds,
+    {
+        self.db.range(self.txn, &self.scope, range)
+    }
+}
+
+impl<'txn> ScopedReadView<'_, 'txn, ScopedBytesDatabase> {
+    /// Get a value from the bound scope.
+    pub fn get(&self, key: &[u8]) -> Result<Option<&'txn [u8]>, ScopedDbError> {
+        self.db.get(self.txn, &self.scope, key)
+    }
+
+    /// Iterate over the entries of the bound scope.
+    pub fn iter(&self) -> BytesIterResult<'txn> {
+        self.db.iter(self.txn, &self.scope)
+    }
+
+    /// Iterate over a range of entries in the bound scope.
+    pub fn range<'bounds, R>(&self, range: &'bounds R) -> BytesIterResult<'txn>
+    where
+        R: RangeBounds<&'bounds [u8]> + 'bounds,
+    {
+        self.db.range(self.txn, &self.scope, range)
+    }
+}
@@ -0,0 +1,117 @@
+//! Snapshots of a scope's contents and diffs between them.
+//!
+//! A snapshot taken with `ScopedBytesDatabase::snapshot_scope` is a plain ordered map of
+//! raw keys to raw values. Diffing two snapshots of the same scope yields the changes
+//! needed to bring a replica from the old state to the new one, which is a simple form
+//! of change-data-capture.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// The raw contents of one scope, keyed and ordered by the raw key bytes
+pub type ScopeSnapshot = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Changes between two snapshots of a scope.
+///
+/// Returned by `diff_snapshots`. Every key appears in at most one of the maps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeDiff {
+    /// Keys only present in the new snapshot, with their new values
+    pub added: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// Keys present in both snapshots with different values, as `(old, new)`
+    pub changed: BTreeMap<Vec<u8>, (Vec<u8>, Vec<u8>)>,
+    /// Keys only present in the old snapshot, with their old values
+    pub removed: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl ScopeDiff {
+    /// Whether the snapshots were identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Computes the changes that turn `old` into `new`.
+///
+/// Both snapshots are walked once in key order, so this runs in linear time.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{diff_snapshots, Scope, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// let before = db.snapshot_scope(&rtxn, &tenant)?;
+/// // ... writes happen ...
+/// let after = db.snapshot_scope(&env.read_txn()?, &tenant)?;
+/// let diff = diff_snapshots(&before, &after);
+/// for (key, value) in &diff.added {
+///     replica.put(key, value)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn diff_snapshots(old: &ScopeSnapshot, new: &ScopeSnapshot) -> ScopeDiff {
+    let mut diff = ScopeDiff::default();
+    let mut old_entries = old.iter().peekable();
+    let mut new_entries = new.iter().peekable();
+
+    loop {
+        let order = match (old_entries.peek(), new_entries.peek()) {
+            (Some((old_key, _)), Some((new_key, _))) => old_key.cmp(new_key),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+        match order {
+            Ordering::Less => {
+                let (key, value) = old_entries.next().unwrap();
+                diff.removed.insert(key.clone(), value.clone());
+            }
+            Ordering::Greater => {
+                let (key, value) = new_entries.next().unwrap();
+                diff.added.insert(key.clone(), value.clone());
+            }
+            Ordering::Equal => {
+                let (key, old_value) = old_entries.next().unwrap();
+                let (_, new_value) = new_entries.next().unwrap();
+                if old_value != new_value {
+                    diff.changed
+                        .insert(key.clone(), (old_value.clone(), new_value.clone()));
+                }
+            }
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entries: &[(&[u8], &[u8])]) -> ScopeSnapshot {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let old = snapshot(&[(b"a", b"1"), (b"b", b"2"), (b"c", b"3"), (b"e", b"5")]);
+        let new = snapshot(&[(b"b", b"2"), (b"c", b"30"), (b"d", b"4"), (b"e", b"5")]);
+
+        let diff = diff_snapshots(&old, &new);
+        assert_eq!(diff.added, snapshot(&[(b"d", b"4")]));
+        assert_eq!(
+            diff.changed,
+            BTreeMap::from([(b"c".to_vec(), (b"3".to_vec(), b"30".to_vec()))])
+        );
+        assert_eq!(diff.removed, snapshot(&[(b"a", b"1")]));
+        assert!(!diff.is_empty());
+
+        assert!(diff_snapshots(&new, &new).is_empty());
+        assert_eq!(diff_snapshots(&ScopeSnapshot::new(), &new).added, new);
+        assert_eq!(diff_snapshots(&old, &ScopeSnapshot::new()).removed, old);
+    }
+}
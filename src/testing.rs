@@ -0,0 +1,83 @@
+//! Helpers for tests that need a scratch LMDB environment.
+//!
+//! Available with the `test-util` feature, for this crate's tests and for downstream
+//! crates testing their own code against scoped-heed. Each environment lives in a fresh
+//! temporary directory that is removed when the returned `TempDir` is dropped.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use scoped_heed::testing::temp_scoped_db;
+//!
+//! let test_db = temp_scoped_db::<String, String>("users")?;
+//! let mut wtxn = test_db.env.write_txn()?;
+//! test_db.db.put(&mut wtxn, &Scope::named("tenant1")?, &"alice".into(), &"admin".into())?;
+//! wtxn.commit()?;
+//! ```
+
+use heed::{Env, EnvOpenOptions};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+use crate::{GlobalScopeRegistry, ScopedDatabase, ScopedDbError, scoped_database_options};
+
+/// Map size of the environments created by this module (10 MiB)
+pub const TEMP_MAP_SIZE: usize = 10 * 1024 * 1024;
+
+/// A scoped database in its own temporary environment, see `temp_scoped_db`.
+///
+/// Fields are dropped in declaration order, so the database and the environment are
+/// closed before the directory holding them is removed.
+pub struct TempScopedDb<K, V>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    pub db: ScopedDatabase<K, V>,
+    pub registry: Arc<GlobalScopeRegistry>,
+    pub env: Env,
+    pub dir: TempDir,
+}
+
+/// Open an environment in a new temporary directory.
+///
+/// The environment has a 10 MiB map and room for `max_dbs` named databases; remember
+/// that the registry and each scoped database use several of them. Keep the `TempDir`
+/// alive for as long as the environment is used.
+pub fn temp_env(max_dbs: u32) -> Result<(TempDir, Env), ScopedDbError> {
+    let dir = TempDir::new().map_err(heed::Error::Io)?;
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(TEMP_MAP_SIZE)
+            .max_dbs(max_dbs)
+            .open(dir.path())?
+    };
+    Ok((dir, env))
+}
+
+/// Create a `ScopedDatabase` named `name` with a fresh registry in a temporary
+/// environment.
+///
+/// The registry and the database are created and committed before this returns, so
+/// the database is ready for use with new transactions on `env`.
+pub fn temp_scoped_db<K, V>(name: &str) -> Result<TempScopedDb<K, V>, ScopedDbError>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    let (dir, env) = temp_env(20)?;
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<K, V>()
+        .name(name)
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+    Ok(TempScopedDb {
+        db,
+        registry,
+        env,
+        dir,
+    })
+}
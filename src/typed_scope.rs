@@ -0,0 +1,322 @@
+use heed::{RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::{
+    BytesIterResult, BytesKeyIterResult, IterResult, Scope, ScopedBytesDatabase,
+    ScopedBytesKeyDatabase, ScopedDatabase, ScopedDbError,
+};
+
+/// A `Scope` tagged with a marker type at compile time.
+///
+/// Larger codebases often have several scoped databases whose scopes live in different
+/// name spaces, e.g. user tenants and billing accounts. Wrapping a database in
+/// `TypedScoped<M, _>` makes it accept only `TypedScope<M>`, so a scope built for one
+/// name space cannot accidentally be used with a database of another.
+///
+/// `TypedScope<M>` dereferences to `Scope`, so it can be passed to any API taking
+/// `&Scope`. The marker has no runtime cost.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// # use scoped_heed::{TypedScope, TypedScoped, ScopedDbError};
+/// # fn main() -> Result<(), ScopedDbError> {
+/// struct UserTenant;
+/// struct BillingTenant;
+///
+/// let users: TypedScoped<UserTenant, _> = TypedScoped::new(users_db);
+/// let billing: TypedScoped<BillingTenant, _> = TypedScoped::new(billing_db);
+///
+/// let tenant = TypedScope::<UserTenant>::named("acme")?;
+/// users.put(&mut wtxn, &tenant, &"alice".to_string(), &"admin".to_string())?;
+/// // billing.put(&mut wtxn, &tenant, ...) does not compile
+/// # Ok(())
+/// # }
+/// ```
+pub struct TypedScope<M> {
+    scope: Scope,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M> TypedScope<M> {
+    /// Create a typed named scope
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::EmptyScopeDisallowed` if the name is empty.
+    pub fn named(name: &str) -> Result<Self, ScopedDbError> {
+        Scope::named(name).map(Self::new)
+    }
+
+    /// Create the typed default scope
+    pub fn default_scope() -> Self {
+        Self::new(Scope::Default)
+    }
+
+    /// Tag an existing scope with the marker type
+    pub fn new(scope: Scope) -> Self {
+        Self {
+            scope,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Access the underlying untyped scope
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    /// Unwrap into the underlying untyped scope
+    pub fn into_scope(self) -> Scope {
+        self.scope
+    }
+}
+
+impl<M> Deref for TypedScope<M> {
+    type Target = Scope;
+
+    fn deref(&self) -> &Scope {
+        &self.scope
+    }
+}
+
+impl<M> AsRef<Scope> for TypedScope<M> {
+    fn as_ref(&self) -> &Scope {
+        &self.scope
+    }
+}
+
+impl<M> Clone for TypedScope<M> {
+    fn clone(&self) -> Self {
+        Self::new(self.scope.clone())
+    }
+}
+
+impl<M> PartialEq for TypedScope<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scope == other.scope
+    }
+}
+
+impl<M> Eq for TypedScope<M> {}
+
+impl<M> fmt::Debug for TypedScope<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedScope").field(&self.scope).finish()
+    }
+}
+
+/// A database that only accepts scopes tagged with the marker type `M`.
+///
+/// See `TypedScope` for details. The wrapped database remains reachable through
+/// `inner` for operations not covered here.
+///
+/// Passing a scope of another name space is a compile error:
+///
+/// ```compile_fail
+/// # use scoped_heed::{ScopedBytesDatabase, ScopedDbError, TypedScope, TypedScoped};
+/// struct UserTenant;
+/// struct BillingTenant;
+///
+/// fn write(
+///     billing: &TypedScoped<BillingTenant, ScopedBytesDatabase>,
+///     txn: &mut heed::RwTxn,
+///     tenant: &TypedScope<UserTenant>,
+/// ) -> Result<(), ScopedDbError> {
+///     billing.put(txn, tenant, b"key", b"value")
+/// }
+/// ```
+pub struct TypedScoped<M, D> {
+    db: D,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M, D> TypedScoped<M, D> {
+    /// Wrap a database so that it requires `TypedScope<M>`
+    pub fn new(db: D) -> Self {
+        Self {
+            db,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Access the underlying database
+    pub fn inner(&self) -> &D {
+        &self.db
+    }
+}
+
+impl<M, D: Clone> Clone for TypedScoped<M, D> {
+    fn clone(&self) -> Self {
+        Self::new(self.db.clone())
+    }
+}
+
+impl<M, D: fmt::Debug> fmt::Debug for TypedScoped<M, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedScoped").field(&self.db).finish()
+    }
+}
+
+impl<M, K, V> TypedScoped<M, ScopedDatabase<K, V>>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Clone + Default + 'static,
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &K,
+        value: &V,
+    ) -> Result<(), ScopedDbError> {
+        self.db.put(txn, scope, key, value)
+    }
+
+    /// Get a value from the database.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+        key: &K,
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(txn, scope, key)
+    }
+
+    /// Delete a key-value pair from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &K,
+    ) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, scope, key)
+    }
+
+    /// Clear all entries within a scope, returning the number of entries removed.
+    pub fn clear(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+    ) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, scope)
+    }
+
+    /// Iterate over entries in a scope.
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+    ) -> IterResult<'txn, K, V> {
+        self.db.iter(txn, scope)
+    }
+}
+
+impl<M, V> TypedScoped<M, ScopedBytesKeyDatabase<V>>
+where
+    V: Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+        value: &V,
+    ) -> Result<(), ScopedDbError> {
+        self.db.put(txn, scope, key, value)
+    }
+
+    /// Get a value from the database.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+    ) -> Result<Option<V>, ScopedDbError> {
+        self.db.get(txn, scope, key)
+    }
+
+    /// Delete a key-value pair from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, scope, key)
+    }
+
+    /// Clear all entries within a scope, returning the number of entries removed.
+    pub fn clear(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+    ) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, scope)
+    }
+
+    /// Iterate over entries in a scope.
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+    ) -> BytesKeyIterResult<'txn, V> {
+        self.db.iter(txn, scope)
+    }
+}
+
+impl<M> TypedScoped<M, ScopedBytesDatabase> {
+    /// Insert a key-value pair into the database.
+    pub fn put(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<(), ScopedDbError> {
+        self.db.put(txn, scope, key, value)
+    }
+
+    /// Get a value from the database.
+    pub fn get<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+    ) -> Result<Option<&'txn [u8]>, ScopedDbError> {
+        self.db.get(txn, scope, key)
+    }
+
+    /// Delete a key-value pair from the database.
+    pub fn delete(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+        key: &[u8],
+    ) -> Result<bool, ScopedDbError> {
+        self.db.delete(txn, scope, key)
+    }
+
+    /// Clear all entries within a scope, returning the number of entries removed.
+    pub fn clear(
+        &self,
+        txn: &mut RwTxn<'_>,
+        scope: &TypedScope<M>,
+    ) -> Result<usize, ScopedDbError> {
+        self.db.clear(txn, scope)
+    }
+
+    /// Iterate over entries in a scope.
+    pub fn iter<'txn>(
+        &self,
+        txn: &'txn RoTxn<'txn>,
+        scope: &TypedScope<M>,
+    ) -> BytesIterResult<'txn> {
+        self.db.iter(txn, scope)
+    }
+}
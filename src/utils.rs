@@ -1,5 +1,10 @@
-use crate::ScopedDbError;
-use heed::{BytesDecode, BytesEncode};
+use crate::scope::compute_xxhash;
+use crate::{
+    BatchOp, BytesKeyIterResult, DEFAULT_SCOPE_HASH, GlobalScopeRegistry, Scope, ScopedDbError,
+};
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{BytesDecode, BytesEncode, Database as HeedDatabase, DatabaseFlags, Env, RoTxn, RwTxn};
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::ops::{Bound, RangeBounds};
 
@@ -49,15 +54,28 @@ impl<'a, R: RangeBounds<&'a [u8]>> RangeBounds<[u8]> for HeedRangeAdapter<'a, R>
 ///
 /// The 8-byte key length field matches bincode's encoding for byte slices
 /// and provides sufficient capacity for even very large keys.
+///
+/// Empty keys are valid. They encode to the bare `[scope_hash][0u64]` header, which is
+/// the smallest key of the scope, so the `(hash, &[])` start bound used by iteration,
+/// ranges and `clear` includes them, while the exclusive `(next_hash, &[])` end bound
+/// excludes the empty key of the following scope.
+///
+/// The default scope of the separate layout is the exception: it stores keys without
+/// this header, and LMDB rejects zero-length keys with `MDB_BAD_VALSIZE`. Use a named
+/// scope or the unified layout when empty keys are needed.
 pub enum ScopedBytesCodec {}
 
 impl ScopedBytesCodec {
+    /// Size of the `[scope_hash][key_len]` header in front of every encoded key.
+    pub const HEADER_LEN: usize = 12;
+
+    /// Encode a scope hash and key into the scoped key format.
     #[inline]
     pub fn encode(scope_hash: u32, key: &[u8]) -> Vec<u8> {
-        let mut output = Vec::with_capacity(12 + key.len());
+        let mut output = Vec::with_capacity(Self::HEADER_LEN + key.len());
 
         // Scope hash as u32 little-endian (4 bytes)
-        output.extend_from_slice(&scope_hash.to_le_bytes());
+        output.extend_from_slice(&Self::hash_prefix(scope_hash));
 
         // Key length as u64 little-endian (8 bytes) - matches bincode format
         let key_len = key.len() as u64;
@@ -67,6 +85,41 @@ impl ScopedBytesCodec {
         output
     }
 
+    /// The 4-byte prefix every key of the scope named `name` starts with.
+    ///
+    /// This is the xxHash32 (seed 0) of the UTF-8 name in little-endian byte order,
+    /// i.e. `hash_prefix` applied to the hash of `Scope::named(name)`. External tools
+    /// can use it to build or recognize the on-disk keys of a scope without going through
+    /// the database API. A full key continues with the key length as 8 little-endian
+    /// bytes and the key itself, see `encode`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use scoped_heed::ScopedBytesCodec;
+    ///
+    /// let prefix = ScopedBytesCodec::scope_prefix("tenant1");
+    /// let key = ScopedBytesCodec::encode(u32::from_le_bytes(prefix), b"key");
+    /// assert!(key.starts_with(&prefix));
+    /// ```
+    pub fn scope_prefix(name: &str) -> [u8; 4] {
+        Self::hash_prefix(compute_xxhash(name.as_bytes()))
+    }
+
+    /// The 4-byte prefix every key stored under `scope_hash` starts with.
+    ///
+    /// The hash is written little-endian. This byte order is part of the on-disk format
+    /// and stays fixed; see the crate docs on byte order for how scope bounds follow it.
+    #[inline]
+    pub fn hash_prefix(scope_hash: u32) -> [u8; 4] {
+        scope_hash.to_le_bytes()
+    }
+
+    /// Decode a scoped key into its scope hash and key.
+    ///
+    /// The returned key borrows from `bytes` without copying. When decoding entries
+    /// read from LMDB it points directly into the memory-mapped page and stays valid
+    /// for the lifetime of the transaction.
     #[inline]
     pub fn decode(bytes: &[u8]) -> Result<(u32, &[u8]), ScopedDbError> {
         if bytes.len() < 12 {
@@ -90,6 +143,26 @@ impl ScopedBytesCodec {
 
         Ok((scope_hash, key))
     }
+
+    /// Read only the scope hash of a scoped key.
+    ///
+    /// Only the 4-byte prefix is inspected; the length field and key body are neither
+    /// validated nor touched. This is meant for scans that filter entries by scope
+    /// before decoding them. Every scoped key layout in this crate starts with the same
+    /// little-endian hash, so this also works on the keys of `ScopedDatabase`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScopedDbError::Encoding` if `bytes` is shorter than 4 bytes.
+    #[inline]
+    pub fn decode_hash_only(bytes: &[u8]) -> Result<u32, ScopedDbError> {
+        match bytes.first_chunk::<4>() {
+            Some(prefix) => Ok(u32::from_le_bytes(*prefix)),
+            None => Err(ScopedDbError::Encoding(
+                "Not enough bytes to decode scope hash".into(),
+            )),
+        }
+    }
 }
 
 impl<'a> BytesEncode<'a> for ScopedBytesCodec {
@@ -126,3 +199,1027 @@ where
 {
     K::default()
 }
+
+/// Run `f` in a child transaction nested inside `parent`.
+///
+/// The child is committed into `parent` when `f` returns `Ok` and aborted when it
+/// returns `Err`, so a failing step can be rolled back without giving up the rest of the
+/// parent transaction. Changes made in the child only become durable when `parent` is
+/// committed. Any scoped database opened in `env` can be used with the child transaction.
+///
+/// LMDB does not support nested transactions in environments opened with `WRITEMAP`;
+/// opening the child then fails and `f` is not called.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut wtxn = env.write_txn()?;
+/// db.put(&mut wtxn, &Scope::Default, &"kept".to_string(), &1)?;
+/// let result = with_child_txn(&env, &mut wtxn, |child| {
+///     db.put(child, &Scope::Default, &"dropped".to_string(), &2)?;
+///     Err(ScopedDbError::InvalidInput("validation failed".into()))
+/// });
+/// assert!(result.is_err());
+/// wtxn.commit()?; // only "kept" is written
+/// ```
+pub fn with_child_txn<T, F>(env: &Env, parent: &mut RwTxn, f: F) -> Result<T, ScopedDbError>
+where
+    F: FnOnce(&mut RwTxn) -> Result<T, ScopedDbError>,
+{
+    let mut child = env.nested_write_txn(parent)?;
+    match f(&mut child) {
+        Ok(value) => {
+            child.commit()?;
+            Ok(value)
+        }
+        Err(err) => {
+            child.abort();
+            Err(err)
+        }
+    }
+}
+
+/// Move every entry stored under `from_hash` so that it is stored under `to_hash`.
+///
+/// All scoped key encodings in this crate start with the scope hash as a little-endian
+/// `u32`, so entries can be re-prefixed on the raw bytes without decoding keys or values.
+/// Entries are moved one at a time, so only a single key/value pair is buffered in memory.
+///
+/// Returns the number of moved entries.
+pub(crate) fn move_scope_entries(
+    db: HeedDatabase<Bytes, Bytes>,
+    txn: &mut RwTxn,
+    from_hash: u32,
+    to_hash: u32,
+) -> Result<usize, ScopedDbError> {
+    let from_prefix = ScopedBytesCodec::hash_prefix(from_hash);
+    let mut moved = 0;
+
+    loop {
+        // Copy out the first remaining entry so the cursor borrow ends before writing
+        let entry = match db.prefix_iter(txn, &from_prefix)?.next() {
+            Some(result) => {
+                let (key, value) = result?;
+                Some((key.to_vec(), value.to_vec()))
+            }
+            None => None,
+        };
+
+        let Some((mut key, value)) = entry else {
+            break;
+        };
+
+        db.delete(txn, &key)?;
+        key[..4].copy_from_slice(&ScopedBytesCodec::hash_prefix(to_hash));
+        db.put(txn, &key, &value)?;
+        moved += 1;
+    }
+
+    Ok(moved)
+}
+
+/// Swap all entries stored under two scope hashes within a single transaction.
+///
+/// The entries of `hash_a` are first parked under a temporary hash that has no entries
+/// in this database, then the entries of `hash_b` are moved to `hash_a`, and finally the
+/// parked entries are moved to `hash_b`. Keys never overwrite each other because every
+/// move targets a prefix that is empty at that point.
+pub(crate) fn swap_scope_entries(
+    db: HeedDatabase<Bytes, Bytes>,
+    txn: &mut RwTxn,
+    hash_a: u32,
+    hash_b: u32,
+) -> Result<(), ScopedDbError> {
+    if hash_a == hash_b {
+        return Ok(());
+    }
+
+    // Find a temporary hash prefix that is unused in this database
+    let mut temp_hash = hash_a.wrapping_add(1);
+    while temp_hash == hash_a
+        || temp_hash == hash_b
+        || db
+            .prefix_iter(txn, &ScopedBytesCodec::hash_prefix(temp_hash))?
+            .next()
+            .is_some()
+    {
+        temp_hash = temp_hash.wrapping_add(1);
+    }
+
+    move_scope_entries(db, txn, hash_a, temp_hash)?;
+    move_scope_entries(db, txn, hash_b, hash_a)?;
+    move_scope_entries(db, txn, temp_hash, hash_b)?;
+
+    Ok(())
+}
+
+/// Count the entries of every scope stored in a scoped database in a single pass.
+///
+/// Only the 4-byte little-endian scope hash at the start of each key is read and values
+/// are never decoded, so every entry is touched exactly once. Hashes are resolved to names
+/// through the registry at the end; a hash without a registered name is reported with
+/// its hexadecimal value (e.g. `0x0000002a`) as the name. The default scope comes first
+/// with `default_count`, followed by the named scopes that hold entries, by hash. A
+/// `default_count` of `None` means default-scope entries are stored in `db` under
+/// `DEFAULT_SCOPE_HASH` and are counted in the same pass.
+pub(crate) fn count_entries_per_scope(
+    db: HeedDatabase<Bytes, DecodeIgnore>,
+    txn: &RoTxn,
+    registry: &GlobalScopeRegistry,
+    default_count: Option<u64>,
+) -> Result<Vec<(Scope, u64)>, ScopedDbError> {
+    let mut counts: BTreeMap<u32, u64> = BTreeMap::new();
+    for result in db.iter(txn)? {
+        let (key, ()) = result?;
+        *counts
+            .entry(ScopedBytesCodec::decode_hash_only(key)?)
+            .or_default() += 1;
+    }
+
+    let default_count = match default_count {
+        Some(count) => count,
+        None => counts.remove(&DEFAULT_SCOPE_HASH).unwrap_or(0),
+    };
+
+    let mut result = Vec::with_capacity(counts.len() + 1);
+    result.push((Scope::Default, default_count));
+    for (hash, count) in counts {
+        let name = registry
+            .get_scope_name(txn, &hash)?
+            .unwrap_or_else(|| placeholder_scope_name(hash));
+        result.push((Scope::Named { name, hash }, count));
+    }
+    Ok(result)
+}
+
+/// Sort per-scope counts by count, breaking ties by scope so the order is stable.
+pub(crate) fn sort_by_count(counts: &mut [(Scope, u64)], descending: bool) {
+    counts.sort_by(|(a_scope, a_count), (b_scope, b_count)| {
+        let by_count = if descending {
+            b_count.cmp(a_count)
+        } else {
+            a_count.cmp(b_count)
+        };
+        by_count.then_with(|| a_scope.cmp(b_scope))
+    });
+}
+
+/// Sort batch operations into the order their keys are stored in.
+///
+/// Named scopes follow the `ScopedBytesCodec` layout: the little-endian hash prefix,
+/// then the little-endian key length and the key bytes, so keys are compared exactly
+/// as LMDB compares the encoded keys. Default-scope operations come first. Their keys
+/// are raw and sorted bytewise, except in `unified` databases, where they are encoded
+/// like named-scope keys under `DEFAULT_SCOPE_HASH`. The sort is stable, so operations
+/// on the same key keep their relative order.
+pub(crate) fn sort_batch_ops<V>(ops: &mut [(Scope, BatchOp<Vec<u8>, V>)], unified: bool) {
+    let sort_key = |scope: &Scope, key: &[u8]| {
+        let hash = match scope {
+            Scope::Default if unified => Some(DEFAULT_SCOPE_HASH),
+            scope => scope.hash(),
+        };
+        let header = hash.map(|hash| (hash.to_le_bytes(), (key.len() as u64).to_le_bytes()));
+        (header.is_some(), header, key.to_vec())
+    };
+    ops.sort_by_cached_key(|(scope, op)| sort_key(scope, op.key()));
+}
+
+/// List the distinct scope hashes present in a scoped database, in ascending order.
+///
+/// Rather than walking every entry, this seeks to the first key of each scope and then
+/// past the scope's 4-byte hash prefix, so the cost grows with the number of scopes
+/// instead of the number of entries.
+pub(crate) fn distinct_scope_hashes(
+    db: HeedDatabase<Bytes, DecodeIgnore>,
+    txn: &RoTxn,
+) -> Result<Vec<u32>, ScopedDbError> {
+    let mut hashes = Vec::new();
+    let mut last = None;
+    while let Some(hash) = seek_next_scope(db, txn, last)? {
+        hashes.push(hash);
+        last = Some(hash);
+    }
+
+    hashes.sort_unstable();
+    Ok(hashes)
+}
+
+/// Find the scope stored right after `after` in a scoped database.
+///
+/// Positions a cursor at the first key following every key prefixed with `after`'s
+/// hash (or at the first key when `after` is `None`) and returns that key's scope hash.
+/// Scopes are visited in key order, which follows the little-endian hash bytes rather
+/// than numeric order. Returns `None` once no further scope exists.
+pub(crate) fn seek_next_scope(
+    db: HeedDatabase<Bytes, DecodeIgnore>,
+    txn: &RoTxn,
+    after: Option<u32>,
+) -> Result<Option<u32>, ScopedDbError> {
+    let start = match after {
+        Some(hash) => match next_scope_bound(hash) {
+            Some(next_hash) => ScopedBytesCodec::hash_prefix(next_hash),
+            None => return Ok(None),
+        },
+        None => [0u8; 4],
+    };
+
+    let bounds = (Bound::Included(&start[..]), Bound::Unbounded);
+    match db.range(txn, &bounds)?.next() {
+        Some(result) => {
+            let (key, ()) = result?;
+            Ok(Some(ScopedBytesCodec::decode_hash_only(key)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Lazily yield the scope hashes present in a scoped database, in key order.
+///
+/// Each step is a single `seek_next_scope` call; iteration stops after the first error.
+pub(crate) fn scope_hashes_present<'txn>(
+    db: HeedDatabase<Bytes, DecodeIgnore>,
+    txn: &'txn RoTxn<'txn>,
+) -> impl Iterator<Item = Result<u32, ScopedDbError>> + 'txn {
+    let mut last = None;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        match seek_next_scope(db, txn, last) {
+            Ok(Some(hash)) => {
+                last = Some(hash);
+                Some(Ok(hash))
+            }
+            Ok(None) => {
+                done = true;
+                None
+            }
+            Err(e) => {
+                done = true;
+                Some(Err(e))
+            }
+        }
+    })
+}
+
+/// The scope hash whose key prefix immediately follows the prefix of `hash`.
+///
+/// Every scoped key starts with the 4-byte little-endian scope hash, so keys are
+/// ordered by those bytes rather than numerically and `hash + 1` is generally not the
+/// next prefix (keys of `0x100` sort before those of `0xFF`). Encoding the returned hash
+/// with an empty key therefore gives an exclusive upper bound covering exactly the
+/// entries of `hash`.
+///
+/// Returns `None` for `u32::MAX`, whose `[0xFF; 4]` prefix sorts last; ranges over that
+/// scope extend to the end of the database instead.
+pub(crate) fn next_scope_bound(hash: u32) -> Option<u32> {
+    let mut prefix = hash.to_le_bytes();
+    for byte in prefix.iter_mut().rev() {
+        if *byte == u8::MAX {
+            *byte = 0;
+        } else {
+            *byte += 1;
+            return Some(u32::from_le_bytes(prefix));
+        }
+    }
+    None
+}
+
+/// Reject a key that would not fit in LMDB once `overhead` bytes of scope prefix are added.
+///
+/// LMDB fails such writes with an opaque `MDB_BAD_VALSIZE`; checking up front lets the
+/// error name the key length and the limit instead.
+pub(crate) fn check_key_size(
+    env: &Env,
+    key_len: usize,
+    overhead: usize,
+) -> Result<(), ScopedDbError> {
+    let max_len = env.max_key_size().saturating_sub(overhead);
+    if key_len > max_len {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "Key of {} bytes exceeds the maximum of {} bytes ({} byte LMDB limit minus {} bytes of scope prefix)",
+            key_len,
+            max_len,
+            env.max_key_size(),
+            overhead
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a value too large for a `DUP_SORT` database.
+///
+/// LMDB stores duplicate values like keys, so they share the key size limit and an
+/// oversized value would fail with `MDB_BAD_VALSIZE`.
+pub(crate) fn check_dup_value_size(env: &Env, value_len: usize) -> Result<(), ScopedDbError> {
+    if value_len > env.max_key_size() {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "Value of {} bytes exceeds the maximum of {} bytes for values of a multi-value database",
+            value_len,
+            env.max_key_size()
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a prefix operation on a default-scope database opened with heed flags.
+///
+/// `INTEGER_KEY` and `REVERSE_KEY` change how LMDB compares keys, so keys sharing a
+/// prefix are no longer contiguous and a prefix scan would stop early and return
+/// partial results.
+pub(crate) fn check_prefix_scan_flags(flags: DatabaseFlags) -> Result<(), ScopedDbError> {
+    if !flags.is_empty() {
+        return Err(ScopedDbError::InvalidInput(format!(
+            "Prefix operations need lexicographic key order, which the default-scope database opened with {:?} does not have",
+            flags
+        )));
+    }
+    Ok(())
+}
+
+/// Name reported for a scope hash that has no registered name
+pub(crate) fn placeholder_scope_name(hash: u32) -> String {
+    format!("{hash:#010x}")
+}
+
+/// Delete every raw key starting with `prefix` from an unscoped bytes database.
+///
+/// An empty prefix deletes every entry. Returns the number of deleted entries.
+pub(crate) fn delete_key_prefix(
+    db: HeedDatabase<Bytes, Bytes>,
+    txn: &mut RwTxn,
+    prefix: &[u8],
+) -> Result<usize, ScopedDbError> {
+    let mut iter = db.prefix_iter_mut(txn, prefix)?;
+    let mut count = 0;
+    while let Some(result) = iter.next() {
+        result?;
+        // Safety: No references to cursor data are kept after deletion
+        unsafe { iter.del_current()? };
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Delete every key starting with `prefix` within one scope of a `ScopedBytesCodec` database.
+///
+/// The codec stores the key length between the scope hash and the key bytes, so keys
+/// sharing a prefix are not contiguous in LMDB order and a single `delete_range` cannot
+/// select them. Instead this walks the entries of the scope, which are contiguous under
+/// the 4-byte hash prefix, and deletes matching keys in place. The walk never leaves the
+/// scope's hash prefix, including for `u32::MAX` and all-`0xFF` key prefixes.
+///
+/// Returns the number of deleted entries.
+pub(crate) fn delete_scoped_key_prefix(
+    db: HeedDatabase<Bytes, Bytes>,
+    txn: &mut RwTxn,
+    scope_hash: u32,
+    prefix: &[u8],
+) -> Result<usize, ScopedDbError> {
+    let mut iter = db.prefix_iter_mut(txn, &ScopedBytesCodec::hash_prefix(scope_hash))?;
+    let mut count = 0;
+    while let Some(result) = iter.next() {
+        let (encoded_key, _) = result?;
+        let (_, key) = ScopedBytesCodec::decode(encoded_key)?;
+        if key.starts_with(prefix) {
+            // Safety: No references to cursor data are kept after deletion
+            unsafe { iter.del_current()? };
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// The smallest byte string greater than every string starting with `prefix`.
+///
+/// Returns `None` when no such string exists, i.e. for an empty or all-`0xFF` prefix.
+fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(last) = successor.pop() {
+        if last < u8::MAX {
+            successor.push(last + 1);
+            return Some(successor);
+        }
+    }
+    None
+}
+
+/// Bounds selecting the keys `head + prefix + suffix` whose suffix lies in `suffix_range`.
+///
+/// An unbounded end stops at the successor of `head + prefix`, so only keys that
+/// start with the prefix are selected.
+fn prefixed_bounds<'a, R>(
+    head: &[u8],
+    prefix: &[u8],
+    suffix_range: &R,
+) -> (Bound<Vec<u8>>, Bound<Vec<u8>>)
+where
+    R: RangeBounds<&'a [u8]>,
+{
+    let join = |suffix: &[u8]| [head, prefix, suffix].concat();
+    let start = match suffix_range.start_bound() {
+        Bound::Included(suffix) => Bound::Included(join(suffix)),
+        Bound::Excluded(suffix) => Bound::Excluded(join(suffix)),
+        Bound::Unbounded => Bound::Included(join(&[])),
+    };
+    let end = match suffix_range.end_bound() {
+        Bound::Included(suffix) => Bound::Included(join(suffix)),
+        Bound::Excluded(suffix) => Bound::Excluded(join(suffix)),
+        Bound::Unbounded => match prefix_successor(&join(&[])) {
+            Some(successor) => Bound::Excluded(successor),
+            None => Bound::Unbounded,
+        },
+    };
+    (start, end)
+}
+
+/// Borrow a pair of owned bounds as bounds over `[u8]`
+fn borrow_bounds(bounds: &(Bound<Vec<u8>>, Bound<Vec<u8>>)) -> (Bound<&[u8]>, Bound<&[u8]>) {
+    (
+        bounds.0.as_ref().map(Vec::as_slice),
+        bounds.1.as_ref().map(Vec::as_slice),
+    )
+}
+
+/// Iterate over the keys starting with `prefix` whose remainder lies in `suffix_range`.
+///
+/// With `scope_hash` set, `db` holds `ScopedBytesCodec` keys and only that scope is
+/// read; otherwise its keys are raw and a single range covers the matches. Scoped keys
+/// store their length ahead of the key bytes, so the matches of a scope are contiguous
+/// only among keys of the same length. The scope is therefore visited one length at a
+/// time: a seek finds the next length present, and a single range within that length
+/// yields the matches, so keys outside the prefix and suffix bounds are never read.
+/// Entries come out in storage order, as from `range`.
+///
+/// Returned keys have the scope header stripped.
+pub(crate) fn prefix_range_entries<'txn, 'a, DC, R>(
+    db: HeedDatabase<Bytes, DC>,
+    txn: &'txn RoTxn<'txn>,
+    scope_hash: Option<u32>,
+    prefix: &[u8],
+    suffix_range: &R,
+) -> BytesKeyIterResult<'txn, DC::DItem>
+where
+    DC: BytesDecode<'txn> + 'static,
+    R: RangeBounds<&'a [u8]>,
+{
+    let Some(scope_hash) = scope_hash else {
+        let bounds = prefixed_bounds(&[], prefix, suffix_range);
+        let iter = db
+            .range(txn, &borrow_bounds(&bounds))?
+            .map(|result| result.map_err(ScopedDbError::from));
+        return Ok(Box::new(iter));
+    };
+
+    let prefix = prefix.to_vec();
+    let suffix_range = (
+        suffix_range.start_bound().map(|suffix| suffix.to_vec()),
+        suffix_range.end_bound().map(|suffix| suffix.to_vec()),
+    );
+    let scope_end = match next_scope_bound(scope_hash) {
+        Some(next_hash) => Bound::Excluded(ScopedBytesCodec::hash_prefix(next_hash).to_vec()),
+        None => Bound::Unbounded,
+    };
+    // Start of the next length to visit, `None` once the scope is exhausted
+    let mut next_length = Some(ScopedBytesCodec::hash_prefix(scope_hash).to_vec());
+    let mut current: Option<heed::RoRange<'txn, Bytes, DC>> = None;
+
+    let iter = std::iter::from_fn(move || {
+        loop {
+            if let Some(range) = &mut current {
+                match range.next() {
+                    Some(Ok((key, value))) => {
+                        let key: &'txn [u8] = key;
+                        return Some(Ok((&key[ScopedBytesCodec::HEADER_LEN..], value)));
+                    }
+                    Some(Err(e)) => return Some(Err(ScopedDbError::from(e))),
+                    None => current = None,
+                }
+            }
+
+            // Find the header of the next key length present in the scope
+            let start = next_length.take()?;
+            let bounds = (Bound::Included(start), scope_end.clone());
+            let first = db
+                .remap_data_type::<DecodeIgnore>()
+                .range(txn, &borrow_bounds(&bounds))
+                .and_then(|mut range| range.next().transpose());
+            let header = match first {
+                Ok(Some((key, ()))) => match key.get(..ScopedBytesCodec::HEADER_LEN) {
+                    Some(header) => header,
+                    None => {
+                        return Some(Err(ScopedDbError::Encoding(
+                            "Not enough bytes to decode scoped key".into(),
+                        )));
+                    }
+                },
+                Ok(None) => return None,
+                Err(e) => return Some(Err(ScopedDbError::from(e))),
+            };
+            next_length = prefix_successor(header);
+
+            let suffix_bounds = borrow_bounds(&suffix_range);
+            let bounds = prefixed_bounds(header, &prefix, &suffix_bounds);
+            match db.range(txn, &borrow_bounds(&bounds)) {
+                Ok(range) => current = Some(range),
+                Err(e) => return Some(Err(ScopedDbError::from(e))),
+            }
+        }
+    });
+    Ok(Box::new(iter))
+}
+
+/// Direction of a neighbor lookup relative to a probe key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Neighbor {
+    /// Smallest key greater than or equal to the probe
+    Ceiling,
+    /// Smallest key strictly greater than the probe
+    Higher,
+    /// Largest key less than or equal to the probe
+    Floor,
+    /// Largest key strictly less than the probe
+    Lower,
+}
+
+impl Neighbor {
+    /// Whether the lookup scans forward from the probe key
+    pub(crate) fn is_forward(self) -> bool {
+        matches!(self, Neighbor::Ceiling | Neighbor::Higher)
+    }
+
+    /// Half-open bounds starting at the probe key in the direction of the lookup.
+    ///
+    /// The open side is unbounded so that callers only need to verify that the first
+    /// entry found still belongs to the requested scope.
+    pub(crate) fn bounds<T: ?Sized>(self, key: &T) -> (Bound<&T>, Bound<&T>) {
+        match self {
+            Neighbor::Ceiling => (Bound::Included(key), Bound::Unbounded),
+            Neighbor::Higher => (Bound::Excluded(key), Bound::Unbounded),
+            Neighbor::Floor => (Bound::Unbounded, Bound::Included(key)),
+            Neighbor::Lower => (Bound::Unbounded, Bound::Excluded(key)),
+        }
+    }
+}
+
+/// Marker for key types whose bincode encoding sorts in the same order as the values.
+///
+/// `ScopedDatabase::range` hands its bounds to LMDB, which compares the bincode-encoded
+/// keys byte by byte. bincode writes integers little-endian and prefixes strings and
+/// vectors with their length, so for most types the byte order differs from the logical
+/// order: `256u64` sorts before `1u64`, `-1i64` sorts after `1i64`, and `"b"` sorts
+/// before `"aa"`. A range over such keys silently returns or deletes the wrong entries,
+/// so `range`, `delete_range` and `update_range` only accept key types implementing
+/// this trait.
+///
+/// It is implemented for `u8`, `bool`, `char`, `()`, byte arrays `[u8; N]`, and tuples
+/// of these. For integer or compound keys use `ScopedTupleKeyDatabase`, which encodes
+/// keys big-endian, and for string prefixes use `ScopedDatabase::iter_prefix`.
+///
+/// Implementing this trait for a type whose encoding does not preserve order makes
+/// `range` return incorrect results and `delete_range` and `update_range` miss entries.
+///
+/// ```compile_fail
+/// # use scoped_heed::{ScopedDatabase, Scope, ScopedDbError};
+/// # fn demo(db: &ScopedDatabase<i64, String>, rtxn: &heed::RoTxn) -> Result<(), ScopedDbError> {
+/// // -1i64 is encoded as [0xFF; 8] and would sort after 1i64
+/// db.range(rtxn, &Scope::Default, &(-1i64..=1))?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use scoped_heed::{ScopedDatabase, Scope, ScopedDbError};
+/// # fn demo(db: &ScopedDatabase<i64, String>, wtxn: &mut heed::RwTxn) -> Result<(), ScopedDbError> {
+/// // Would leave -1 in place while deleting everything from 0 up
+/// db.delete_range(wtxn, &Scope::Default, &(-1i64..=1))?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait OrderPreservingKey {}
+
+impl OrderPreservingKey for u8 {}
+impl OrderPreservingKey for bool {}
+// UTF-8 byte order matches code point order and the encoding is self-delimiting
+impl OrderPreservingKey for char {}
+impl OrderPreservingKey for () {}
+impl<const N: usize> OrderPreservingKey for [u8; N] {}
+
+macro_rules! impl_order_preserving_key_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: OrderPreservingKey),+> OrderPreservingKey for ($($name,)+) {}
+    };
+}
+
+impl_order_preserving_key_tuple!(A);
+impl_order_preserving_key_tuple!(A, B);
+impl_order_preserving_key_tuple!(A, B, C);
+impl_order_preserving_key_tuple!(A, B, C, D);
+
+/// Fixed-width key types with an order-preserving byte encoding.
+///
+/// Implementations encode values so that comparing the encoded bytes lexicographically
+/// gives the same result as comparing the values themselves. This is what LMDB uses
+/// to order keys, so range queries over encoded keys match numeric ordering:
+/// - Unsigned integers are encoded big-endian
+/// - Signed integers are encoded big-endian with the sign bit flipped
+/// - Byte arrays are encoded as-is
+/// - Tuples concatenate the encodings of their components
+///
+/// Little-endian encodings do not have this property: `256u64` encodes to
+/// `[0, 1, 0, ...]` which sorts before `1u64` encoded as `[1, 0, 0, ...]`.
+pub trait OrderedKey: Sized {
+    /// Number of bytes in the encoded form
+    const WIDTH: usize;
+
+    /// Append the encoded form of this value to `out`
+    fn encode_into(&self, out: &mut Vec<u8>);
+
+    /// Decode a value from exactly `WIDTH` bytes
+    fn decode_from(bytes: &[u8]) -> Result<Self, ScopedDbError>;
+}
+
+/// Encode a `u64` as order-preserving big-endian bytes.
+#[inline]
+pub fn encode_u64_be(value: u64) -> [u8; 8] {
+    value.to_be_bytes()
+}
+
+/// Encode a `u32` as order-preserving big-endian bytes.
+#[inline]
+pub fn encode_u32_be(value: u32) -> [u8; 4] {
+    value.to_be_bytes()
+}
+
+/// Encode an `OrderedKey` (typically a tuple of integers) into order-preserving bytes.
+///
+/// # Example
+///
+/// ```
+/// # use scoped_heed::utils::encode_tuple;
+/// let key = encode_tuple(&(42u64, 7u32));
+/// assert_eq!(key.len(), 12);
+/// assert!(encode_tuple(&(1u64, 0u32)) < encode_tuple(&(256u64, 0u32)));
+/// ```
+pub fn encode_tuple<K: OrderedKey>(key: &K) -> Vec<u8> {
+    let mut out = Vec::with_capacity(K::WIDTH);
+    key.encode_into(&mut out);
+    out
+}
+
+/// Decode bytes produced by `encode_tuple` back into an `OrderedKey`.
+pub fn decode_tuple<K: OrderedKey>(bytes: &[u8]) -> Result<K, ScopedDbError> {
+    K::decode_from(bytes)
+}
+
+#[inline]
+fn check_key_width(bytes: &[u8], width: usize) -> Result<(), ScopedDbError> {
+    if bytes.len() != width {
+        return Err(ScopedDbError::Encoding(format!(
+            "Expected {} bytes for ordered key, got {}",
+            width,
+            bytes.len()
+        )));
+    }
+    Ok(())
+}
+
+macro_rules! impl_ordered_key_unsigned {
+    ($($ty:ty),*) => {$(
+        impl OrderedKey for $ty {
+            const WIDTH: usize = std::mem::size_of::<$ty>();
+
+            #[inline]
+            fn encode_into(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_be_bytes());
+            }
+
+            #[inline]
+            fn decode_from(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+                check_key_width(bytes, Self::WIDTH)?;
+                Ok(<$ty>::from_be_bytes(bytes.try_into().unwrap()))
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_ordered_key_signed {
+    ($($ty:ty => $uty:ty),*) => {$(
+        impl OrderedKey for $ty {
+            const WIDTH: usize = std::mem::size_of::<$ty>();
+
+            #[inline]
+            fn encode_into(&self, out: &mut Vec<u8>) {
+                // Flipping the sign bit makes negative values sort before positive ones
+                let flipped = (*self as $uty) ^ (1 << (<$uty>::BITS - 1));
+                out.extend_from_slice(&flipped.to_be_bytes());
+            }
+
+            #[inline]
+            fn decode_from(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+                check_key_width(bytes, Self::WIDTH)?;
+                let flipped = <$uty>::from_be_bytes(bytes.try_into().unwrap());
+                Ok((flipped ^ (1 << (<$uty>::BITS - 1))) as $ty)
+            }
+        }
+    )*};
+}
+
+impl_ordered_key_unsigned!(u8, u16, u32, u64, u128);
+impl_ordered_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl<const N: usize> OrderedKey for [u8; N] {
+    const WIDTH: usize = N;
+
+    #[inline]
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    #[inline]
+    fn decode_from(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+        check_key_width(bytes, N)?;
+        Ok(bytes.try_into().unwrap())
+    }
+}
+
+macro_rules! impl_ordered_key_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: OrderedKey),+> OrderedKey for ($($name,)+) {
+            const WIDTH: usize = 0 $(+ $name::WIDTH)+;
+
+            #[allow(non_snake_case)]
+            fn encode_into(&self, out: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                $($name.encode_into(out);)+
+            }
+
+            #[allow(unused_assignments)]
+            fn decode_from(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+                check_key_width(bytes, Self::WIDTH)?;
+                let mut offset = 0;
+                Ok(($({
+                    let value = $name::decode_from(&bytes[offset..offset + $name::WIDTH])?;
+                    offset += $name::WIDTH;
+                    value
+                },)+))
+            }
+        }
+    };
+}
+
+impl_ordered_key_tuple!(A);
+impl_ordered_key_tuple!(A, B);
+impl_ordered_key_tuple!(A, B, C);
+impl_ordered_key_tuple!(A, B, C, D);
+
+/// Key types with an order-preserving encoding of any width.
+///
+/// Used by `ScopedOrderedDatabase`, where the encoded key is the last component of the
+/// stored key and therefore needs no length prefix or terminator. This is what allows
+/// variable-length keys: `String` is encoded as its UTF-8 bytes, whose byte order is the
+/// code point order, and `Vec<u8>` as-is. Every `OrderedKey`, i.e. integers, byte arrays
+/// and tuples of these, is encoded like `encode_tuple` does.
+///
+/// As with `OrderedKey`, comparing the encoded bytes lexicographically must give the
+/// same result as comparing the values, otherwise range queries return wrong results.
+pub trait SortableKey: Sized {
+    /// Append the encoded form of this value to `out`
+    fn encode_sortable(&self, out: &mut Vec<u8>);
+
+    /// Decode a value from all of `bytes`
+    fn decode_sortable(bytes: &[u8]) -> Result<Self, ScopedDbError>;
+}
+
+impl<T: OrderedKey> SortableKey for T {
+    #[inline]
+    fn encode_sortable(&self, out: &mut Vec<u8>) {
+        self.encode_into(out);
+    }
+
+    #[inline]
+    fn decode_sortable(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+        T::decode_from(bytes)
+    }
+}
+
+impl SortableKey for String {
+    #[inline]
+    fn encode_sortable(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode_sortable(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| ScopedDbError::Encoding(e.to_string()))
+    }
+}
+
+impl SortableKey for Vec<u8> {
+    #[inline]
+    fn encode_sortable(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    #[inline]
+    fn decode_sortable(bytes: &[u8]) -> Result<Self, ScopedDbError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sortable_key_preserves_ordering() {
+        fn encode<K: SortableKey>(key: &K) -> Vec<u8> {
+            let mut out = Vec::new();
+            key.encode_sortable(&mut out);
+            out
+        }
+
+        let ints = [i64::MIN, -256, -1, 0, 1, 255, 256, i64::MAX];
+        for pair in ints.windows(2) {
+            assert!(encode(&pair[0]) < encode(&pair[1]));
+        }
+        let strings = ["", "a", "aa", "ab", "b", "é"].map(String::from);
+        for pair in strings.windows(2) {
+            assert!(encode(&pair[0]) < encode(&pair[1]));
+        }
+
+        assert_eq!(i64::decode_sortable(&encode(&-42i64)).unwrap(), -42);
+        assert_eq!(
+            String::decode_sortable(&encode(&"tenant".to_string())).unwrap(),
+            "tenant"
+        );
+        assert!(String::decode_sortable(&[0xFF]).is_err());
+    }
+
+    #[test]
+    fn test_ordered_key_roundtrip() {
+        let key = (42u64, 7u32, -3i16);
+        let bytes = encode_tuple(&key);
+        assert_eq!(bytes.len(), 14);
+        assert_eq!(decode_tuple::<(u64, u32, i16)>(&bytes).unwrap(), key);
+
+        // Wrong widths are rejected rather than silently truncated
+        assert!(decode_tuple::<(u64, u32)>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_ordered_key_preserves_ordering() {
+        let mut values = vec![
+            (0u64, 0u32),
+            (1, 300),
+            (1, 2),
+            (256, 0),
+            (u64::MAX, 1),
+            (255, u32::MAX),
+        ];
+        let mut encoded: Vec<Vec<u8>> = values.iter().map(encode_tuple).collect();
+        values.sort();
+        encoded.sort();
+        let decoded: Vec<(u64, u32)> = encoded.iter().map(|b| decode_tuple(b).unwrap()).collect();
+        assert_eq!(decoded, values);
+
+        let mut signed = vec![i32::MIN, -1, 0, 1, i32::MAX];
+        let mut encoded: Vec<Vec<u8>> = signed.iter().map(encode_tuple).collect();
+        signed.sort();
+        encoded.sort();
+        let decoded: Vec<i32> = encoded.iter().map(|b| decode_tuple(b).unwrap()).collect();
+        assert_eq!(decoded, signed);
+    }
+
+    #[test]
+    fn test_scoped_bytes_codec_borrows_key() {
+        let encoded = ScopedBytesCodec::encode(0xDEAD_BEEF, b"payload");
+        let (hash, key) = ScopedBytesCodec::decode(&encoded).unwrap();
+        assert_eq!(hash, 0xDEAD_BEEF);
+        assert_eq!(key, b"payload");
+        // The key is a view into the encoded bytes, not a copy
+        assert_eq!(key.as_ptr(), encoded[12..].as_ptr());
+    }
+
+    #[test]
+    fn test_scope_prefix_byte_order_is_stable() {
+        let encoded = ScopedBytesCodec::encode(0x0102_0304, b"ab");
+        assert_eq!(encoded, [4, 3, 2, 1, 2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+        assert_eq!(ScopedBytesCodec::hash_prefix(0x0102_0304), [4, 3, 2, 1]);
+
+        // The generic layout's bincode keys share the prefix
+        let scoped_key = crate::ScopedKey {
+            scope_hash: 0x0102_0304,
+            key: "ab".to_string(),
+        };
+        let encoded = bincode::serialize(&scoped_key).unwrap();
+        assert!(encoded.starts_with(&[4, 3, 2, 1]));
+        assert_eq!(
+            ScopedBytesCodec::decode_hash_only(&encoded).unwrap(),
+            0x0102_0304
+        );
+    }
+
+    #[test]
+    fn test_prefix_successor() {
+        assert_eq!(prefix_successor(b"ab"), Some(b"ac".to_vec()));
+        assert_eq!(prefix_successor(b"a\xff"), Some(b"b".to_vec()));
+        assert_eq!(prefix_successor(b"\xff\xff"), None);
+        assert_eq!(prefix_successor(b""), None);
+    }
+
+    #[test]
+    fn test_next_scope_bound() {
+        assert_eq!(next_scope_bound(0), Some(0x0100_0000));
+        assert_eq!(next_scope_bound(u32::MAX - 1), Some(0xFF));
+        assert_eq!(next_scope_bound(u32::MAX), None);
+        assert_eq!(next_scope_bound(0xFF), Some(0x0100_00FF));
+
+        for hash in [0, 1, 0xFF, 0x100, 0x00FF_FFFF, u32::MAX - 1] {
+            let next = next_scope_bound(hash).unwrap();
+            let bound = ScopedBytesCodec::encode(next, &[]);
+            // Every key of the scope sorts before the bound...
+            assert!(ScopedBytesCodec::encode(hash, &[0xFF; 16]) < bound);
+            // ...and no other scope lies between the scope and the bound
+            for other in [0u32, 1, 0xFF, 0x100, u32::MAX - 1, u32::MAX] {
+                let key = ScopedBytesCodec::encode(other, b"key");
+                let in_range = ScopedBytesCodec::encode(hash, &[]) <= key && key < bound;
+                assert_eq!(in_range, other == hash, "{other:#x} vs scope {hash:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_scoped_bytes_codec_decode_hash_only() {
+        let encoded = ScopedBytesCodec::encode(u32::MAX - 1, b"key");
+        assert_eq!(
+            ScopedBytesCodec::decode_hash_only(&encoded).unwrap(),
+            u32::MAX - 1
+        );
+
+        // Only the hash prefix is needed, even if the rest is truncated
+        assert_eq!(
+            ScopedBytesCodec::decode_hash_only(&encoded[..4]).unwrap(),
+            u32::MAX - 1
+        );
+        assert!(ScopedBytesCodec::decode(&encoded[..4]).is_err());
+        assert!(ScopedBytesCodec::decode_hash_only(&encoded[..3]).is_err());
+    }
+
+    #[test]
+    fn test_sort_batch_ops_groups_scopes_stably() {
+        let tenant = Scope::named("tenant").unwrap();
+        let mut ops: Vec<(Scope, BatchOp<Vec<u8>, u8>)> = vec![
+            (tenant.clone(), BatchOp::Put(b"bb".to_vec(), 1)),
+            (Scope::Default, BatchOp::Put(b"z".to_vec(), 2)),
+            (tenant.clone(), BatchOp::Put(b"c".to_vec(), 3)),
+            (tenant.clone(), BatchOp::Delete(b"bb".to_vec())),
+        ];
+        sort_batch_ops(&mut ops, false);
+        assert_eq!(
+            ops,
+            vec![
+                (Scope::Default, BatchOp::Put(b"z".to_vec(), 2)),
+                (tenant.clone(), BatchOp::Put(b"c".to_vec(), 3)),
+                (tenant.clone(), BatchOp::Put(b"bb".to_vec(), 1)),
+                (tenant, BatchOp::Delete(b"bb".to_vec())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_batch_ops_follows_encoded_key_order() {
+        let tenant = Scope::named("tenant").unwrap();
+        // Little-endian lengths: 256 is encoded [0, 1, ..] and sorts before 2 [2, 0, ..]
+        let long = vec![b'a'; 256];
+        let ops = vec![
+            (tenant.clone(), BatchOp::Put(b"zz".to_vec(), 1)),
+            (tenant.clone(), BatchOp::Put(long.clone(), 2)),
+            (Scope::Default, BatchOp::Put(b"b".to_vec(), 3)),
+            (Scope::Default, BatchOp::Put(b"ab".to_vec(), 4)),
+        ];
+
+        let mut sorted = ops.clone();
+        sort_batch_ops(&mut sorted, false);
+        let order: Vec<u8> = sorted.iter().map(|(_, op)| batch_value(op)).collect();
+        // Default-scope keys are raw and sorted bytewise
+        assert_eq!(order, vec![4, 3, 2, 1]);
+
+        let mut sorted = ops;
+        sort_batch_ops(&mut sorted, true);
+        let order: Vec<u8> = sorted.iter().map(|(_, op)| batch_value(op)).collect();
+        // Unified default-scope keys carry the codec header, so the shorter key is first
+        assert_eq!(order, vec![3, 4, 2, 1]);
+
+        // The encoded order matches the codec
+        let encode = |key: &[u8]| ScopedBytesCodec::encode(tenant.hash().unwrap(), key);
+        assert!(encode(&long) < encode(b"zz"));
+    }
+
+    fn batch_value(op: &BatchOp<Vec<u8>, u8>) -> u8 {
+        match op {
+            BatchOp::Put(_, value) => *value,
+            BatchOp::Delete(_) => unreachable!(),
+        }
+    }
+}
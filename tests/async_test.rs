@@ -0,0 +1,95 @@
+#![cfg(feature = "tokio")]
+
+use heed::EnvOpenOptions;
+use scoped_heed::{
+    AsyncScopedDatabase, GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options,
+};
+use std::sync::Arc;
+
+// Helper function to create a test environment
+fn setup_test_env() -> (tempfile::TempDir, heed::Env) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(temp_dir.path())
+            .unwrap()
+    };
+
+    (temp_dir, env)
+}
+
+#[tokio::test]
+async fn test_async_operations() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("users")
+        .create(&mut wtxn)?;
+    let blobs = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let users = AsyncScopedDatabase::new(&env, users);
+    let blobs = AsyncScopedDatabase::new(&env, blobs);
+    let tenant = Scope::named("tenant")?;
+
+    users
+        .put(tenant.clone(), "alice".to_string(), "admin".to_string())
+        .await?;
+    users
+        .put(Scope::Default, "alice".to_string(), "guest".to_string())
+        .await?;
+    assert_eq!(
+        users.get(tenant.clone(), "alice".to_string()).await?,
+        Some("admin".to_string())
+    );
+    assert_eq!(
+        users.get(Scope::Default, "alice".to_string()).await?,
+        Some("guest".to_string())
+    );
+
+    blobs
+        .put(tenant.clone(), b"key".to_vec(), b"value".to_vec())
+        .await?;
+    assert_eq!(
+        blobs.get(tenant.clone(), b"key".to_vec()).await?,
+        Some(b"value".to_vec())
+    );
+    assert!(blobs.delete(tenant.clone(), b"key".to_vec()).await?);
+    assert_eq!(blobs.get(tenant.clone(), b"key".to_vec()).await?, None);
+
+    // Several operations in one transaction
+    users
+        .write(|db, txn| {
+            let tenant = Scope::named("tenant")?;
+            db.put(txn, &tenant, &"bob".to_string(), &"editor".to_string())?;
+            db.put(txn, &tenant, &"carol".to_string(), &"viewer".to_string())
+        })
+        .await?;
+    let count = users
+        .read(|db, txn| Ok(db.iter(txn, &Scope::named("tenant")?)?.count()))
+        .await?;
+    assert_eq!(count, 3usize);
+
+    // A failing write is rolled back
+    let result = users
+        .write(|db, txn| {
+            db.put(txn, &Scope::Default, &"dave".to_string(), &"x".to_string())?;
+            Err::<(), _>(ScopedDbError::InvalidInput("abort".into()))
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(users.get(Scope::Default, "dave".to_string()).await?, None);
+
+    assert_eq!(users.clear(tenant.clone()).await?, 3);
+    assert_eq!(users.get(tenant, "alice".to_string()).await?, None);
+
+    Ok(())
+}
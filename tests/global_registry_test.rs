@@ -1,5 +1,9 @@
 use heed::EnvOpenOptions;
-use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
+use scoped_heed::{
+    GlobalScopeRegistry, RegistryAudit, RegistryOptions, Scope, ScopeEmptinessChecker,
+    ScopeHashEnumerator, ScopeNameRules, ScopedClear, ScopedDbError, clear_scope_everywhere,
+    scoped_database_options, standalone_database_options,
+};
 use std::sync::Arc;
 
 // Helper function to create a test environment
@@ -175,15 +179,17 @@ fn test_multiple_databases_sharing_registry() -> Result<(), ScopedDbError> {
     let mut wtxn = env.write_txn()?;
 
     // Clear scope in one database
-    db1.clear(&mut wtxn, &tenant)?;
+    assert_eq!(db1.clear(&mut wtxn, &tenant)?, 1);
 
     // The scope is still not empty overall because it's used in db2 and db3
     let empty_count_db1 = db1.find_empty_scopes(&mut wtxn)?;
     assert_eq!(empty_count_db1, 1, "DB1 should find one empty scope");
 
     // After clearing all databases, the scope should be empty
-    db2.clear(&mut wtxn, &tenant)?;
-    db3.clear(&mut wtxn, &tenant)?;
+    assert_eq!(db2.clear(&mut wtxn, &tenant)?, 1);
+    assert_eq!(db3.clear(&mut wtxn, &tenant)?, 1);
+    // Clearing an already empty scope removes nothing
+    assert_eq!(db3.clear(&mut wtxn, &tenant)?, 0);
 
     let empty_count_db1_after = db1.find_empty_scopes(&mut wtxn)?;
     assert_eq!(
@@ -210,6 +216,13 @@ fn test_multiple_databases_sharing_registry() -> Result<(), ScopedDbError> {
 
     // Prune globally empty scopes using the global registry
     let databases: [&dyn scoped_heed::ScopeEmptinessChecker; 3] = [&db1, &db2, &db3];
+
+    // A dry run reports the candidate without touching the registry
+    let candidates = registry.find_globally_unused_scopes(&wtxn, &databases)?;
+    assert_eq!(candidates, vec![tenant.clone()]);
+    assert!(registry.scope_exists(&wtxn, &tenant)?);
+    assert!(registry.find_globally_unused_scopes(&wtxn, &[])?.is_empty());
+
     let pruned_count = registry.prune_globally_unused_scopes(&mut wtxn, &databases)?;
     assert_eq!(pruned_count, 1, "Should have pruned 1 empty scope");
 
@@ -232,3 +245,1012 @@ fn test_multiple_databases_sharing_registry() -> Result<(), ScopedDbError> {
     // TempDir will be automatically cleaned up when dropped
     Ok(())
 }
+
+#[test]
+fn test_builder_pre_registers_scopes() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let scopes = [Scope::named("tenant1")?, Scope::named("tenant2")?];
+    let _db = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("prereg")
+        .register_scopes(&scopes)
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Scopes are listed before any data has been written
+    let rtxn = env.read_txn()?;
+    let listed = registry.list_all_scopes(&rtxn)?;
+    assert_eq!(listed.len(), 3);
+    for scope in &scopes {
+        assert!(registry.scope_exists(&rtxn, scope)?);
+    }
+    drop(rtxn);
+
+    // A scope whose hash is already registered under a different name is rejected
+    let tenant1_hash = scopes[0].hash().unwrap();
+    let impostor = Scope::Named {
+        name: "impostor".to_string(),
+        hash: tenant1_hash,
+    };
+    let mut wtxn = env.write_txn()?;
+    let result = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("prereg_bytes")
+        .register_scopes(&[impostor])
+        .create(&mut wtxn);
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_exists_by_name() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    registry.register_scope(&mut wtxn, &Scope::named("tenant1")?)?;
+
+    // Simulate a collision: "impostor" registered under the hash of "tenant2"
+    let tenant2_hash = Scope::named("tenant2")?.hash().unwrap();
+    registry.register_scope(
+        &mut wtxn,
+        &Scope::Named {
+            name: "impostor".to_string(),
+            hash: tenant2_hash,
+        },
+    )?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(registry.scope_exists_by_name(&rtxn, "tenant1")?);
+    assert!(!registry.scope_exists_by_name(&rtxn, "unknown")?);
+    assert!(!registry.scope_exists_by_name(&rtxn, "tenant2")?);
+    assert!(matches!(
+        registry.scope_exists_by_name(&rtxn, ""),
+        Err(ScopedDbError::EmptyScopeDisallowed)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_create_with_new_registry() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let (registry, db) = standalone_database_options(&env)
+        .types::<String, String>()
+        .name("standalone")
+        .register_scopes(&[Scope::named("tenant1")?])
+        .create_with_new_registry()?;
+
+    // The returned registry can be shared with further databases
+    let mut wtxn = env.write_txn()?;
+    let bytes_db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("standalone_bytes")
+        .create(&mut wtxn)?;
+    let tenant2 = Scope::named("tenant2")?;
+    db.put(
+        &mut wtxn,
+        &tenant2,
+        &"key".to_string(),
+        &"value".to_string(),
+    )?;
+    bytes_db.put(&mut wtxn, &tenant2, b"key", b"value")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(registry.scope_exists_by_name(&rtxn, "tenant1")?);
+    assert!(registry.scope_exists_by_name(&rtxn, "tenant2")?);
+    assert_eq!(
+        db.get(&rtxn, &tenant2, &"key".to_string())?,
+        Some("value".to_string())
+    );
+    drop(rtxn);
+
+    // Without a registry, create is rejected
+    let mut wtxn = env.write_txn()?;
+    let result = standalone_database_options(&env)
+        .raw_bytes()
+        .name("no_registry")
+        .create(&mut wtxn);
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_scope_name_rules() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let rules = ScopeNameRules::new()
+        .max_len(64)
+        .allowed_chars(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_:-".contains(c));
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?.with_name_rules(rules));
+    let db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("validated")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &Scope::named("tenant:1")?, b"key", b"value")?;
+    db.put(&mut wtxn, &Scope::Default, b"key", b"value")?;
+
+    // Writes implicitly register the scope, so invalid names are rejected
+    let result = db.put(&mut wtxn, &Scope::named("Tenant 1")?, b"key", b"value");
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    let long_name = "a".repeat(65);
+    let result = registry.register_scope(&mut wtxn, &Scope::named(&long_name)?);
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(registry.scope_exists_by_name(&rtxn, "tenant:1")?);
+    assert!(!registry.scope_exists_by_name(&rtxn, "Tenant 1")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_clear_scope_everywhere() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("users")
+        .create(&mut wtxn)?;
+    let blobs = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let leaving = Scope::named("leaving")?;
+    let staying = Scope::named("staying")?;
+    let mut wtxn = env.write_txn()?;
+    for scope in [&leaving, &staying] {
+        users.put(&mut wtxn, scope, &"a".to_string(), &"1".to_string())?;
+        users.put(&mut wtxn, scope, &"b".to_string(), &"2".to_string())?;
+        blobs.put(&mut wtxn, scope, b"a", b"1")?;
+    }
+    wtxn.commit()?;
+
+    let databases: [&dyn ScopedClear; 2] = [&users, &blobs];
+
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(clear_scope_everywhere(&mut wtxn, &leaving, &databases)?, 3);
+    // The scope remains registered until explicitly unregistered
+    assert!(registry.scope_exists_by_name(&wtxn, "leaving")?);
+    assert_eq!(
+        registry.clear_and_unregister_scope(&mut wtxn, &leaving, &databases)?,
+        0
+    );
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(!registry.scope_exists_by_name(&rtxn, "leaving")?);
+    assert_eq!(users.iter(&rtxn, &leaving)?.count(), 0);
+    assert_eq!(blobs.iter(&rtxn, &leaving)?.count(), 0);
+    assert_eq!(users.iter(&rtxn, &staying)?.count(), 2);
+    assert_eq!(blobs.iter(&rtxn, &staying)?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_scoped_clear_trait_objects() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let databases: Vec<Box<dyn ScopedClear>> = vec![
+        Box::new(
+            scoped_database_options(&env, registry.clone())
+                .types::<u32, String>()
+                .name("typed")
+                .create(&mut wtxn)?,
+        ),
+        Box::new(
+            scoped_database_options(&env, registry.clone())
+                .bytes_keys::<u32>()
+                .name("bytes_keys")
+                .create(&mut wtxn)?,
+        ),
+        Box::new(
+            scoped_database_options(&env, registry.clone())
+                .raw_bytes()
+                .name("raw")
+                .create(&mut wtxn)?,
+        ),
+    ];
+    let bytes_db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let mut wtxn = env.write_txn()?;
+    bytes_db.put(&mut wtxn, &tenant, b"a", b"1")?;
+    bytes_db.put(&mut wtxn, &tenant, b"b", b"2")?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    let mut removed = 0;
+    for db in &databases {
+        removed += db.clear_scope(&mut wtxn, &tenant)?;
+    }
+    assert_eq!(removed, 2);
+
+    // Boxed databases can also be passed to clear_scope_everywhere
+    let refs: Vec<&dyn ScopedClear> = databases.iter().map(|db| db as &dyn ScopedClear).collect();
+    assert_eq!(clear_scope_everywhere(&mut wtxn, &tenant, &refs)?, 0);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_audit_and_repair() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("users")
+        .create(&mut wtxn)?;
+    let blobs = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let active = Scope::named("active")?;
+    let abandoned = Scope::named("abandoned")?;
+    let mut wtxn = env.write_txn()?;
+    users.put(&mut wtxn, &active, &"a".to_string(), &"1".to_string())?;
+    users.put(&mut wtxn, &active, &"b".to_string(), &"2".to_string())?;
+    users.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"c".to_string(),
+        &"3".to_string(),
+    )?;
+    registry.register_scope(&mut wtxn, &abandoned)?;
+
+    // Simulate data written behind the registry's back, including hashes whose
+    // little-endian encoding ends in 0xFF
+    let raw_blobs: heed::Database<heed::types::Bytes, heed::types::Bytes> = env
+        .open_database(&wtxn, Some("blobs_scoped"))?
+        .expect("scoped database exists");
+    for hash in [42u32, 0xFF00_0000, u32::MAX] {
+        for key in [&b"x"[..], b"y"] {
+            let mut encoded = hash.to_le_bytes().to_vec();
+            encoded.extend_from_slice(&(key.len() as u64).to_le_bytes());
+            encoded.extend_from_slice(key);
+            raw_blobs.put(&mut wtxn, &encoded, b"v")?;
+        }
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        users.distinct_scope_hashes(&rtxn)?,
+        vec![active.hash().unwrap()]
+    );
+    assert_eq!(
+        blobs.distinct_scope_hashes(&rtxn)?,
+        vec![42, 0xFF00_0000, u32::MAX]
+    );
+
+    let databases: [&dyn ScopeHashEnumerator; 2] = [&users, &blobs];
+    let audit = registry.audit(&rtxn, &databases)?;
+    assert_eq!(audit.orphan_hashes, vec![42, 0xFF00_0000, u32::MAX]);
+    assert_eq!(audit.stale_entries, vec![abandoned.clone()]);
+    assert!(!audit.is_consistent());
+    assert_eq!(registry.audit(&rtxn, &[])?, RegistryAudit::default());
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    let repaired = registry.repair(&mut wtxn, &databases)?;
+    wtxn.commit()?;
+    assert_eq!(repaired, audit);
+
+    let rtxn = env.read_txn()?;
+    assert!(registry.audit(&rtxn, &databases)?.is_consistent());
+    assert_eq!(
+        registry.get_scope_name(&rtxn, &42)?,
+        Some("0x0000002a".to_string())
+    );
+    assert!(!registry.scope_exists(&rtxn, &abandoned)?);
+    assert!(registry.scope_exists(&rtxn, &active)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_clear_and_unregister() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("users")
+        .create(&mut wtxn)?;
+    let files = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("files")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let single = Scope::named("single")?;
+    let shared = Scope::named("shared")?;
+    let mut wtxn = env.write_txn()?;
+    users.put(&mut wtxn, &single, &"alice".to_string(), &"a".to_string())?;
+    users.put(&mut wtxn, &single, &"bob".to_string(), &"b".to_string())?;
+    users.put(&mut wtxn, &shared, &"carol".to_string(), &"c".to_string())?;
+    files.put(&mut wtxn, &shared, b"report", b"data")?;
+    wtxn.commit()?;
+
+    // Only this database holds the scope, so it is unregistered
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(users.clear_and_unregister(&mut wtxn, &single, &[])?, 2);
+    assert!(!registry.scope_exists(&wtxn, &single)?);
+
+    // The scope still holds data elsewhere, so it stays registered
+    let others: [&dyn ScopeEmptinessChecker; 1] = [&files];
+    assert_eq!(users.clear_and_unregister(&mut wtxn, &shared, &others)?, 1);
+    assert!(registry.scope_exists(&wtxn, &shared)?);
+
+    // Once the other database is empty as well, the scope is unregistered
+    let others: [&dyn ScopeEmptinessChecker; 1] = [&users];
+    assert_eq!(files.clear_and_unregister(&mut wtxn, &shared, &others)?, 1);
+    assert!(!registry.scope_exists(&wtxn, &shared)?);
+
+    // Clearing the default scope leaves the registry alone
+    users.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"dave".to_string(),
+        &"d".to_string(),
+    )?;
+    assert_eq!(
+        users.clear_and_unregister(&mut wtxn, &Scope::Default, &[])?,
+        1
+    );
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(registry.list_all_scopes(&rtxn)?, vec![Scope::Default]);
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_scopes() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    for i in 0..20 {
+        registry.register_scope(&mut wtxn, &Scope::named(&format!("tenant{i}"))?)?;
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let all = registry.list_all_scopes(&rtxn)?;
+    assert_eq!(all.len(), 21);
+    assert_eq!(all[0], Scope::Default);
+
+    let streamed: Vec<Scope> = registry.iter_scopes(&rtxn)?.collect::<Result<_, _>>()?;
+    assert_eq!(streamed, all);
+
+    // Stop early without decoding the remaining entries
+    let first_named = registry
+        .iter_scopes(&rtxn)?
+        .find(|scope| matches!(scope, Ok(Scope::Named { .. })))
+        .transpose()?;
+    assert_eq!(first_named.as_ref(), all.get(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_destroy_database() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let doomed = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("doomed")
+        .create(&mut wtxn)?;
+    let doomed_unified = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("doomed_unified")
+        .unified()
+        .create(&mut wtxn)?;
+    let kept = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("kept")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let mut wtxn = env.write_txn()?;
+    doomed.put(&mut wtxn, &tenant, &"a".to_string(), &"1".to_string())?;
+    doomed.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"b".to_string(),
+        &"2".to_string(),
+    )?;
+    doomed_unified.put(&mut wtxn, &tenant, b"a", b"1")?;
+    doomed_unified.put(&mut wtxn, &Scope::Default, b"b", b"2")?;
+    kept.put(&mut wtxn, &tenant, b"a", b"1")?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    doomed.destroy(&mut wtxn)?;
+    doomed_unified.destroy(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Reopening yields empty databases while others and the registry are untouched
+    let mut wtxn = env.write_txn()?;
+    let reopened = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("doomed")
+        .create(&mut wtxn)?;
+    let reopened_unified = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("doomed_unified")
+        .unified()
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let (default_stat, scoped_stat) = reopened.heed_stats(&rtxn)?;
+    assert_eq!((default_stat.entries, scoped_stat.entries), (0, 0));
+    let (_, unified_stat) = reopened_unified.heed_stats(&rtxn)?;
+    assert_eq!(unified_stat.entries, 0);
+    assert_eq!(kept.get(&rtxn, &tenant, b"a")?, Some(&b"1"[..]));
+    assert!(registry.scope_exists(&rtxn, &tenant)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_without_names() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+    let options = RegistryOptions { store_names: false };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new_with_options(
+        &env, &mut wtxn, options,
+    )?);
+    assert!(!registry.stores_names());
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("urls")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let name = "https://example.com/a/very/long/tenant/url";
+    let tenant = Scope::named(name)?;
+    let hash = tenant.hash().unwrap();
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &tenant, &"key".to_string(), &"value".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        db.get(&rtxn, &tenant, &"key".to_string())?,
+        Some("value".to_string())
+    );
+    assert!(registry.scope_exists(&rtxn, &tenant)?);
+    assert!(registry.scope_exists_by_name(&rtxn, name)?);
+    assert_eq!(registry.lookup_scope_hash(&rtxn, name)?, Some(hash));
+    assert_eq!(registry.lookup_scope_hash(&rtxn, "other")?, None);
+    assert_eq!(registry.get_scope_name(&rtxn, &hash)?, None);
+    assert_eq!(
+        registry.list_all_scopes(&rtxn)?,
+        vec![
+            Scope::Default,
+            Scope::Named {
+                name: format!("{hash:#010x}"),
+                hash,
+            },
+        ]
+    );
+    drop(rtxn);
+
+    // The mode is recorded and must match on reopening
+    let mut wtxn = env.write_txn()?;
+    assert!(matches!(
+        GlobalScopeRegistry::new(&env, &mut wtxn),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    let reopened = GlobalScopeRegistry::new_with_options(&env, &mut wtxn, options)?;
+    assert!(reopened.scope_exists(&wtxn, &tenant)?);
+
+    // Unregistering works on hashes alone
+    reopened.unregister_scope(&mut wtxn, &hash)?;
+    assert!(!reopened.scope_exists(&wtxn, &tenant)?);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_with_names_rejects_nameless_open() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = GlobalScopeRegistry::new(&env, &mut wtxn)?;
+    assert!(registry.stores_names());
+    registry.register_scope(&mut wtxn, &Scope::named("tenant")?)?;
+
+    let result = GlobalScopeRegistry::new_with_options(
+        &env,
+        &mut wtxn,
+        RegistryOptions { store_names: false },
+    );
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_initial_data() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+    let reference = Scope::named("reference")?;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let countries = scoped_database_options(&env, registry.clone())
+        .types::<String, String>()
+        .name("countries")
+        .initial_data(
+            reference.clone(),
+            vec![
+                ("de".to_string(), "Germany".to_string()),
+                ("fr".to_string(), "France".to_string()),
+            ],
+        )
+        .initial_data(
+            Scope::Default,
+            vec![("default".to_string(), "de".to_string())],
+        )
+        .create(&mut wtxn)?;
+    let blobs = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .initial_data(reference.clone(), vec![(b"logo".to_vec(), b"png".to_vec())])
+        .create(&mut wtxn)?;
+    let grid = scoped_database_options(&env, registry.clone())
+        .tuple_keys::<(u32, u32), u8>()
+        .name("grid")
+        .initial_data(reference.clone(), vec![((1, 2), 12), ((0, 5), 5)])
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(registry.scope_exists(&rtxn, &reference)?);
+    assert_eq!(
+        countries.get(&rtxn, &reference, &"fr".to_string())?,
+        Some("France".to_string())
+    );
+    assert_eq!(
+        countries.get(&rtxn, &Scope::Default, &"default".to_string())?,
+        Some("de".to_string())
+    );
+    assert_eq!(countries.iter(&rtxn, &reference)?.count(), 2);
+    assert_eq!(blobs.get(&rtxn, &reference, b"logo")?, Some(&b"png"[..]));
+    let cells: Vec<((u32, u32), u8)> = grid.iter(&rtxn, &reference)?.collect::<Result<_, _>>()?;
+    assert_eq!(cells, vec![((0, 5), 5), ((1, 2), 12)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_lookup_scope_hash_after_churn() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    // Clones see each other's changes
+    let clone = GlobalScopeRegistry::clone(&registry);
+
+    let scopes: Vec<Scope> = (0..20)
+        .map(|i| Scope::named(&format!("tenant{i}")))
+        .collect::<Result<_, _>>()?;
+    for scope in &scopes {
+        registry.register_scope(&mut wtxn, scope)?;
+    }
+    for scope in &scopes {
+        assert_eq!(
+            clone.lookup_scope_hash(&wtxn, scope.name().unwrap())?,
+            scope.hash()
+        );
+    }
+
+    // Unregister half through the clone, then register a new scope
+    for scope in scopes.iter().step_by(2) {
+        clone.unregister_scope(&mut wtxn, &scope.hash().unwrap())?;
+    }
+    let late = Scope::named("late")?;
+    registry.register_scope(&mut wtxn, &late)?;
+    for (i, scope) in scopes.iter().enumerate() {
+        let expected = if i % 2 == 0 { None } else { scope.hash() };
+        assert_eq!(
+            registry.lookup_scope_hash(&wtxn, scope.name().unwrap())?,
+            expected
+        );
+    }
+    assert_eq!(clone.lookup_scope_hash(&wtxn, "late")?, late.hash());
+    wtxn.commit()?;
+
+    // A lookup inside an aborted transaction does not leak into later ones
+    let mut wtxn = env.write_txn()?;
+    let aborted = Scope::named("aborted")?;
+    registry.register_scope(&mut wtxn, &aborted)?;
+    assert_eq!(
+        registry.lookup_scope_hash(&wtxn, "aborted")?,
+        aborted.hash()
+    );
+    wtxn.abort();
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(registry.lookup_scope_hash(&rtxn, "aborted")?, None);
+    assert_eq!(registry.lookup_scope_hash(&rtxn, "late")?, late.hash());
+    assert_eq!(registry.lookup_scope_hash(&rtxn, "never-registered")?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_namespaced_registries() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let billing = Arc::new(GlobalScopeRegistry::namespaced(&env, &mut wtxn, "billing")?);
+    let analytics = Arc::new(GlobalScopeRegistry::namespaced(
+        &env,
+        &mut wtxn,
+        "analytics",
+    )?);
+    assert_eq!(billing.namespace(), Some("billing"));
+    assert!(GlobalScopeRegistry::namespaced(&env, &mut wtxn, "").is_err());
+    assert!(GlobalScopeRegistry::namespaced(&env, &mut wtxn, "a:b").is_err());
+
+    let invoices = scoped_database_options(&env, billing.clone())
+        .raw_bytes()
+        .name("invoices")
+        .create(&mut wtxn)?;
+    let events = scoped_database_options(&env, analytics.clone())
+        .raw_bytes()
+        .name("events")
+        .create(&mut wtxn)?;
+
+    let billing_acme = billing.scope("acme")?;
+    let analytics_acme = analytics.scope("acme")?;
+    assert_ne!(billing_acme.hash(), analytics_acme.hash());
+    assert_eq!(billing_acme, Scope::named("billing:acme")?);
+
+    invoices.put(&mut wtxn, &billing_acme, b"inv-1", b"100")?;
+    events.put(&mut wtxn, &analytics_acme, b"ev-1", b"click")?;
+
+    // Scopes outside the namespace are rejected on write
+    assert!(matches!(
+        invoices.put(&mut wtxn, &Scope::named("acme")?, b"inv-2", b"200"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(
+        invoices
+            .put(&mut wtxn, &analytics_acme, b"inv-2", b"200")
+            .is_err()
+    );
+
+    assert!(billing.scope_exists_by_name(&wtxn, "acme")?);
+    assert!(!billing.scope_exists_by_name(&wtxn, "globex")?);
+    assert_eq!(
+        analytics.lookup_scope_hash(&wtxn, "acme")?,
+        analytics_acme.hash()
+    );
+    assert_eq!(
+        billing.list_all_scopes(&wtxn)?,
+        vec![Scope::Default, billing_acme.clone()]
+    );
+    assert_eq!(
+        analytics.list_all_scopes(&wtxn)?,
+        vec![Scope::Default, analytics_acme.clone()]
+    );
+
+    // Repairs only touch the registry's own namespace
+    let audit = billing.repair(&mut wtxn, &[&invoices, &events])?;
+    assert!(audit.is_consistent());
+    assert!(analytics.scope_exists_by_name(&wtxn, "acme")?);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_list_scopes_where() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("tenants")
+        .create(&mut wtxn)?;
+    for name in ["acme-eu", "acme-us", "globex", "initech-acme"] {
+        db.register_scope(&mut wtxn, &Scope::named(name)?)?;
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut names: Vec<String> = db
+        .list_scopes_where(&rtxn, |name| name.contains("acme"))?
+        .iter()
+        .map(|scope| scope.name().unwrap().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["acme-eu", "acme-us", "initech-acme"]);
+
+    let prefixed = db.list_scopes_with_prefix(&rtxn, "acme-")?;
+    assert_eq!(prefixed.len(), 2);
+    assert!(!prefixed.contains(&Scope::Default));
+
+    // The default scope is matched as the empty name
+    let all = registry.list_scopes_where(&rtxn, |_| true)?;
+    assert_eq!(all, registry.list_all_scopes(&rtxn)?);
+    assert!(
+        registry
+            .list_scopes_with_prefix(&rtxn, "")?
+            .contains(&Scope::Default)
+    );
+    assert!(db.list_scopes_where(&rtxn, |_| false)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_would_collide() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    registry.register_scope(&mut wtxn, &Scope::named("tenant1")?)?;
+
+    // Simulate a collision: "impostor" registered under the hash of "tenant2"
+    let tenant2_hash = Scope::named("tenant2")?.hash().unwrap();
+    registry.register_scope(
+        &mut wtxn,
+        &Scope::Named {
+            name: "impostor".to_string(),
+            hash: tenant2_hash,
+        },
+    )?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        registry.would_collide(&rtxn, "tenant2")?,
+        Some("impostor".to_string())
+    );
+    assert_eq!(registry.would_collide(&rtxn, "tenant1")?, None);
+    assert_eq!(registry.would_collide(&rtxn, "tenant3")?, None);
+    assert!(matches!(
+        registry.would_collide(&rtxn, ""),
+        Err(ScopedDbError::EmptyScopeDisallowed)
+    ));
+    drop(rtxn);
+
+    // A namespaced registry reports the colliding name without its namespace
+    let mut wtxn = env.write_txn()?;
+    let acme = GlobalScopeRegistry::namespaced(&env, &mut wtxn, "acme")?;
+    let users_hash = acme.scope("users")?.hash().unwrap();
+    acme.register_scope(
+        &mut wtxn,
+        &Scope::Named {
+            name: "acme:other".to_string(),
+            hash: users_hash,
+        },
+    )?;
+    assert_eq!(
+        acme.would_collide(&wtxn, "users")?,
+        Some("other".to_string())
+    );
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_register_scopes_reports_collisions() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    registry.register_scope(&mut wtxn, &Scope::named("existing")?)?;
+    wtxn.commit()?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+    // Share hashes with a registered scope and with an earlier scope of the batch
+    let clash_existing = Scope::Named {
+        name: "clash_existing".to_string(),
+        hash: Scope::named("existing")?.hash().unwrap(),
+    };
+    let clash_batch = Scope::Named {
+        name: "clash_batch".to_string(),
+        hash: tenant1.hash().unwrap(),
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let collisions = registry.register_scopes(
+        &mut wtxn,
+        &[
+            tenant1.clone(),
+            clash_existing.clone(),
+            Scope::Default,
+            Scope::named("existing")?,
+            clash_batch.clone(),
+            tenant2.clone(),
+        ],
+    )?;
+    assert_eq!(
+        collisions,
+        vec![
+            (clash_existing, "existing".to_string()),
+            (clash_batch, "tenant1".to_string()),
+        ]
+    );
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut names: Vec<String> = registry
+        .list_all_scopes(&rtxn)?
+        .iter()
+        .filter_map(|scope| scope.name().map(str::to_owned))
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["existing", "tenant1", "tenant2"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_prune_own_empty_scopes() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("single")
+        .create(&mut wtxn)?;
+
+    let kept = Scope::named("kept")?;
+    let emptied = Scope::named("emptied")?;
+    let never_written = Scope::named("never_written")?;
+    db.put(&mut wtxn, &kept, b"key", b"value")?;
+    db.put(&mut wtxn, &emptied, b"key", b"value")?;
+    db.register_scope(&mut wtxn, &never_written)?;
+    db.put(&mut wtxn, &Scope::Default, b"key", b"value")?;
+    db.clear(&mut wtxn, &emptied)?;
+    db.clear(&mut wtxn, &Scope::Default)?;
+
+    assert_eq!(db.prune_own_empty_scopes(&mut wtxn)?, 2);
+    assert_eq!(
+        registry.list_all_scopes(&wtxn)?,
+        vec![Scope::Default, kept.clone()]
+    );
+    // Nothing is left to prune
+    assert_eq!(db.prune_own_empty_scopes(&mut wtxn)?, 0);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_concurrent_registration_from_two_databases() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("users")
+        .create(&mut wtxn)?;
+    let orders = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("orders")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let shared = Scope::named("shared")?;
+    let users_only = Scope::named("users_only")?;
+    let impostor = Scope::Named {
+        name: "impostor".to_string(),
+        hash: shared.hash().unwrap(),
+    };
+
+    // An aborted registration leaves no trace, even in the name index
+    let mut wtxn = env.write_txn()?;
+    users.put(&mut wtxn, &users_only, b"k", b"v")?;
+    assert_eq!(
+        registry.lookup_scope_hash(&wtxn, "users_only")?,
+        users_only.hash()
+    );
+    wtxn.abort();
+    assert_eq!(
+        registry.lookup_scope_hash(&env.read_txn()?, "users_only")?,
+        None
+    );
+
+    let (registered_tx, registered_rx) = std::sync::mpsc::channel();
+    let (snapshot_tx, snapshot_rx) = std::sync::mpsc::channel();
+    std::thread::scope(|s| -> Result<(), ScopedDbError> {
+        let writer = {
+            let (env, users, shared, users_only) = (&env, &users, &shared, &users_only);
+            s.spawn(move || -> Result<(), ScopedDbError> {
+                let mut wtxn = env.write_txn()?;
+                users.put(&mut wtxn, shared, b"user", b"alice")?;
+                users.put(&mut wtxn, users_only, b"user", b"bob")?;
+                registered_tx.send(()).unwrap();
+                // Hold the write transaction until a reader has taken its snapshot
+                snapshot_rx.recv().unwrap();
+                wtxn.commit()?;
+                Ok(())
+            })
+        };
+
+        registered_rx.recv().unwrap();
+        let rtxn = env.read_txn()?;
+        // Uncommitted registrations are invisible to other transactions
+        assert!(!registry.scope_exists(&rtxn, &shared)?);
+        assert_eq!(registry.lookup_scope_hash(&rtxn, "shared")?, None);
+        snapshot_tx.send(()).unwrap();
+
+        // Blocks until the first writer commits, then sees its registrations
+        let mut wtxn = env.write_txn()?;
+        orders.put(&mut wtxn, &shared, b"order", b"42")?;
+        assert!(matches!(
+            orders.put(&mut wtxn, &impostor, b"order", b"43"),
+            Err(ScopedDbError::InvalidInput(_))
+        ));
+        wtxn.commit()?;
+        writer.join().unwrap()?;
+
+        // The reader's snapshot is unchanged, and the index built from it is not trusted
+        assert!(!registry.scope_exists(&rtxn, &shared)?);
+        Ok(())
+    })?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(registry.lookup_scope_hash(&rtxn, "shared")?, shared.hash());
+    assert_eq!(
+        registry.list_all_scopes(&rtxn)?.len(),
+        3,
+        "Default, shared and users_only"
+    );
+    assert_eq!(orders.get(&rtxn, &shared, b"order")?, Some(&b"42"[..]));
+    assert_eq!(users.get(&rtxn, &shared, b"user")?, Some(&b"alice"[..]));
+
+    Ok(())
+}
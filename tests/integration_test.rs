@@ -1,8 +1,12 @@
 use heed::{Env, EnvOpenOptions};
-use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
+use scoped_heed::{
+    BatchOp, GlobalScopeRegistry, OperationObserver, Scope, ScopeEmptinessChecker,
+    ScopeHashEnumerator, ScopedDbError, ScopedKey, diff_snapshots, scoped_database_options,
+    with_child_txn,
+};
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 struct TestEnv {
     env: Env,
@@ -337,7 +341,7 @@ fn test_clear_operations() -> Result<(), ScopedDbError> {
     {
         let mut wtxn = env.write_txn()?;
         let scope1 = Scope::named("scope1")?;
-        db.clear(&mut wtxn, &scope1)?;
+        assert_eq!(db.clear(&mut wtxn, &scope1)?, 2);
         wtxn.commit()?;
     }
 
@@ -363,7 +367,7 @@ fn test_clear_operations() -> Result<(), ScopedDbError> {
     // Clear default scope
     {
         let mut wtxn = env.write_txn()?;
-        db.clear(&mut wtxn, &Scope::Default)?;
+        assert_eq!(db.clear(&mut wtxn, &Scope::Default)?, 1);
         wtxn.commit()?;
     }
 
@@ -477,7 +481,7 @@ fn test_range_operations() -> Result<(), ScopedDbError> {
     // Create the database with the registry
     let mut wtxn = env.write_txn()?;
     let db = scoped_database_options(env, registry.clone())
-        .types::<String, String>()
+        .types::<char, String>()
         .name("test")
         .create(&mut wtxn)?;
     wtxn.commit()?;
@@ -487,57 +491,17 @@ fn test_range_operations() -> Result<(), ScopedDbError> {
         let mut wtxn = env.write_txn()?;
 
         // Default scope
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"a".to_string(),
-            &"default_a".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"b".to_string(),
-            &"default_b".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"c".to_string(),
-            &"default_c".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"d".to_string(),
-            &"default_d".to_string(),
-        )?;
+        db.put(&mut wtxn, &Scope::Default, &'a', &"default_a".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'b', &"default_b".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'c', &"default_c".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'d', &"default_d".to_string())?;
 
         // Scope1
         let scope1 = Scope::named("scope1")?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"a".to_string(),
-            &"scope1_a".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"b".to_string(),
-            &"scope1_b".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"c".to_string(),
-            &"scope1_c".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"d".to_string(),
-            &"scope1_d".to_string(),
-        )?;
+        db.put(&mut wtxn, &scope1, &'a', &"scope1_a".to_string())?;
+        db.put(&mut wtxn, &scope1, &'b', &"scope1_b".to_string())?;
+        db.put(&mut wtxn, &scope1, &'c', &"scope1_c".to_string())?;
+        db.put(&mut wtxn, &scope1, &'d', &"scope1_d".to_string())?;
 
         wtxn.commit()?;
     }
@@ -545,9 +509,9 @@ fn test_range_operations() -> Result<(), ScopedDbError> {
     // Test inclusive range [b, c]
     {
         let rtxn = env.read_txn()?;
-        let mut items: Vec<(String, String)> = vec![];
+        let mut items: Vec<(char, String)> = vec![];
 
-        let range = "b".to_string()..="c".to_string();
+        let range = 'b'..='c';
         let scope1 = Scope::named("scope1")?;
         for result in db.range(&rtxn, &scope1, &range)? {
             let (key, value) = result?;
@@ -555,28 +519,25 @@ fn test_range_operations() -> Result<(), ScopedDbError> {
         }
 
         assert_eq!(items.len(), 2);
-        assert_eq!(items[0], ("b".to_string(), "scope1_b".to_string()));
-        assert_eq!(items[1], ("c".to_string(), "scope1_c".to_string()));
+        assert_eq!(items[0], ('b', "scope1_b".to_string()));
+        assert_eq!(items[1], ('c', "scope1_c".to_string()));
     }
 
     // Test exclusive range (a, d)
     {
         let rtxn = env.read_txn()?;
-        let mut items: Vec<(String, String)> = vec![];
+        let mut items: Vec<(char, String)> = vec![];
 
         use std::ops::Bound;
-        let range = (
-            Bound::Excluded("a".to_string()),
-            Bound::Excluded("d".to_string()),
-        );
+        let range = (Bound::Excluded('a'), Bound::Excluded('d'));
         for result in db.range(&rtxn, &Scope::Default, &range)? {
             let (key, value) = result?;
             items.push((key, value));
         }
 
         assert_eq!(items.len(), 2);
-        assert_eq!(items[0], ("b".to_string(), "default_b".to_string()));
-        assert_eq!(items[1], ("c".to_string(), "default_c".to_string()));
+        assert_eq!(items[0], ('b', "default_b".to_string()));
+        assert_eq!(items[1], ('c', "default_c".to_string()));
     }
 
     // Test range with bytes database
@@ -622,3 +583,2871 @@ fn test_range_operations() -> Result<(), ScopedDbError> {
 
     Ok(())
 }
+
+#[test]
+fn test_delete_range_operations() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("delete_range_ops")?;
+    let env = &test_env.env;
+
+    // Create a global registry for tracking scopes
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    wtxn.commit()?;
+
+    // Create the database with the registry
+    let mut wtxn = env.write_txn()?;
+    let db = scoped_database_options(env, registry.clone())
+        .types::<char, String>()
+        .name("test")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let scope1 = Scope::named("scope1")?;
+    let scope2 = Scope::named("scope2")?;
+
+    // Insert the same keys in the default scope and two named scopes
+    {
+        let mut wtxn = env.write_txn()?;
+        for key in ['a', 'b', 'c', 'd'] {
+            for scope in [&Scope::Default, &scope1, &scope2] {
+                db.put(&mut wtxn, scope, &key, &format!("value_{}", key))?;
+            }
+        }
+        wtxn.commit()?;
+    }
+
+    // Delete [b, c] from scope1 and (a, d) from the default scope
+    {
+        let mut wtxn = env.write_txn()?;
+        let deleted = db.delete_range(&mut wtxn, &scope1, &('b'..='c'))?;
+        assert_eq!(deleted, 2);
+
+        use std::ops::Bound;
+        let range = (Bound::Excluded('a'), Bound::Excluded('d'));
+        let deleted = db.delete_range(&mut wtxn, &Scope::Default, &range)?;
+        assert_eq!(deleted, 2);
+        wtxn.commit()?;
+    }
+
+    // Verify only the requested ranges were removed
+    {
+        let rtxn = env.read_txn()?;
+        let keys = |scope: &Scope| -> Result<Vec<char>, ScopedDbError> {
+            db.iter(&rtxn, scope)?
+                .map(|result| result.map(|(key, _)| key))
+                .collect()
+        };
+
+        assert_eq!(keys(&scope1)?, vec!['a', 'd']);
+        assert_eq!(keys(&Scope::Default)?, vec!['a', 'd']);
+        assert_eq!(keys(&scope2)?.len(), 4);
+    }
+
+    // An unbounded range clears the rest of the scope
+    {
+        let mut wtxn = env.write_txn()?;
+        let deleted = db.delete_range_with_name(&mut wtxn, Some("scope1"), &(..))?;
+        assert_eq!(deleted, 2);
+        wtxn.commit()?;
+    }
+
+    {
+        let rtxn = env.read_txn()?;
+        assert_eq!(db.iter(&rtxn, &scope1)?.count(), 0);
+        assert_eq!(db.iter(&rtxn, &scope2)?.count(), 4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_key_range_operations() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("tuple_key_ops")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .tuple_keys::<(u64, u32), String>()
+        .name("documents")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+
+    // Doc ids above 255 would sort incorrectly with a little-endian encoding
+    let mut wtxn = env.write_txn()?;
+    for (user_id, doc_id) in [(7u64, 300u32), (7, 2), (256, 1), (1, 9), (7, 256)] {
+        db.put(
+            &mut wtxn,
+            &tenant,
+            &(user_id, doc_id),
+            &format!("{}:{}", user_id, doc_id),
+        )?;
+    }
+    db.put(&mut wtxn, &Scope::Default, &(7, 1), &"default".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+
+    // All documents of user 7 in numeric doc_id order
+    let keys: Vec<(u64, u32)> = db
+        .range(&rtxn, &tenant, &((7, 0)..=(7, u32::MAX)))?
+        .map(|result| result.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![(7, 2), (7, 256), (7, 300)]);
+
+    // Full iteration follows numeric ordering of the first component
+    let keys: Vec<(u64, u32)> = db
+        .iter(&rtxn, &tenant)?
+        .map(|result| result.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![(1, 9), (7, 2), (7, 256), (7, 300), (256, 1)]);
+
+    assert_eq!(
+        db.get(&rtxn, &tenant, &(256, 1))?,
+        Some("256:1".to_string())
+    );
+    assert_eq!(
+        db.get(&rtxn, &Scope::Default, &(7, 1))?,
+        Some("default".to_string())
+    );
+    assert_eq!(db.get(&rtxn, &tenant, &(7, 1))?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_operations() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("merge_ops")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, Vec<u32>>()
+        .name("sets")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let key = "members".to_string();
+    let union = |current: Option<Vec<u32>>, delta: Vec<u32>| {
+        let mut merged = current.unwrap_or_default();
+        merged.extend(delta);
+        merged.sort_unstable();
+        merged.dedup();
+        merged
+    };
+
+    let mut wtxn = env.write_txn()?;
+    db.merge(&mut wtxn, &tenant, &key, vec![3, 1], union)?;
+    db.merge(&mut wtxn, &tenant, &key, vec![2, 3], union)?;
+    db.merge_with_name(&mut wtxn, None, &key, vec![9], union)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get(&rtxn, &tenant, &key)?, Some(vec![1, 2, 3]));
+    assert_eq!(db.get(&rtxn, &Scope::Default, &key)?, Some(vec![9]));
+    assert_eq!(db.get(&rtxn, &Scope::named("tenant2")?, &key)?, None);
+    assert_eq!(db.list_scopes(&rtxn)?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_heed_stats() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("heed_stats")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("stats")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("stats_bytes")
+        .create(&mut wtxn)?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+    db.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"a".to_string(),
+        &"1".to_string(),
+    )?;
+    db.put(&mut wtxn, &tenant1, &"a".to_string(), &"1".to_string())?;
+    db.put(&mut wtxn, &tenant1, &"b".to_string(), &"2".to_string())?;
+    db.put(&mut wtxn, &tenant2, &"a".to_string(), &"1".to_string())?;
+    bytes_db.put(&mut wtxn, &tenant1, b"a", b"1")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let (default_stat, scoped_stat) = db.heed_stats(&rtxn)?;
+    assert_eq!(default_stat.entries, 1);
+    // The scoped database mixes all named scopes in one B-tree
+    assert_eq!(scoped_stat.entries, 3);
+    assert!(scoped_stat.depth >= 1);
+
+    let (default_stat, scoped_stat) = bytes_db.heed_stats(&rtxn)?;
+    assert_eq!(default_stat.entries, 0);
+    assert_eq!(scoped_stat.entries, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_env_accessor_and_clear_and_commit() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("env_accessor")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<String>()
+        .name("env_accessor")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    assert_eq!(db.env().path(), env.path());
+    assert_eq!(db.clone().env().path(), env.path());
+
+    let tenant = Scope::named("tenant1")?;
+    let mut wtxn = db.env().write_txn()?;
+    db.put(&mut wtxn, &tenant, b"a", &"1".to_string())?;
+    db.put(&mut wtxn, &tenant, b"b", &"2".to_string())?;
+    db.put(&mut wtxn, &Scope::Default, b"a", &"3".to_string())?;
+    wtxn.commit()?;
+
+    assert_eq!(db.clear_and_commit(&tenant)?, 2);
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.iter(&rtxn, &tenant)?.count(), 0);
+    assert_eq!(db.get(&rtxn, &Scope::Default, b"a")?, Some("3".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_prefix_string_keys() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("iter_prefix")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("orders")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+
+    // Keys of different lengths, which bincode orders by length first
+    let keys = [
+        "order:2024:1",
+        "order:2024:10",
+        "order:2024:200",
+        "order:2023:5",
+        "order:2024",
+        "invoice:2024:1",
+    ];
+    let mut wtxn = env.write_txn()?;
+    for (i, key) in keys.iter().enumerate() {
+        db.put(&mut wtxn, &tenant1, &key.to_string(), &(i as u32))?;
+        db.put(&mut wtxn, &Scope::Default, &key.to_string(), &(i as u32))?;
+    }
+    db.put(&mut wtxn, &tenant2, &"order:2024:9".to_string(), &99)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let collect = |scope: Option<&str>, prefix: &str| -> Result<Vec<String>, ScopedDbError> {
+        let mut keys = db
+            .iter_prefix_with_name(&rtxn, scope, prefix)?
+            .map(|r| r.map(|(k, _)| k))
+            .collect::<Result<Vec<_>, _>>()?;
+        keys.sort();
+        Ok(keys)
+    };
+
+    let expected = vec!["order:2024:1", "order:2024:10", "order:2024:200"];
+    assert_eq!(collect(Some("tenant1"), "order:2024:")?, expected);
+    assert_eq!(collect(None, "order:2024:")?, expected);
+    assert_eq!(collect(Some("tenant1"), "order:")?.len(), 5);
+    assert_eq!(collect(Some("tenant1"), "")?.len(), 6);
+    assert_eq!(
+        collect(Some("tenant2"), "order:2024:")?,
+        vec!["order:2024:9"]
+    );
+    assert!(collect(Some("tenant3"), "order:")?.is_empty());
+
+    let values = db
+        .iter_prefix(&rtxn, &tenant1, "invoice:")?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, vec![("invoice:2024:1".to_string(), 5)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_range_key_ordering() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("range_key_ordering")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let array_db = scoped_database_options(env, registry.clone())
+        .types::<[u8; 2], u32>()
+        .name("array_keys")
+        .create(&mut wtxn)?;
+    let signed_db = scoped_database_options(env, registry.clone())
+        .tuple_keys::<i64, u32>()
+        .name("signed_keys")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let mut wtxn = env.write_txn()?;
+    for (i, key) in [[0u8, 255], [1, 0], [1, 1], [2, 0]].iter().enumerate() {
+        array_db.put(&mut wtxn, &tenant, key, &(i as u32))?;
+    }
+    // bincode would encode -1i64 as [0xFF; 8], sorting it after 1i64
+    for key in [-300i64, -1, 0, 1, 300] {
+        signed_db.put(&mut wtxn, &tenant, &key, &(key.unsigned_abs() as u32))?;
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let keys: Vec<[u8; 2]> = array_db
+        .range(&rtxn, &tenant, &([0, 255]..[2, 0]))?
+        .map(|r| r.map(|(k, _)| k))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![[0, 255], [1, 0], [1, 1]]);
+
+    let keys: Vec<i64> = signed_db
+        .range(&rtxn, &tenant, &(-1..=1))?
+        .map(|r| r.map(|(k, _)| k))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![-1, 0, 1]);
+    drop(rtxn);
+
+    // Deletion walks the same bounds, so it removes exactly the keys `range` returns
+    let mut wtxn = env.write_txn()?;
+    let deleted = array_db.delete_range(&mut wtxn, &tenant, &([0, 255]..[2, 0]))?;
+    assert_eq!(deleted, 3);
+    let keys: Vec<[u8; 2]> = array_db
+        .iter(&wtxn, &tenant)?
+        .map(|r| r.map(|(k, _)| k))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![[2, 0]]);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_neighbor_lookups() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("neighbor_lookups")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<char, u32>()
+        .name("neighbors")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("neighbors_bytes")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Adjacent hashes, so a seek past the end of one scope lands in the other
+    let scope = Scope::Named {
+        name: "five".to_string(),
+        hash: 5,
+    };
+    let next_scope = Scope::Named {
+        name: "six".to_string(),
+        hash: 6,
+    };
+
+    let mut wtxn = env.write_txn()?;
+    for (i, key) in ['b', 'd', 'f'].into_iter().enumerate() {
+        db.put(&mut wtxn, &scope, &key, &(i as u32))?;
+        db.put(&mut wtxn, &Scope::Default, &key, &(i as u32 + 10))?;
+        bytes_db.put(&mut wtxn, &scope, &[key as u8], &[i as u8])?;
+    }
+    db.put(&mut wtxn, &next_scope, &'a', &100)?;
+    db.put(&mut wtxn, &next_scope, &'z', &101)?;
+    bytes_db.put(&mut wtxn, &next_scope, b"a", b"x")?;
+    bytes_db.put(&mut wtxn, &Scope::Default, b"a", b"y")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.ceiling(&rtxn, &scope, &'d')?, Some(('d', 1)));
+    assert_eq!(db.ceiling(&rtxn, &scope, &'c')?, Some(('d', 1)));
+    assert_eq!(db.higher(&rtxn, &scope, &'d')?, Some(('f', 2)));
+    assert_eq!(db.floor(&rtxn, &scope, &'e')?, Some(('d', 1)));
+    assert_eq!(db.lower(&rtxn, &scope, &'d')?, Some(('b', 0)));
+    // Never cross into the adjacent scopes
+    assert_eq!(db.ceiling(&rtxn, &scope, &'g')?, None);
+    assert_eq!(db.higher(&rtxn, &scope, &'f')?, None);
+    assert_eq!(db.floor(&rtxn, &scope, &'a')?, None);
+    assert_eq!(db.lower(&rtxn, &next_scope, &'a')?, None);
+    assert_eq!(db.floor(&rtxn, &next_scope, &'y')?, Some(('a', 100)));
+    assert_eq!(db.ceiling(&rtxn, &Scope::Default, &'e')?, Some(('f', 12)));
+    assert_eq!(db.lower(&rtxn, &Scope::Default, &'b')?, None);
+
+    assert_eq!(
+        bytes_db.ceiling(&rtxn, &scope, b"c")?,
+        Some((&b"d"[..], &[1u8][..]))
+    );
+    assert_eq!(bytes_db.higher(&rtxn, &scope, b"f")?, None);
+    assert_eq!(
+        bytes_db.lower(&rtxn, &scope, b"d")?,
+        Some((&b"b"[..], &[0u8][..]))
+    );
+    assert_eq!(bytes_db.floor(&rtxn, &next_scope, b"\x00")?, None);
+    assert_eq!(
+        bytes_db.floor(&rtxn, &Scope::Default, b"z")?,
+        Some((&b"a"[..], &b"y"[..]))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scoped_views() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("scoped_views")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<char, String>()
+        .name("views")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("views_bytes")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = db.scoped(Scope::named("tenant1")?);
+    let default = db.scoped(Scope::Default);
+    let raw = bytes_db.scoped(Scope::named("tenant1")?);
+    assert_eq!(tenant.scope(), &Scope::named("tenant1")?);
+
+    let mut wtxn = env.write_txn()?;
+    tenant.put(&mut wtxn, &'a', &"tenant_a".to_string())?;
+    tenant.put(&mut wtxn, &'b', &"tenant_b".to_string())?;
+    tenant.put(&mut wtxn, &'c', &"tenant_c".to_string())?;
+    default.put(&mut wtxn, &'a', &"default_a".to_string())?;
+    raw.put(&mut wtxn, b"k", b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(tenant.get(&rtxn, &'a')?, Some("tenant_a".to_string()));
+    assert_eq!(default.get(&rtxn, &'a')?, Some("default_a".to_string()));
+    assert_eq!(
+        db.get(&rtxn, &Scope::named("tenant1")?, &'b')?,
+        Some("tenant_b".to_string())
+    );
+    assert_eq!(tenant.iter(&rtxn)?.count(), 3);
+    let range = 'b'..='c';
+    let keys: Vec<char> = tenant
+        .range(&rtxn, &range)?
+        .map(|r| r.map(|(k, _)| k))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec!['b', 'c']);
+    assert_eq!(raw.get(&rtxn, b"k")?, Some(&b"v"[..]));
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    assert!(tenant.delete(&mut wtxn, &'a')?);
+    assert_eq!(tenant.clear(&mut wtxn)?, 2);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(tenant.iter(&rtxn)?.count(), 0);
+    assert_eq!(default.get(&rtxn, &'a')?, Some("default_a".to_string()));
+    assert_eq!(raw.get(&rtxn, b"k")?, Some(&b"v"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn test_per_scope_counts() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("per_scope_counts")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("counts")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("counts_bytes")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+
+    let mut wtxn = env.write_txn()?;
+    for i in 0..3u32 {
+        db.put(&mut wtxn, &tenant1, &format!("key{i}"), &i)?;
+    }
+    db.put(&mut wtxn, &tenant2, &"key".to_string(), &0)?;
+    db.put(&mut wtxn, &Scope::Default, &"a".to_string(), &0)?;
+    db.put(&mut wtxn, &Scope::Default, &"b".to_string(), &0)?;
+    bytes_db.put(&mut wtxn, &tenant2, b"k1", b"v")?;
+    bytes_db.put(&mut wtxn, &tenant2, b"k2", b"v")?;
+    wtxn.commit()?;
+
+    // Write an entry under a hash that was never registered
+    let mut wtxn = env.write_txn()?;
+    let raw_db: heed::Database<heed::types::Bytes, heed::types::Bytes> = env
+        .open_database(&wtxn, Some("counts_bytes_scoped"))?
+        .expect("scoped database exists");
+    let mut key = 42u32.to_le_bytes().to_vec();
+    key.extend_from_slice(&1u64.to_le_bytes());
+    key.push(b'x');
+    raw_db.put(&mut wtxn, &key, b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut counts = db.per_scope_counts(&rtxn)?;
+    assert_eq!(counts.remove(0), (Scope::Default, 2));
+    counts.sort_by(|a, b| a.0.name().cmp(&b.0.name()));
+    assert_eq!(counts, vec![(tenant1.clone(), 3), (tenant2.clone(), 1)]);
+
+    let counts = bytes_db.per_scope_counts(&rtxn)?;
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[0], (Scope::Default, 0));
+    assert!(counts.contains(&(tenant2.clone(), 2)));
+    assert!(counts.contains(&(
+        Scope::Named {
+            name: "0x0000002a".to_string(),
+            hash: 42,
+        },
+        1
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn test_distinct_scope_hashes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("distinct_scope_hashes")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<u32, u32>()
+        .name("hashes")
+        .create(&mut wtxn)?;
+    let unified_db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("hashes_unified")
+        .unified()
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Hashes whose little-endian encodings sort differently from their numeric order
+    let hashes = [0x0000_00FF, 0x0000_0100, 0x0100_0000, u32::MAX];
+    let scopes: Vec<Scope> = hashes
+        .iter()
+        .map(|&hash| Scope::Named {
+            name: format!("scope_{hash:x}"),
+            hash,
+        })
+        .collect();
+
+    let mut wtxn = env.write_txn()?;
+    for scope in &scopes {
+        for i in 0..50u32 {
+            db.put(&mut wtxn, scope, &i, &i)?;
+        }
+        unified_db.put(&mut wtxn, scope, b"key", &1)?;
+    }
+    db.put(&mut wtxn, &Scope::Default, &1, &1)?;
+    unified_db.put(&mut wtxn, &Scope::Default, b"key", &1)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.distinct_scope_hashes(&rtxn)?, hashes.to_vec());
+    // The reserved default-scope hash of unified databases is not a named scope
+    assert_eq!(unified_db.distinct_scope_hashes(&rtxn)?, hashes.to_vec());
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    db.clear(&mut wtxn, &scopes[1])?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        db.distinct_scope_hashes(&rtxn)?,
+        vec![0x0000_00FF, 0x0100_0000, u32::MAX]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_hashes_present() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("scope_hashes_present")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("present")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    for hash in [0x0000_0100u32, 0x0000_00FF, u32::MAX] {
+        let scope = Scope::Named {
+            name: format!("scope_{hash:x}"),
+            hash,
+        };
+        for i in 0..20u8 {
+            db.put(&mut wtxn, &scope, &[i], b"v")?;
+        }
+    }
+    db.put(&mut wtxn, &Scope::Default, b"k", b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    // Storage order follows the little-endian hash bytes
+    let present = db
+        .scope_hashes_present(&rtxn)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(present, vec![0x0000_0100, 0x0000_00FF, u32::MAX]);
+
+    let first = db.scope_hashes_present(&rtxn)?.next().transpose()?;
+    assert_eq!(first, Some(0x0000_0100));
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    events: Mutex<Vec<String>>,
+}
+
+impl RecordingObserver {
+    fn record(&self, event: &str, scope: &Scope) {
+        let scope = scope.name().unwrap_or("default");
+        self.events.lock().unwrap().push(format!("{event}:{scope}"));
+    }
+}
+
+impl OperationObserver for RecordingObserver {
+    fn on_put(&self, scope: &Scope) {
+        self.record("put", scope);
+    }
+
+    fn on_get(&self, scope: &Scope, hit: bool) {
+        self.record(if hit { "hit" } else { "miss" }, scope);
+    }
+
+    fn on_delete(&self, scope: &Scope, deleted: bool) {
+        self.record(if deleted { "delete" } else { "delete_missing" }, scope);
+    }
+
+    fn on_iter(&self, scope: &Scope) {
+        self.record("iter", scope);
+    }
+}
+
+#[test]
+fn test_operation_observer() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("operation_observer")?;
+    let env = &test_env.env;
+    let observer = Arc::new(RecordingObserver::default());
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("observed")
+        .observer(observer.clone())
+        .create(&mut wtxn)?;
+    let raw_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("observed_raw")
+        .unified()
+        .observer(observer.clone())
+        .create(&mut wtxn)?;
+    let unobserved = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("unobserved")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &tenant, &"a".to_string(), &"1".to_string())?;
+    db.scoped(Scope::Default)
+        .put(&mut wtxn, &"b".to_string(), &"2".to_string())?;
+    raw_db.put_with_name(&mut wtxn, None, b"c", b"3")?;
+    unobserved.put(&mut wtxn, &tenant, b"d", b"4")?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    db.get(&wtxn, &tenant, &"a".to_string())?;
+    db.get(&wtxn, &tenant, &"missing".to_string())?;
+    raw_db.iter(&wtxn, &Scope::Default)?.count();
+    db.delete(&mut wtxn, &tenant, &"a".to_string())?;
+    db.delete(&mut wtxn, &tenant, &"a".to_string())?;
+    wtxn.commit()?;
+
+    // Failed operations are not reported
+    let reserved = Scope::Named {
+        name: "reserved".to_string(),
+        hash: scoped_heed::DEFAULT_SCOPE_HASH,
+    };
+    let mut wtxn = env.write_txn()?;
+    assert!(raw_db.put(&mut wtxn, &reserved, b"e", b"5").is_err());
+    drop(wtxn);
+
+    assert_eq!(
+        *observer.events.lock().unwrap(),
+        vec![
+            "put:tenant1",
+            "put:default",
+            "put:default",
+            "hit:tenant1",
+            "miss:tenant1",
+            "iter:default",
+            "delete:tenant1",
+            "delete_missing:tenant1",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_get_raw_values() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("get_raw_values")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let typed = scoped_database_options(env, registry.clone())
+        .types::<String, Vec<u8>>()
+        .name("typed_raw")
+        .create(&mut wtxn)?;
+    let bytes_keys = scoped_database_options(env, registry.clone())
+        .bytes_keys::<String>()
+        .name("bytes_keys_raw")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let payload = vec![1u8, 2, 3, 4];
+    let mut wtxn = env.write_txn()?;
+    typed.put(&mut wtxn, &tenant, &"blob".to_string(), &payload)?;
+    typed.put(&mut wtxn, &Scope::Default, &"blob".to_string(), &vec![9u8])?;
+    bytes_keys.put(&mut wtxn, &tenant, b"greeting", &"hello".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        typed.get_raw(&rtxn, &tenant, &"blob".to_string())?,
+        Some(bincode::serialize(&payload).unwrap().as_slice())
+    );
+    assert_eq!(
+        typed.get_raw(&rtxn, &Scope::Default, &"blob".to_string())?,
+        Some(bincode::serialize(&vec![9u8]).unwrap().as_slice())
+    );
+    assert_eq!(
+        bytes_keys.get_raw(&rtxn, &tenant, b"greeting")?,
+        Some(bincode::serialize("hello").unwrap().as_slice())
+    );
+
+    // Missing keys and other scopes yield nothing
+    assert_eq!(typed.get_raw(&rtxn, &tenant, &"missing".to_string())?, None);
+    assert_eq!(
+        bytes_keys.get_raw(&rtxn, &Scope::Default, b"greeting")?,
+        None
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_range_keys_explicit_bounds() -> Result<(), ScopedDbError> {
+    use std::ops::Bound;
+
+    let test_env = TestEnv::new("range_keys_explicit_bounds")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw_range_keys")
+        .create(&mut wtxn)?;
+    let typed = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("typed_range_keys")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let mut wtxn = env.write_txn()?;
+    for (i, key) in [b"a", b"b", b"c", b"d"].iter().enumerate() {
+        for scope in [&tenant, &Scope::Default] {
+            raw.put(&mut wtxn, scope, *key, *key)?;
+            typed.put(&mut wtxn, scope, *key, &(i as u32))?;
+        }
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        let keys: Vec<&[u8]> = raw
+            .range_keys(
+                &rtxn,
+                scope,
+                Bound::Included(&b"b"[..]),
+                Bound::Excluded(&b"d"[..]),
+            )?
+            .map(|result| result.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(keys, vec![&b"b"[..], &b"c"[..]]);
+
+        let values: Vec<u32> = typed
+            .range_keys(&rtxn, scope, Bound::Excluded(&b"a"[..]), Bound::Unbounded)?
+            .map(|result| result.map(|(_, value)| value))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_prefix_matches_stored_keys() -> Result<(), ScopedDbError> {
+    use heed::types::Bytes;
+    use scoped_heed::ScopedBytesCodec;
+
+    let test_env = TestEnv::new("scope_prefix_matches_stored_keys")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("prefix_raw")
+        .create(&mut wtxn)?;
+    let typed = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("prefix_typed")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let name = "tenant-42";
+    let prefix = ScopedBytesCodec::scope_prefix(name);
+    let scope = Scope::named(name)?;
+    assert_eq!(prefix, ScopedBytesCodec::hash_prefix(scope.hash().unwrap()));
+
+    let mut wtxn = env.write_txn()?;
+    raw.put(&mut wtxn, &scope, b"key", b"value")?;
+    typed.put(&mut wtxn, &scope, &"key".to_string(), &"value".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let stored = |db_name: &str| -> Result<Vec<Vec<u8>>, ScopedDbError> {
+        let db = env
+            .open_database::<Bytes, Bytes>(&rtxn, Some(db_name))?
+            .unwrap();
+        let keys = db
+            .iter(&rtxn)?
+            .map(|result| result.map(|(key, _)| key.to_vec()))
+            .collect::<Result<_, _>>()?;
+        Ok(keys)
+    };
+
+    // The raw key is exactly the prefix, the key length and the key
+    let mut expected = prefix.to_vec();
+    expected.extend_from_slice(&3u64.to_le_bytes());
+    expected.extend_from_slice(b"key");
+    assert_eq!(stored("prefix_raw_scoped")?, vec![expected]);
+
+    // The generic database uses the same prefix
+    let typed_keys = stored("prefix_typed_scoped")?;
+    assert_eq!(typed_keys.len(), 1);
+    assert!(typed_keys[0].starts_with(&prefix));
+
+    Ok(())
+}
+
+#[test]
+fn test_put_returning_previous_value() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("put_returning_previous_value")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let typed = scoped_database_options(env, registry.clone())
+        .types::<String, u64>()
+        .name("put_returning_typed")
+        .create(&mut wtxn)?;
+    let bytes_keys = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u64>()
+        .name("put_returning_bytes_keys")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("put_returning_raw")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let key = "epoch".to_string();
+    let mut wtxn = env.write_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        assert_eq!(typed.put_returning(&mut wtxn, scope, &key, &1)?, None);
+        assert_eq!(typed.put_returning(&mut wtxn, scope, &key, &2)?, Some(1));
+        assert_eq!(typed.get(&wtxn, scope, &key)?, Some(2));
+
+        assert_eq!(
+            bytes_keys.put_returning(&mut wtxn, scope, b"epoch", &1)?,
+            None
+        );
+        assert_eq!(
+            bytes_keys.put_returning(&mut wtxn, scope, b"epoch", &2)?,
+            Some(1)
+        );
+        assert_eq!(bytes_keys.get(&wtxn, scope, b"epoch")?, Some(2));
+
+        assert_eq!(raw.put_returning(&mut wtxn, scope, b"epoch", b"one")?, None);
+        assert_eq!(
+            raw.put_returning(&mut wtxn, scope, b"epoch", b"two")?,
+            Some(b"one".to_vec())
+        );
+        assert_eq!(raw.get(&wtxn, scope, b"epoch")?, Some(&b"two"[..]));
+    }
+    assert!(registry.scope_exists(&wtxn, &tenant)?);
+    wtxn.commit()?;
+
+    // Values written through put_returning are visible to the regular API
+    let rtxn = env.read_txn()?;
+    assert_eq!(typed.get(&rtxn, &tenant, &key)?, Some(2));
+    assert_eq!(bytes_keys.get(&rtxn, &Scope::Default, b"epoch")?, Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_compare_and_swap() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("compare_and_swap")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("cas")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let mut wtxn = env.write_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        // Insert only if absent
+        assert!(db.compare_and_swap(&mut wtxn, scope, b"lock", None, Some(b"w1"))?);
+        assert!(!db.compare_and_swap(&mut wtxn, scope, b"lock", None, Some(b"w2"))?);
+        assert_eq!(db.get(&wtxn, scope, b"lock")?, Some(&b"w1"[..]));
+
+        // Replace only the expected value
+        assert!(!db.compare_and_swap(&mut wtxn, scope, b"lock", Some(b"w2"), Some(b"w3"))?);
+        assert!(db.compare_and_swap(&mut wtxn, scope, b"lock", Some(b"w1"), Some(b"w2"))?);
+        assert_eq!(db.get(&wtxn, scope, b"lock")?, Some(&b"w2"[..]));
+
+        // Delete only the expected value
+        assert!(!db.compare_and_swap(&mut wtxn, scope, b"lock", Some(b"w1"), None)?);
+        assert!(db.compare_and_swap(&mut wtxn, scope, b"lock", Some(b"w2"), None)?);
+        assert_eq!(db.get(&wtxn, scope, b"lock")?, None);
+
+        // Expecting absence and writing absence is a successful no-op
+        assert!(db.compare_and_swap(&mut wtxn, scope, b"lock", None, None)?);
+        assert_eq!(db.get(&wtxn, scope, b"lock")?, None);
+    }
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_and_diff_scope() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("snapshot_diff")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("snapshots")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    db.put(&mut wtxn, &tenant, b"keep", b"1")?;
+    db.put(&mut wtxn, &tenant, b"change", b"old")?;
+    db.put(&mut wtxn, &tenant, b"remove", b"gone")?;
+    db.put(&mut wtxn, &other, b"unrelated", b"x")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let before = db.snapshot_scope(&rtxn, &tenant)?;
+    drop(rtxn);
+    assert_eq!(before.len(), 3);
+    assert_eq!(before.get(&b"change"[..]), Some(&b"old".to_vec()));
+
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &tenant, b"change", b"new")?;
+    db.delete(&mut wtxn, &tenant, b"remove")?;
+    db.put(&mut wtxn, &tenant, b"add", b"fresh")?;
+    db.put(&mut wtxn, &other, b"unrelated", b"y")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let after = db.snapshot_scope(&rtxn, &tenant)?;
+    let diff = diff_snapshots(&before, &after);
+    assert_eq!(
+        diff.added.into_iter().collect::<Vec<_>>(),
+        vec![(b"add".to_vec(), b"fresh".to_vec())]
+    );
+    assert_eq!(
+        diff.changed.into_iter().collect::<Vec<_>>(),
+        vec![(b"change".to_vec(), (b"old".to_vec(), b"new".to_vec()))]
+    );
+    assert_eq!(
+        diff.removed.into_iter().collect::<Vec<_>>(),
+        vec![(b"remove".to_vec(), b"gone".to_vec())]
+    );
+    assert!(diff_snapshots(&after, &db.snapshot_scope(&rtxn, &tenant)?).is_empty());
+    assert!(db.snapshot_scope(&rtxn, &Scope::Default)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_entry_api() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("entry_api")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u64>()
+        .name("counters")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let key = "visits".to_string();
+
+    let mut wtxn = env.write_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        let entry = db.entry(&mut wtxn, scope, &key)?;
+        assert!(!entry.is_occupied());
+        assert_eq!(entry.and_modify(|count| *count += 1).or_insert(1)?, 1);
+
+        let entry = db.entry(&mut wtxn, scope, &key)?;
+        assert!(entry.is_occupied());
+        assert_eq!(entry.get(), Some(&1));
+        assert_eq!(entry.and_modify(|count| *count += 1).or_insert(1)?, 2);
+
+        // The default is only computed for vacant entries
+        let value = db
+            .entry(&mut wtxn, scope, &key)?
+            .or_insert_with(|| panic!("entry is occupied"))?;
+        assert_eq!(value, 2);
+
+        // Modifications are discarded unless a terminal method is called
+        let _ = db
+            .entry(&mut wtxn, scope, &key)?
+            .and_modify(|count| *count = 100);
+        assert_eq!(db.get(&wtxn, scope, &key)?, Some(2));
+
+        assert_eq!(
+            db.entry(&mut wtxn, scope, &"other".to_string())?
+                .or_default()?,
+            0
+        );
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get(&rtxn, &tenant, &key)?, Some(2));
+    assert_eq!(db.get(&rtxn, &Scope::Default, &key)?, Some(2));
+    assert_eq!(db.get(&rtxn, &tenant, &"other".to_string())?, Some(0));
+    assert_eq!(db.list_scopes(&rtxn)?, vec![Scope::Default, tenant]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ordered_keys_database() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("ordered_keys")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let events = scoped_database_options(env, registry.clone())
+        .types::<i64, String>()
+        .ordered_keys()
+        .name("events")
+        .create(&mut wtxn)?;
+    let names = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .ordered_keys()
+        .name("names")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant1")?;
+    let other = Scope::named("tenant2")?;
+
+    let mut wtxn = env.write_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        for key in [300i64, -1, 1, -256, 256, 0] {
+            events.put(&mut wtxn, scope, &key, &key.to_string())?;
+        }
+        for (value, key) in ["b", "aa", "a", "ab"].into_iter().enumerate() {
+            names.put(&mut wtxn, scope, &key.to_string(), &(value as u32))?;
+        }
+    }
+    events.put(&mut wtxn, &other, &5, &"other".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for scope in [&tenant, &Scope::Default] {
+        // Bincode would order these as 0, 1, 256, 300, -256, -1
+        let keys: Vec<i64> = events
+            .iter(&rtxn, scope)?
+            .map(|entry| entry.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(keys, vec![-256, -1, 0, 1, 256, 300]);
+
+        let keys: Vec<i64> = events
+            .range(&rtxn, scope, &(-1..=256))?
+            .map(|entry| entry.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(keys, vec![-1, 0, 1, 256]);
+
+        assert_eq!(events.ceiling(&rtxn, scope, &2)?.map(|(k, _)| k), Some(256));
+        assert_eq!(events.floor(&rtxn, scope, &-2)?.map(|(k, _)| k), Some(-256));
+        assert_eq!(events.higher(&rtxn, scope, &300)?, None);
+        assert_eq!(events.lower(&rtxn, scope, &-256)?, None);
+
+        // Bincode would put "aa" and "ab" after "b" because of the length prefix
+        let keys: Vec<String> = names
+            .range(&rtxn, scope, &("a".to_string().."b".to_string()))?
+            .map(|entry| entry.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(keys, vec!["a", "aa", "ab"]);
+        assert_eq!(names.get(&rtxn, scope, &"b".to_string())?, Some(0));
+    }
+    assert_eq!(
+        events.iter(&rtxn, &other)?.collect::<Result<Vec<_>, _>>()?,
+        vec![(5, "other".to_string())]
+    );
+    let mut expected = vec![tenant.hash().unwrap(), other.hash().unwrap()];
+    expected.sort_unstable();
+    assert_eq!(events.scope_hashes_in_db(&rtxn)?, expected);
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(events.clear(&mut wtxn, &tenant)?, 6);
+    assert!(events.delete(&mut wtxn, &Scope::Default, &0)?);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert!(events.is_scope_empty_in_db(&rtxn, &tenant)?);
+    assert_eq!(events.iter(&rtxn, &Scope::Default)?.count(), 5);
+    assert_eq!(events.iter(&rtxn, &other)?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_clear_removes_keys_below_default() -> Result<(), ScopedDbError> {
+    // A key type whose default value is not its smallest encoding
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Priority(u8);
+
+    impl Default for Priority {
+        fn default() -> Self {
+            Priority(128)
+        }
+    }
+
+    let test_env = TestEnv::new("clear_below_default")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<Priority, String>()
+        .name("priorities")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    for level in [1, 128, 200] {
+        db.put(&mut wtxn, &tenant, &Priority(level), &level.to_string())?;
+        db.put(&mut wtxn, &other, &Priority(level), &level.to_string())?;
+    }
+    assert_eq!(db.clear(&mut wtxn, &tenant)?, 3);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for level in [1, 128, 200] {
+        assert_eq!(db.get(&rtxn, &tenant, &Priority(level))?, None);
+        assert_eq!(
+            db.get(&rtxn, &other, &Priority(level))?,
+            Some(level.to_string())
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_require_registered_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("require_registered")?;
+    let env = &test_env.env;
+
+    let provisioned = Scope::named("provisioned")?;
+    let typo = Scope::named("provisoned")?;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("strict")
+        .register_scopes(std::slice::from_ref(&provisioned))
+        .require_registered_scopes(true)
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("strict_raw")
+        .unified()
+        .require_registered_scopes(true)
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let key = "k".to_string();
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &provisioned, &key, &"v".to_string())?;
+    db.put(&mut wtxn, &Scope::Default, &key, &"v".to_string())?;
+    raw.put(&mut wtxn, &provisioned, b"k", b"v")?;
+    raw.put(&mut wtxn, &Scope::Default, b"k", b"v")?;
+
+    assert!(matches!(
+        db.put(&mut wtxn, &typo, &key, &"v".to_string()),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        raw.put(&mut wtxn, &typo, b"k", b"v"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(!registry.scope_exists(&wtxn, &typo)?);
+
+    // Reads of unregistered scopes are unaffected
+    assert_eq!(db.get(&wtxn, &typo, &key)?, None);
+
+    // Explicit registration enables writes
+    db.register_scope(&mut wtxn, &typo)?;
+    raw.put(&mut wtxn, &typo, b"k", b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get(&rtxn, &provisioned, &key)?, Some("v".to_string()));
+    assert_eq!(raw.get(&rtxn, &typo, b"k")?, Some(&b"v"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn test_move_key_between_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("move_key")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("accounts")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw_accounts")
+        .unified()
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let acme = Scope::named("acme")?;
+    let globex = Scope::named("globex")?;
+    let key = "account-42".to_string();
+
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &acme, &key, &"balance".to_string())?;
+    raw.put(&mut wtxn, &acme, b"account-42", b"balance")?;
+    assert!(!registry.scope_exists(&wtxn, &globex)?);
+
+    assert!(db.move_key(&mut wtxn, &acme, &globex, &key)?);
+    assert!(raw.move_key(&mut wtxn, &acme, &globex, b"account-42")?);
+    assert!(registry.scope_exists(&wtxn, &globex)?);
+    assert_eq!(db.get(&wtxn, &acme, &key)?, None);
+    assert_eq!(db.get(&wtxn, &globex, &key)?, Some("balance".to_string()));
+    assert_eq!(raw.get(&wtxn, &acme, b"account-42")?, None);
+    assert_eq!(
+        raw.get(&wtxn, &globex, b"account-42")?,
+        Some(&b"balance"[..])
+    );
+
+    // Missing keys are reported and nothing is written
+    assert!(!db.move_key(&mut wtxn, &acme, &globex, &key)?);
+    assert!(!raw.move_key(&mut wtxn, &acme, &Scope::Default, b"account-42")?);
+    assert_eq!(raw.get(&wtxn, &Scope::Default, b"account-42")?, None);
+
+    // Moving onto itself keeps the entry
+    assert!(db.move_key(&mut wtxn, &globex, &globex, &key)?);
+    assert!(raw.move_key(&mut wtxn, &globex, &globex, b"account-42")?);
+    assert_eq!(db.get(&wtxn, &globex, &key)?, Some("balance".to_string()));
+    assert_eq!(
+        raw.get(&wtxn, &globex, b"account-42")?,
+        Some(&b"balance"[..])
+    );
+
+    // Renaming the key while moving to the default scope
+    let renamed = "account-7".to_string();
+    assert!(db.move_key_as(&mut wtxn, &globex, &key, &Scope::Default, &renamed)?);
+    assert!(raw.move_key_as(
+        &mut wtxn,
+        &globex,
+        b"account-42",
+        &Scope::Default,
+        b"account-7"
+    )?);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get(&rtxn, &globex, &key)?, None);
+    assert_eq!(
+        db.get(&rtxn, &Scope::Default, &renamed)?,
+        Some("balance".to_string())
+    );
+    assert_eq!(
+        raw.get(&rtxn, &Scope::Default, b"account-7")?,
+        Some(&b"balance"[..])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_bytes_database() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("multi_bytes")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let followers = scoped_database_options(env, registry.clone())
+        .multi_bytes()
+        .name("followers")
+        .create(&mut wtxn)?;
+    let unified = scoped_database_options(env, registry.clone())
+        .multi_bytes()
+        .name("tags")
+        .unified()
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+
+    let mut wtxn = env.write_txn()?;
+    for db in [&followers, &unified] {
+        db.put(&mut wtxn, &tenant1, b"alice", b"carol")?;
+        db.put(&mut wtxn, &tenant1, b"alice", b"bob")?;
+        // Adding a value twice keeps a single copy
+        db.put(&mut wtxn, &tenant1, b"alice", b"bob")?;
+        db.put(&mut wtxn, &tenant2, b"alice", b"dave")?;
+        db.put(&mut wtxn, &Scope::Default, b"alice", b"erin")?;
+
+        // Values share LMDB's key size limit
+        let oversized = vec![0u8; env.max_key_size() + 1];
+        assert!(matches!(
+            db.put(&mut wtxn, &tenant1, b"alice", &oversized),
+            Err(ScopedDbError::InvalidInput(_))
+        ));
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for db in [&followers, &unified] {
+        let values: Vec<&[u8]> = db
+            .get_all(&rtxn, &tenant1, b"alice")?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![&b"bob"[..], &b"carol"[..]]);
+
+        let values: Vec<&[u8]> = db
+            .get_all(&rtxn, &tenant2, b"alice")?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![&b"dave"[..]]);
+
+        let values: Vec<&[u8]> = db
+            .get_all(&rtxn, &Scope::Default, b"alice")?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![&b"erin"[..]]);
+
+        assert_eq!(db.get_all(&rtxn, &tenant1, b"missing")?.count(), 0);
+        assert!(db.contains(&rtxn, &tenant1, b"alice", b"carol")?);
+        assert!(db.contains(&rtxn, &tenant1, b"alice", b"bob")?);
+        assert!(db.contains(&rtxn, &Scope::Default, b"alice", b"erin")?);
+        // Before, between and after the stored values
+        assert!(!db.contains(&rtxn, &tenant1, b"alice", b"amy")?);
+        assert!(!db.contains(&rtxn, &tenant1, b"alice", b"bobby")?);
+        assert!(!db.contains(&rtxn, &tenant1, b"alice", b"zed")?);
+        assert!(!db.contains(&rtxn, &tenant2, b"alice", b"carol")?);
+        assert!(!db.contains(&rtxn, &tenant1, b"missing", b"carol")?);
+
+        // Iteration yields each key once per value
+        assert_eq!(db.iter(&rtxn, &tenant1)?.count(), 2);
+        assert_eq!(db.scope_hashes_in_db(&rtxn)?.len(), 2);
+    }
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    for db in [&followers, &unified] {
+        assert!(db.delete_one(&mut wtxn, &tenant1, b"alice", b"bob")?);
+        assert!(!db.delete_one(&mut wtxn, &tenant1, b"alice", b"bob")?);
+        let values: Vec<&[u8]> = db
+            .get_all(&wtxn, &tenant1, b"alice")?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(values, vec![&b"carol"[..]]);
+
+        assert!(db.delete(&mut wtxn, &tenant2, b"alice")?);
+        assert!(db.is_scope_empty_in_db(&wtxn, &tenant2)?);
+
+        db.put(&mut wtxn, &tenant1, b"bob", b"alice")?;
+        assert_eq!(db.clear(&mut wtxn, &tenant1)?, 2);
+        assert!(db.is_scope_empty_in_db(&wtxn, &tenant1)?);
+        assert!(!db.is_scope_empty_in_db(&wtxn, &Scope::Default)?);
+    }
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_has_data() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("has_data")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("typed")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+
+    let mut wtxn = env.write_txn()?;
+    db.register_scope(&mut wtxn, &tenant)?;
+    // Registered but empty: the registry knows the scope, the data does not
+    assert!(registry.scope_exists(&wtxn, &tenant)?);
+    assert!(!db.has_data(&wtxn, &tenant)?);
+    assert!(!raw.has_data(&wtxn, &Scope::Default)?);
+
+    db.put(&mut wtxn, &tenant, &"k".to_string(), &"v".to_string())?;
+    raw.put(&mut wtxn, &Scope::Default, b"k", b"v")?;
+    assert!(db.has_data(&wtxn, &tenant)?);
+    assert!(!db.has_data(&wtxn, &Scope::Default)?);
+    assert!(raw.has_data(&wtxn, &Scope::Default)?);
+    assert!(!raw.has_data(&wtxn, &tenant)?);
+
+    db.clear(&mut wtxn, &tenant)?;
+    assert!(!db.has_data(&wtxn, &tenant)?);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_backfill_registry_on_open() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("backfill_registry")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("legacy")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let hash = tenant.hash().unwrap();
+    db.put(&mut wtxn, &tenant, b"k", b"v")?;
+    // Simulate data written before the registry existed
+    registry.unregister_scope(&mut wtxn, &hash)?;
+    wtxn.commit()?;
+
+    // Reopening without the flag leaves the registry untouched
+    let mut wtxn = env.write_txn()?;
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("legacy")
+        .create(&mut wtxn)?;
+    assert_eq!(db.list_scopes(&wtxn)?, vec![Scope::Default]);
+
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("legacy")
+        .backfill_registry_on_open(true)
+        .create(&mut wtxn)?;
+    let scopes = db.list_scopes(&wtxn)?;
+    assert_eq!(scopes.len(), 2);
+    assert_eq!(scopes[1].hash(), Some(hash));
+    assert_eq!(scopes[1].name(), Some(format!("{hash:#010x}").as_str()));
+
+    // Writing with the real name replaces the synthetic one
+    db.put(&mut wtxn, &tenant, b"k2", b"v2")?;
+    assert_eq!(db.list_scopes(&wtxn)?, vec![Scope::Default, tenant.clone()]);
+    assert_eq!(db.get(&wtxn, &tenant, b"k")?, Some(&b"v"[..]));
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_range_limited_pagination() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("range_limited")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<u8, u32>()
+        .name("numbers")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw_numbers")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    for i in 0..10u8 {
+        db.put(&mut wtxn, &tenant, &i, &(i as u32 * 10))?;
+        db.put(&mut wtxn, &other, &i, &0)?;
+        raw.put(&mut wtxn, &tenant, &[b'a' + i], b"v")?;
+        raw.put(&mut wtxn, &other, &[b'a' + i], b"v")?;
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let page: Vec<(u8, u32)> = db
+        .range_limited(&rtxn, &tenant, &(2..), 3, 4)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(page, vec![(5, 50), (6, 60), (7, 70), (8, 80)]);
+
+    // A page running past the end of the scope stops at the scope boundary
+    let page: Vec<(u8, u32)> = db
+        .range_limited(&rtxn, &tenant, &(0..), 8, 5)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(page, vec![(8, 80), (9, 90)]);
+    assert_eq!(db.range_limited(&rtxn, &tenant, &(0..), 0, 0)?.count(), 0);
+
+    let keys: Vec<Vec<u8>> = raw
+        .range_limited(&rtxn, &tenant, &(&b"c"[..]..), 0, 3)?
+        .map(|entry| entry.map(|(key, _)| key.to_vec()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![b"c".to_vec(), b"d".to_vec(), b"e".to_vec()]);
+    assert_eq!(raw.range_limited(&rtxn, &tenant, &(..), 8, 10)?.count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_expect_reports_not_found() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("get_expect")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("typed")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, &"k".to_string(), &"v".to_string())?;
+    raw.put(&mut wtxn, &tenant, b"k", b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get_expect(&rtxn, &tenant, &"k".to_string())?, "v");
+    assert_eq!(raw.get_expect(&rtxn, &tenant, b"k")?, b"v");
+    assert!(matches!(
+        db.get_expect(&rtxn, &Scope::Default, &"k".to_string()),
+        Err(ScopedDbError::NotFound)
+    ));
+    assert!(matches!(
+        raw.get_expect(&rtxn, &tenant, b"missing"),
+        Err(ScopedDbError::NotFound)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_u64_values_counters() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("u64_values")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let counters = scoped_database_options(env, registry.clone())
+        .u64_values()
+        .name("counters")
+        .initial_data(Scope::Default, vec![(b"seeded".to_vec(), 40)])
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    assert_eq!(counters.increment(&mut wtxn, &tenant, b"views", 1)?, 1);
+    assert_eq!(counters.increment(&mut wtxn, &tenant, b"views", 5)?, 6);
+    assert_eq!(
+        counters.increment(&mut wtxn, &Scope::Default, b"seeded", 2)?,
+        42
+    );
+    assert_eq!(counters.get(&wtxn, &Scope::Default, b"views")?, None);
+
+    // Values are stored as 8 native-endian bytes
+    assert_eq!(
+        counters.inner().get(&wtxn, &tenant, b"views")?,
+        Some(&6u64.to_ne_bytes()[..])
+    );
+
+    counters.put(&mut wtxn, &tenant, b"max", u64::MAX)?;
+    assert!(matches!(
+        counters.increment(&mut wtxn, &tenant, b"max", 1),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert_eq!(counters.get_expect(&wtxn, &tenant, b"max")?, u64::MAX);
+
+    // Values of the wrong width are reported instead of misread
+    counters.inner().put(&mut wtxn, &tenant, b"bad", b"abc")?;
+    assert!(matches!(
+        counters.get(&wtxn, &tenant, b"bad"),
+        Err(ScopedDbError::Encoding(_))
+    ));
+    counters.delete(&mut wtxn, &tenant, b"bad")?;
+
+    let entries: Vec<(&[u8], u64)> = counters.iter(&wtxn, &tenant)?.collect::<Result<_, _>>()?;
+    assert_eq!(entries, vec![(&b"max"[..], u64::MAX), (&b"views"[..], 6)]);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_i64_values_increment() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("i64_values")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let balances = scoped_database_options(env, registry.clone())
+        .i64_values()
+        .name("balances")
+        .unified()
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    assert_eq!(balances.increment(&mut wtxn, &tenant, b"alice", 10)?, 10);
+    assert_eq!(balances.increment(&mut wtxn, &tenant, b"alice", -25)?, -15);
+    assert_eq!(
+        balances.increment(&mut wtxn, &Scope::Default, b"alice", -1)?,
+        -1
+    );
+    assert!(registry.scope_exists(&wtxn, &tenant)?);
+
+    balances.put(&mut wtxn, &tenant, b"min", i64::MIN)?;
+    assert!(matches!(
+        balances.increment(&mut wtxn, &tenant, b"min", -1),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert_eq!(balances.get(&wtxn, &tenant, b"min")?, Some(i64::MIN));
+    assert_eq!(
+        balances.increment(&mut wtxn, &tenant, b"min", i64::MAX)?,
+        -1
+    );
+
+    let entries: Vec<(&[u8], i64)> = balances.iter(&wtxn, &tenant)?.collect::<Result<_, _>>()?;
+    // Named-scope keys are ordered by length first
+    assert_eq!(entries, vec![(&b"min"[..], -1), (&b"alice"[..], -15)]);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_oversized_keys_are_rejected() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("oversized_keys")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("typed")
+        .create(&mut wtxn)?;
+
+    use scoped_heed::ScopedBytesCodec;
+
+    let tenant = Scope::named("tenant")?;
+    let limit = env.max_key_size();
+    assert_eq!(raw.max_key_len(&Scope::Default), limit);
+    assert_eq!(
+        raw.max_key_len(&tenant),
+        limit - ScopedBytesCodec::HEADER_LEN
+    );
+    assert_eq!(db.max_key_len(&tenant), limit - 4);
+    assert_eq!(raw.usable_key_bytes(&tenant), raw.max_key_len(&tenant));
+    assert_eq!(raw.usable_key_bytes(&Scope::Default), limit);
+
+    // The longest allowed key still fits, one more byte is rejected before LMDB sees it
+    let longest = vec![b'k'; raw.max_key_len(&tenant)];
+    raw.put(&mut wtxn, &tenant, &longest, b"v")?;
+    let too_long = vec![b'k'; raw.max_key_len(&tenant) + 1];
+    assert!(matches!(
+        raw.put(&mut wtxn, &tenant, &too_long, b"v"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    raw.put(&mut wtxn, &Scope::Default, &too_long, b"v")?;
+
+    // String keys carry an 8-byte bincode length prefix
+    let key = "k".repeat(db.max_key_len(&tenant) - 8 + 1);
+    assert!(matches!(
+        db.put(&mut wtxn, &tenant, &key, &"v".to_string()),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    db.put(&mut wtxn, &tenant, &key[1..].to_string(), &"v".to_string())?;
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_from_resumes_after_key() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("iter_from")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("typed")
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+        for scope in [&tenant, &other, &Scope::Default] {
+            db.put(&mut wtxn, scope, &key.to_string(), &(i as u32))?;
+            raw.put(&mut wtxn, scope, key.as_bytes(), b"v")?;
+        }
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let all: Vec<String> = db
+        .iter(&rtxn, &tenant)?
+        .map(|result| result.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+
+    // Paging through the scope two entries at a time visits every key once
+    let mut paged = Vec::new();
+    let mut page: Vec<String> = db
+        .iter(&rtxn, &tenant)?
+        .take(2)
+        .map(|result| result.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+    while let Some(last) = page.last().cloned() {
+        paged.append(&mut page);
+        page = db
+            .iter_from(&rtxn, &tenant, &last, false)?
+            .take(2)
+            .map(|result| result.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+    }
+    assert_eq!(paged, all);
+
+    let inclusive: Vec<(String, u32)> = db
+        .iter_from(&rtxn, &Scope::Default, &"c".to_string(), true)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(inclusive, vec![("c".to_string(), 2), ("d".to_string(), 3)]);
+
+    // The seek stays inside the scope
+    let keys: Vec<&[u8]> = raw
+        .iter_from(&rtxn, &tenant, b"b", false)?
+        .map(|result| result.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec![&b"c"[..], &b"d"[..]]);
+    assert_eq!(raw.iter_from(&rtxn, &other, b"d", false)?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_heed_flags_on_default_database() -> Result<(), ScopedDbError> {
+    use heed::DatabaseFlags;
+
+    let test_env = TestEnv::new("heed_flags")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("ids")
+        .heed_flags(DatabaseFlags::INTEGER_KEY)
+        .create(&mut wtxn)?;
+
+    // Default-scope keys compare as native integers, named scopes are unaffected
+    let tenant = Scope::named("tenant")?;
+    for id in [256u32, 1, 2] {
+        db.put(&mut wtxn, &Scope::Default, &id.to_ne_bytes(), b"v")?;
+        db.put(&mut wtxn, &tenant, &id.to_ne_bytes(), b"v")?;
+    }
+    let ids: Vec<u32> = db
+        .iter(&wtxn, &Scope::Default)?
+        .map(|result| result.map(|(key, _)| u32::from_ne_bytes(key.try_into().unwrap())))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(ids, vec![1, 2, 256]);
+    assert_eq!(db.iter(&wtxn, &tenant)?.count(), 3);
+
+    // Prefix scans assume lexicographic order, so they are refused on the default scope
+    let prefix = &1u32.to_ne_bytes()[..1];
+    assert!(matches!(
+        db.prefix_range(&wtxn, &Scope::Default, prefix, &(..)),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        db.delete_prefix(&mut wtxn, &Scope::Default, prefix),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert_eq!(db.iter(&wtxn, &Scope::Default)?.count(), 3);
+    assert_eq!(db.prefix_range(&wtxn, &tenant, prefix, &(..))?.count(), 1);
+
+    let reversed = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("reversed")
+        .heed_flags(DatabaseFlags::REVERSE_KEY)
+        .create(&mut wtxn)?;
+    reversed.put(&mut wtxn, &Scope::Default, b"ab", &1)?;
+    assert!(matches!(
+        reversed.delete_prefix(&mut wtxn, &Scope::Default, b"a"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert_eq!(reversed.delete_prefix(&mut wtxn, &tenant, b"a")?, 0);
+
+    let unified = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("unified_ids")
+        .unified()
+        .heed_flags(DatabaseFlags::INTEGER_KEY)
+        .create(&mut wtxn);
+    assert!(matches!(unified, Err(ScopedDbError::InvalidInput(_))));
+    let dup_sort = scoped_database_options(env, registry.clone())
+        .bytes_keys::<String>()
+        .name("dup_ids")
+        .heed_flags(DatabaseFlags::DUP_SORT)
+        .create(&mut wtxn);
+    assert!(matches!(dup_sort, Err(ScopedDbError::InvalidInput(_))));
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_contains_many() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("contains_many")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("perms")
+        .unified()
+        .create(&mut wtxn)?;
+    let raw = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, &"read".to_string(), &"yes".to_string())?;
+    db.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"write".to_string(),
+        &"yes".to_string(),
+    )?;
+    raw.put(&mut wtxn, &tenant, b"a", b"1")?;
+    raw.put(&mut wtxn, &Scope::Default, b"b", b"2")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let keys = ["read".to_string(), "write".to_string(), "read".to_string()];
+    assert_eq!(
+        db.contains_many(&rtxn, &tenant, &keys)?,
+        vec![true, false, true]
+    );
+    assert_eq!(
+        db.contains_many(&rtxn, &Scope::Default, &keys)?,
+        vec![false, true, false]
+    );
+    assert!(db.contains_many(&rtxn, &tenant, &[])?.is_empty());
+
+    let raw_keys: [&[u8]; 2] = [b"a", b"b"];
+    assert_eq!(
+        raw.contains_many(&rtxn, &tenant, &raw_keys)?,
+        vec![true, false]
+    );
+    assert_eq!(
+        raw.contains_many(&rtxn, &Scope::Default, &raw_keys)?,
+        vec![false, true]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_range_rewrites_and_deletes_in_place() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("update_range")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<char, u32>()
+        .name("values")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    for (i, key) in ('a'..='e').enumerate() {
+        for scope in [&tenant, &other, &Scope::Default] {
+            db.put(&mut wtxn, scope, &key, &(i as u32))?;
+        }
+    }
+
+    // Double even values and drop odd ones within b..=d
+    let touched = db.update_range(&mut wtxn, &tenant, &('b'..='d'), |_, value| {
+        (value % 2 == 0).then_some(value * 2)
+    })?;
+    assert_eq!(touched, 3);
+    let entries: Vec<(char, u32)> = db.iter(&wtxn, &tenant)?.collect::<Result<_, _>>()?;
+    assert_eq!(entries, vec![('a', 0), ('c', 4), ('e', 4)]);
+    assert_eq!(db.iter(&wtxn, &other)?.count(), 5);
+    assert_eq!(db.get(&wtxn, &Scope::Default, &'c')?, Some(2));
+
+    let touched = db.update_range(&mut wtxn, &Scope::Default, &(..), |key, value| {
+        (*key != 'a').then_some(value + 10)
+    })?;
+    assert_eq!(touched, 5);
+    let entries: Vec<(char, u32)> = db.iter(&wtxn, &Scope::Default)?.collect::<Result<_, _>>()?;
+    assert_eq!(entries, vec![('b', 11), ('c', 12), ('d', 13), ('e', 14)]);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_with_child_txn_commits_or_rolls_back() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("child_txn")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("child_txn")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, &"parent".to_string(), &1)?;
+
+    let value = with_child_txn(env, &mut wtxn, |child| {
+        db.put(child, &tenant, &"kept".to_string(), &2)?;
+        db.get(child, &tenant, &"parent".to_string())
+    })?;
+    assert_eq!(value, Some(1));
+
+    let result: Result<(), ScopedDbError> = with_child_txn(env, &mut wtxn, |child| {
+        db.put(child, &tenant, &"dropped".to_string(), &3)?;
+        db.delete(child, &tenant, &"parent".to_string())?;
+        Err(ScopedDbError::InvalidInput("rejected".into()))
+    });
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let keys: Vec<String> = db
+        .iter(&rtxn, &tenant)?
+        .map(|entry| entry.map(|(key, _)| key))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec!["kept".to_string(), "parent".to_string()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_extend_from_copies_across_environments() -> Result<(), ScopedDbError> {
+    let src_env = TestEnv::new("extend_from_src")?;
+    let dst_env = TestEnv::new("extend_from_dst")?;
+
+    let mut src_wtxn = src_env.env.write_txn()?;
+    let src_registry = Arc::new(GlobalScopeRegistry::new(&src_env.env, &mut src_wtxn)?);
+    let src_db = scoped_database_options(&src_env.env, src_registry.clone())
+        .types::<String, u32>()
+        .name("shard")
+        .create(&mut src_wtxn)?;
+    let src_bytes = scoped_database_options(&src_env.env, src_registry)
+        .raw_bytes()
+        .name("shard_bytes")
+        .create(&mut src_wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    for i in 0..3u32 {
+        src_db.put(&mut src_wtxn, &tenant, &format!("key{}", i), &i)?;
+        src_bytes.put(&mut src_wtxn, &Scope::Default, &[i as u8], b"value")?;
+    }
+    src_db.put(&mut src_wtxn, &Scope::Default, &"other".to_string(), &99)?;
+    src_wtxn.commit()?;
+
+    let mut dst_wtxn = dst_env.env.write_txn()?;
+    let dst_registry = Arc::new(GlobalScopeRegistry::new(&dst_env.env, &mut dst_wtxn)?);
+    let dst_db = scoped_database_options(&dst_env.env, dst_registry.clone())
+        .types::<String, u32>()
+        .name("shard")
+        .create(&mut dst_wtxn)?;
+    let dst_bytes = scoped_database_options(&dst_env.env, dst_registry.clone())
+        .raw_bytes()
+        .name("shard_bytes")
+        .create(&mut dst_wtxn)?;
+    let moved = Scope::named("moved")?;
+    let empty = Scope::named("empty")?;
+
+    let src_rtxn = src_env.env.read_txn()?;
+    assert_eq!(
+        dst_db.extend_from(&mut dst_wtxn, &moved, &src_db, &src_rtxn, &tenant)?,
+        3
+    );
+    assert_eq!(
+        dst_db.extend_from(&mut dst_wtxn, &empty, &src_db, &src_rtxn, &empty)?,
+        0
+    );
+    assert_eq!(
+        dst_bytes.extend_from(
+            &mut dst_wtxn,
+            &tenant,
+            &src_bytes,
+            &src_rtxn,
+            &Scope::Default
+        )?,
+        3
+    );
+
+    let entries: Vec<(String, u32)> = dst_db.iter(&dst_wtxn, &moved)?.collect::<Result<_, _>>()?;
+    assert_eq!(
+        entries,
+        vec![
+            ("key0".to_string(), 0),
+            ("key1".to_string(), 1),
+            ("key2".to_string(), 2)
+        ]
+    );
+    assert_eq!(dst_db.iter(&dst_wtxn, &Scope::Default)?.count(), 0);
+    assert_eq!(
+        dst_bytes.get(&dst_wtxn, &tenant, &[2])?,
+        Some(&b"value"[..])
+    );
+    let scopes = dst_registry.list_all_scopes(&dst_wtxn)?;
+    assert!(scopes.contains(&moved));
+    assert!(scopes.contains(&empty));
+    dst_wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_prefix_range_matches_filtered_scan() -> Result<(), ScopedDbError> {
+    use std::ops::{Bound, RangeBounds};
+
+    let test_env = TestEnv::new("prefix_range")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let separate = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("prefix_range")
+        .create(&mut wtxn)?;
+    let unified = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .unified()
+        .name("prefix_range_unified")
+        .create(&mut wtxn)?;
+    let typed = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("prefix_range_typed")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+    let keys: Vec<&[u8]> = vec![
+        b"user:",
+        b"user:a",
+        b"user:b",
+        b"user:c",
+        b"user:aa",
+        b"user:bz",
+        b"user:cc",
+        b"user:b\xff\xff",
+        b"usea",
+        b"usez",
+        b"users:a",
+        b"a",
+        b"zzzzzzz",
+        b"\xff\xff",
+        b"\xff\xff\x00",
+        b"\xff\xff\xff",
+    ];
+    for (i, key) in keys.iter().enumerate() {
+        for scope in [&tenant, &Scope::Default] {
+            separate.put(&mut wtxn, scope, key, b"v")?;
+            unified.put(&mut wtxn, scope, key, b"v")?;
+            typed.put(&mut wtxn, scope, key, &(i as u32))?;
+        }
+    }
+    separate.put(&mut wtxn, &other, b"user:b", b"v")?;
+    wtxn.commit()?;
+
+    type SuffixRange<'a> = (Bound<&'a [u8]>, Bound<&'a [u8]>);
+    let cases: Vec<(&[u8], SuffixRange)> = vec![
+        (b"user:", (Bound::Unbounded, Bound::Unbounded)),
+        (b"user:", (Bound::Included(b"b"), Bound::Unbounded)),
+        (b"user:", (Bound::Excluded(b"b"), Bound::Excluded(b"c"))),
+        (b"user:", (Bound::Included(b"a"), Bound::Included(b"b"))),
+        (b"user:", (Bound::Unbounded, Bound::Excluded(b"b"))),
+        (b"", (Bound::Included(b"user"), Bound::Excluded(b"z"))),
+        (b"\xff\xff", (Bound::Unbounded, Bound::Unbounded)),
+        (b"\xff\xff", (Bound::Excluded(b""), Bound::Unbounded)),
+        (b"nothing", (Bound::Unbounded, Bound::Unbounded)),
+    ];
+
+    let rtxn = env.read_txn()?;
+    for (prefix, suffix_range) in &cases {
+        let matches = |key: &[u8]| {
+            key.strip_prefix(*prefix)
+                .is_some_and(|suffix| suffix_range.contains(&suffix))
+        };
+        for scope in [&tenant, &Scope::Default] {
+            for db in [&separate, &unified] {
+                let expected: Vec<Vec<u8>> = db
+                    .iter(&rtxn, scope)?
+                    .map(|entry| entry.map(|(key, _)| key.to_vec()))
+                    .filter(|key| key.as_ref().map_or(true, |key| matches(key)))
+                    .collect::<Result<_, _>>()?;
+                let found: Vec<Vec<u8>> = db
+                    .prefix_range(&rtxn, scope, prefix, suffix_range)?
+                    .map(|entry| entry.map(|(key, _)| key.to_vec()))
+                    .collect::<Result<_, _>>()?;
+                // Storage order is preserved
+                assert_eq!(found, expected, "{prefix:?} {suffix_range:?} in {scope:?}");
+            }
+
+            let found: Vec<(Vec<u8>, u32)> = typed
+                .prefix_range(&rtxn, scope, prefix, suffix_range)?
+                .map(|entry| entry.map(|(key, value)| (key.to_vec(), value)))
+                .collect::<Result<_, _>>()?;
+            for (key, value) in &found {
+                assert!(matches(key));
+                assert_eq!(keys[*value as usize], key.as_slice());
+            }
+            let expected = keys.iter().filter(|key| matches(key)).count();
+            assert_eq!(found.len(), expected);
+        }
+    }
+
+    // Other scopes are never visited
+    let found: Vec<_> = separate
+        .prefix_range(
+            &rtxn,
+            &other,
+            b"user:",
+            &(Bound::<&[u8]>::Unbounded, Bound::Unbounded),
+        )?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(found, vec![(&b"user:b"[..], &b"v"[..])]);
+
+    Ok(())
+}
+
+#[test]
+fn test_codec_failures_are_encoding_errors() -> Result<(), ScopedDbError> {
+    /// A value whose serialization always fails
+    #[derive(Debug, serde::Deserialize)]
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("refusing to serialize"))
+        }
+    }
+
+    let test_env = TestEnv::new("codec_failures")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let broken = scoped_database_options(env, registry.clone())
+        .types::<String, Unserializable>()
+        .name("codec")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    for scope in [&tenant, &Scope::Default] {
+        let err = broken
+            .put(&mut wtxn, scope, &"key".to_string(), &Unserializable)
+            .unwrap_err();
+        assert!(
+            matches!(&err, ScopedDbError::Encoding(msg) if msg.contains("refusing to serialize")),
+            "{err:?}"
+        );
+    }
+
+    // Bytes that do not decode as the value type are reported the same way
+    let numbers = scoped_database_options(env, registry.clone())
+        .types::<String, u8>()
+        .name("numbers")
+        .create(&mut wtxn)?;
+    numbers.put(&mut wtxn, &tenant, &"key".to_string(), &7)?;
+    let strings = scoped_database_options(env, registry)
+        .types::<String, String>()
+        .name("numbers")
+        .create(&mut wtxn)?;
+    assert!(matches!(
+        strings.get(&wtxn, &tenant, &"key".to_string()),
+        Err(ScopedDbError::Encoding(_))
+    ));
+
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_local_sequences() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("sequences")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("sequences")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let other = Scope::named("other")?;
+
+    assert_eq!(db.next_id(&mut wtxn, &tenant, b"seq:orders")?, 1);
+    assert_eq!(db.next_id(&mut wtxn, &tenant, b"seq:orders")?, 2);
+    assert_eq!(db.reserve_ids(&mut wtxn, &tenant, b"seq:orders", 10)?, 3);
+    assert_eq!(db.next_id(&mut wtxn, &tenant, b"seq:orders")?, 13);
+
+    // Sequences are independent per scope and per key
+    assert_eq!(db.next_id(&mut wtxn, &other, b"seq:orders")?, 1);
+    assert_eq!(db.next_id(&mut wtxn, &tenant, b"seq:invoices")?, 1);
+    assert_eq!(db.next_id(&mut wtxn, &Scope::Default, b"seq:orders")?, 1);
+
+    assert!(matches!(
+        db.reserve_ids(&mut wtxn, &tenant, b"seq:orders", 0),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+
+    // The counter is readable as a u64 counter value
+    let counters = scoped_heed::ScopedU64Database::new(db.clone());
+    assert_eq!(counters.get(&wtxn, &tenant, b"seq:orders")?, Some(13));
+    counters.put(&mut wtxn, &tenant, b"seq:full", u64::MAX - 1)?;
+    assert!(matches!(
+        db.reserve_ids(&mut wtxn, &tenant, b"seq:full", 2),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert_eq!(db.next_id(&mut wtxn, &tenant, b"seq:full")?, u64::MAX);
+
+    db.put(&mut wtxn, &tenant, b"seq:bad", b"abc")?;
+    assert!(matches!(
+        db.next_id(&mut wtxn, &tenant, b"seq:bad"),
+        Err(ScopedDbError::Encoding(_))
+    ));
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_scopes_by_size() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("scopes_by_size")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<u32, u32>()
+        .name("sizes")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("sizes_bytes")
+        .create(&mut wtxn)?;
+
+    let small = Scope::named("small")?;
+    let large = Scope::named("large")?;
+    let medium_a = Scope::named("medium_a")?;
+    let medium_b = Scope::named("medium_b")?;
+    for (scope, count) in [(&small, 1u32), (&large, 5), (&medium_a, 3), (&medium_b, 3)] {
+        for i in 0..count {
+            db.put(&mut wtxn, scope, &i, &i)?;
+        }
+    }
+    db.put(&mut wtxn, &Scope::Default, &0, &0)?;
+    db.put(&mut wtxn, &Scope::Default, &1, &0)?;
+    bytes_db.put(&mut wtxn, &small, b"k", b"v")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let (medium_first, medium_second) = if medium_a < medium_b {
+        (medium_a, medium_b)
+    } else {
+        (medium_b, medium_a)
+    };
+    let descending = db.scopes_by_size(&rtxn, true)?;
+    assert_eq!(
+        descending,
+        vec![
+            (large.clone(), 5),
+            (medium_first.clone(), 3),
+            (medium_second.clone(), 3),
+            (Scope::Default, 2),
+            (small.clone(), 1),
+        ]
+    );
+    let ascending = db.scopes_by_size(&rtxn, false)?;
+    assert_eq!(
+        ascending,
+        vec![
+            (small.clone(), 1),
+            (Scope::Default, 2),
+            (medium_first, 3),
+            (medium_second, 3),
+            (large, 5),
+        ]
+    );
+
+    // The default scope is reported even when it is empty
+    assert_eq!(
+        bytes_db.scopes_by_size(&rtxn, true)?,
+        vec![(small, 1), (Scope::Default, 0)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_scope_hashes_rejects_inconsistent_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("verify_scope_hashes")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let strict = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("strict")
+        .verify_scope_hashes(true)
+        .create(&mut wtxn)?;
+    let strict_bytes = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("strict_bytes")
+        .verify_scope_hashes(true)
+        .create(&mut wtxn)?;
+    let strict_ordered = scoped_database_options(env, registry.clone())
+        .types::<u64, u32>()
+        .ordered_keys()
+        .name("strict_ordered")
+        .verify_scope_hashes(true)
+        .create(&mut wtxn)?;
+    let lenient = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("lenient")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let forged = Scope::Named {
+        name: "tenant".to_string(),
+        hash: 42,
+    };
+    let key = "key".to_string();
+
+    strict.put(&mut wtxn, &tenant, &key, &1)?;
+    strict.put(&mut wtxn, &Scope::Default, &key, &1)?;
+    assert!(matches!(
+        strict.put(&mut wtxn, &forged, &key, &2),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        strict.get(&wtxn, &forged, &key),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        strict.delete(&mut wtxn, &forged, &key),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        strict.swap_scopes(&mut wtxn, &tenant, &forged),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        strict_bytes.put(&mut wtxn, &forged, b"key", b"value"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        strict_ordered.put(&mut wtxn, &forged, &1, &1),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    strict_ordered.put(&mut wtxn, &tenant, &1, &1)?;
+    assert_eq!(strict.get(&wtxn, &tenant, &key)?, Some(1));
+
+    // Without the flag the forged scope is used as given
+    lenient.put(&mut wtxn, &forged, &key, &2)?;
+    assert_eq!(lenient.get(&wtxn, &forged, &key)?, Some(2));
+    assert_eq!(lenient.get(&wtxn, &tenant, &key)?, None);
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_read_views() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("read_views")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<char, String>()
+        .name("read_views")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("read_views_bytes")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant1")?;
+    for key in ['a', 'b', 'c'] {
+        db.put(&mut wtxn, &tenant, &key, &format!("tenant_{key}"))?;
+    }
+    db.put(&mut wtxn, &Scope::Default, &'a', &"default_a".to_string())?;
+    bytes_db.put(&mut wtxn, &tenant, b"k", b"v")?;
+
+    // Views over a write transaction see its uncommitted writes
+    assert_eq!(db.read_view(&wtxn, tenant.clone()).iter()?.count(), 3);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let view = db.read_view(&rtxn, tenant.clone());
+    assert_eq!(view.scope(), &tenant);
+    assert_eq!(view.get(&'a')?, Some("tenant_a".to_string()));
+    assert_eq!(view.get(&'z')?, None);
+    let keys: Vec<char> = view
+        .range(&('b'..='c'))?
+        .map(|r| r.map(|(k, _)| k))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keys, vec!['b', 'c']);
+    assert_eq!(
+        db.read_view(&rtxn, Scope::Default).get(&'a')?,
+        Some("default_a".to_string())
+    );
+
+    // Borrowed values outlive the view
+    let value = {
+        let raw = bytes_db.read_view(&rtxn, tenant.clone());
+        raw.get(b"k")?
+    };
+    assert_eq!(value, Some(&b"v"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn test_deny_default_scope() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("deny_default_scope")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let tenant_only = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("tenant_only")
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+    let tenant_only_bytes = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("tenant_only_bytes")
+        .unified()
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+    let tenant_only_counters = scoped_database_options(env, registry.clone())
+        .u64_values()
+        .name("tenant_only_counters")
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+
+    let tenant_only_ordered = scoped_database_options(env, registry.clone())
+        .types::<u64, u32>()
+        .ordered_keys()
+        .name("tenant_only_ordered")
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+    let tenant_only_multi = scoped_database_options(env, registry.clone())
+        .multi_bytes()
+        .name("tenant_only_multi")
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let key = "key".to_string();
+    tenant_only.put(&mut wtxn, &tenant, &key, &1)?;
+
+    let denied = |result: Result<_, ScopedDbError>| matches!(result, Err(ScopedDbError::InvalidInput(msg)) if msg == "default scope is disabled for this database");
+    assert!(denied(
+        tenant_only
+            .put(&mut wtxn, &Scope::Default, &key, &1)
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only.get(&wtxn, &Scope::Default, &key).map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only
+            .delete(&mut wtxn, &Scope::Default, &key)
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only.clear(&mut wtxn, &Scope::Default).map(|_| ())
+    ));
+    assert!(denied(tenant_only.iter(&wtxn, &Scope::Default).map(|_| ())));
+    assert!(denied(
+        tenant_only_bytes
+            .put(&mut wtxn, &Scope::Default, b"key", b"value")
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only_bytes
+            .iter_with_hash(&wtxn, &Scope::Default)
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only_counters
+            .increment(&mut wtxn, &Scope::Default, b"hits", 1)
+            .map(|_| ())
+    ));
+
+    assert!(denied(
+        tenant_only_ordered
+            .range(&wtxn, &Scope::Default, &(1..10))
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only_multi
+            .get_all(&wtxn, &Scope::Default, b"key")
+            .map(|_| ())
+    ));
+    assert!(denied(
+        tenant_only.has_data(&wtxn, &Scope::Default).map(|_| ())
+    ));
+    // Named scopes are unaffected
+    assert_eq!(tenant_only.get(&wtxn, &tenant, &key)?, Some(1));
+    tenant_only_bytes.put(&mut wtxn, &tenant, b"key", b"value")?;
+    assert_eq!(
+        tenant_only_counters.increment(&mut wtxn, &tenant, b"hits", 1)?,
+        1
+    );
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_batch_groups_writes_by_scope() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("apply_batch")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let observer = Arc::new(RecordingObserver::default());
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("apply_batch")
+        .observer(observer.clone())
+        .create(&mut wtxn)?;
+    let typed = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("apply_batch_typed")
+        .create(&mut wtxn)?;
+
+    let a = Scope::named("a")?;
+    let b = Scope::named("b")?;
+    db.put(&mut wtxn, &b, b"stale", b"x")?;
+    let ops = vec![
+        (a.clone(), BatchOp::Put(b"1".to_vec(), b"a1".to_vec())),
+        (b.clone(), BatchOp::Put(b"1".to_vec(), b"b1".to_vec())),
+        (a.clone(), BatchOp::Put(b"2".to_vec(), b"a2".to_vec())),
+        (b.clone(), BatchOp::Delete(b"stale".to_vec())),
+        (Scope::Default, BatchOp::Put(b"1".to_vec(), b"d1".to_vec())),
+        (a.clone(), BatchOp::Delete(b"2".to_vec())),
+    ];
+    observer.events.lock().unwrap().clear();
+    db.apply_batch(&mut wtxn, ops)?;
+
+    // Each scope's writes are applied together, default scope first
+    let events = observer.events.lock().unwrap().clone();
+    let scopes: Vec<&str> = events
+        .iter()
+        .map(|e| e.split(':').nth(1).unwrap())
+        .collect();
+    let mut runs = scopes.clone();
+    runs.dedup();
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0], "default");
+    assert_eq!(scopes.len(), 6);
+
+    // Operations on the same key keep their order
+    assert_eq!(db.get(&wtxn, &a, b"1")?, Some(&b"a1"[..]));
+    assert_eq!(db.get(&wtxn, &a, b"2")?, None);
+    assert_eq!(db.get(&wtxn, &b, b"1")?, Some(&b"b1"[..]));
+    assert_eq!(db.get(&wtxn, &b, b"stale")?, None);
+    assert_eq!(db.get(&wtxn, &Scope::Default, b"1")?, Some(&b"d1"[..]));
+
+    typed.apply_batch(
+        &mut wtxn,
+        vec![
+            (b.clone(), BatchOp::Put(b"k".to_vec(), 2)),
+            (a.clone(), BatchOp::Put(b"k".to_vec(), 1)),
+        ],
+    )?;
+    assert_eq!(typed.get(&wtxn, &a, b"k")?, Some(1));
+    assert_eq!(typed.get(&wtxn, &b, b"k")?, Some(2));
+    wtxn.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("iter_scopes")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, u32>()
+        .name("iter_scopes")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("iter_scopes_bytes")
+        .create(&mut wtxn)?;
+    let keyed_db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("iter_scopes_keyed")
+        .create(&mut wtxn)?;
+
+    let a = Scope::named("a")?;
+    let b = Scope::named("b")?;
+    let skipped = Scope::named("skipped")?;
+    for (scope, value) in [(&a, 1), (&b, 2), (&skipped, 3), (&Scope::Default, 4)] {
+        db.put(&mut wtxn, scope, &"k".to_string(), &value)?;
+        bytes_db.put(&mut wtxn, scope, b"k", &[value as u8])?;
+        keyed_db.put(&mut wtxn, scope, b"k", &value)?;
+    }
+    db.put(&mut wtxn, &b, &"l".to_string(), &5)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let entries: Vec<(Scope, String, u32)> = db
+        .iter_scopes(&rtxn, &[b.clone(), Scope::Default, a.clone()])?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        entries,
+        vec![
+            (b.clone(), "k".to_string(), 2),
+            (b.clone(), "l".to_string(), 5),
+            (Scope::Default, "k".to_string(), 4),
+            (a.clone(), "k".to_string(), 1),
+        ]
+    );
+
+    let bytes_entries: Vec<(Scope, &[u8], &[u8])> = bytes_db
+        .iter_scopes(&rtxn, &[a.clone(), b.clone()])?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        bytes_entries,
+        vec![
+            (a.clone(), &b"k"[..], &[1u8][..]),
+            (b.clone(), &b"k"[..], &[2u8][..])
+        ]
+    );
+
+    let keyed_values: Vec<u32> = keyed_db
+        .iter_scopes(&rtxn, &[a.clone(), a.clone()])?
+        .map(|entry| entry.map(|(_, _, value)| value))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(keyed_values, vec![1, 1]);
+    assert_eq!(db.iter_scopes(&rtxn, &[])?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_with_size() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("get_with_size")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("sized")
+        .create(&mut wtxn)?;
+    let keyed = scoped_database_options(env, registry.clone())
+        .bytes_keys::<Vec<u32>>()
+        .name("sized_keyed")
+        .create(&mut wtxn)?;
+    let tuples = scoped_database_options(env, registry.clone())
+        .tuple_keys::<(u32, u64), u64>()
+        .name("sized_tuples")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    let key = "doc".to_string();
+    db.put(&mut wtxn, &tenant, &key, &"hello".to_string())?;
+    db.put(&mut wtxn, &Scope::Default, &key, &String::new())?;
+    keyed.put(&mut wtxn, &tenant, b"list", &vec![1, 2, 3])?;
+    tuples.put(&mut wtxn, &tenant, &(1, 2), &7)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    // bincode stores a string as an 8-byte length followed by its bytes
+    assert_eq!(
+        db.get_with_size(&rtxn, &tenant, &key)?,
+        Some(("hello".to_string(), 13))
+    );
+    assert_eq!(
+        db.get_with_size(&rtxn, &Scope::Default, &key)?,
+        Some((String::new(), 8))
+    );
+    assert_eq!(
+        db.get_with_size(&rtxn, &tenant, &"missing".to_string())?,
+        None
+    );
+    assert_eq!(
+        db.get_with_size(&rtxn, &tenant, &key)?
+            .map(|(_, size)| size),
+        db.get_raw(&rtxn, &tenant, &key)?.map(<[u8]>::len)
+    );
+    assert_eq!(
+        keyed.get_with_size(&rtxn, &tenant, b"list")?,
+        Some((vec![1, 2, 3], 20))
+    );
+    assert_eq!(tuples.get_with_size(&rtxn, &tenant, &(1, 2))?, Some((7, 8)));
+
+    Ok(())
+}
+
+#[test]
+fn test_scoped_key_orders_by_hash_then_key() {
+    fn assert_tuple_order<K: Ord + Clone + std::fmt::Debug>(keys: Vec<(u32, K)>) {
+        let mut tuples = keys.clone();
+        tuples.sort();
+        let mut scoped: Vec<ScopedKey<K>> = keys
+            .into_iter()
+            .map(|(scope_hash, key)| ScopedKey { scope_hash, key })
+            .collect();
+        scoped.sort();
+        let scoped: Vec<(u32, K)> = scoped
+            .into_iter()
+            .map(|scoped| (scoped.scope_hash, scoped.key))
+            .collect();
+        assert_eq!(scoped, tuples);
+    }
+
+    assert_tuple_order(vec![(2, 1u64), (1, 300), (2, 0), (u32::MAX, 5), (1, 2)]);
+    assert_tuple_order(vec![
+        (7, "b".to_string()),
+        (3, "zz".to_string()),
+        (7, "a".to_string()),
+        (3, "".to_string()),
+    ]);
+    assert_tuple_order(vec![
+        (1, vec![2u8]),
+        (0, vec![9, 9]),
+        (1, vec![1, 255]),
+        (1, vec![]),
+    ]);
+    assert_tuple_order(vec![(5, (2u32, -1i64)), (5, (1, 10)), (4, (9, 0))]);
+
+    // The scope hash takes precedence over the key
+    let low = ScopedKey {
+        scope_hash: 1,
+        key: u64::MAX,
+    };
+    let high = ScopedKey {
+        scope_hash: 2,
+        key: 0u64,
+    };
+    assert!(low < high);
+}
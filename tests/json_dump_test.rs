@@ -0,0 +1,151 @@
+#![cfg(feature = "json")]
+
+use heed::EnvOpenOptions;
+use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_ndjson_dump_round_trip() -> Result<(), ScopedDbError> {
+    let dir = TempDir::new().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let source = scoped_database_options(&env, registry.clone())
+        .types::<String, Vec<u32>>()
+        .name("source")
+        .create(&mut wtxn)?;
+    let target = scoped_database_options(&env, registry.clone())
+        .types::<String, Vec<u32>>()
+        .name("target")
+        .unified()
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    source.put(&mut wtxn, &Scope::Default, &"a".to_string(), &vec![1])?;
+    source.put(&mut wtxn, &tenant, &"a".to_string(), &vec![2, 3])?;
+    source.put(&mut wtxn, &tenant, &"b".to_string(), &vec![])?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut dump = Vec::new();
+    assert_eq!(source.dump_ndjson(&rtxn, &mut dump)?, 3);
+    drop(rtxn);
+
+    let text = String::from_utf8(dump.clone()).unwrap();
+    assert_eq!(
+        text.lines().next(),
+        Some(r#"{"scope":null,"key":"a","value":[1]}"#)
+    );
+    assert!(text.contains(r#"{"scope":"tenant","key":"b","value":[]}"#));
+
+    // Loading into a database with a different layout restores the same entries
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(target.load_ndjson(&mut wtxn, dump.as_slice())?, 3);
+    assert_eq!(
+        target.get(&wtxn, &Scope::Default, &"a".to_string())?,
+        Some(vec![1])
+    );
+    assert_eq!(
+        target.get(&wtxn, &tenant, &"a".to_string())?,
+        Some(vec![2, 3])
+    );
+    assert_eq!(target.get(&wtxn, &tenant, &"b".to_string())?, Some(vec![]));
+
+    let err = target
+        .load_ndjson(&mut wtxn, &b"\n{\"scope\":null,\"key\":1}\n"[..])
+        .unwrap_err();
+    assert!(matches!(&err, ScopedDbError::Encoding(msg) if msg.starts_with("Line 2")));
+
+    Ok(())
+}
+
+#[test]
+fn test_ndjson_dump_skips_denied_default_scope() -> Result<(), ScopedDbError> {
+    let dir = TempDir::new().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<String, u32>()
+        .name("tenant_only")
+        .deny_default_scope()
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, &"a".to_string(), &1)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut dump = Vec::new();
+    assert_eq!(db.dump_ndjson(&rtxn, &mut dump)?, 1);
+    drop(rtxn);
+
+    // Default-scope records are rejected on load like any other default-scope write
+    let mut wtxn = env.write_txn()?;
+    let err = db
+        .load_ndjson(
+            &mut wtxn,
+            &b"{\"scope\":null,\"key\":\"a\",\"value\":1}\n"[..],
+        )
+        .unwrap_err();
+    assert!(matches!(err, ScopedDbError::InvalidInput(_)));
+
+    Ok(())
+}
+
+#[test]
+fn test_ndjson_dump_rejects_repaired_placeholder_scope() -> Result<(), ScopedDbError> {
+    let dir = TempDir::new().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<String, u32>()
+        .name("orphans")
+        .create(&mut wtxn)?;
+
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, &"a".to_string(), &1)?;
+    db.put(&mut wtxn, &tenant, &"b".to_string(), &2)?;
+    registry.unregister_scope(&mut wtxn, &tenant.hash().unwrap())?;
+    let audit = registry.repair(&mut wtxn, &[&db])?;
+    assert_eq!(audit.orphan_hashes, vec![tenant.hash().unwrap()]);
+    wtxn.commit()?;
+
+    // The placeholder name could not be loaded back into the same scope
+    let rtxn = env.read_txn()?;
+    let result = db.dump_ndjson(&rtxn, Vec::new());
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    drop(rtxn);
+
+    // Registering the real name replaces the placeholder and the data is dumped
+    let mut wtxn = env.write_txn()?;
+    registry.register_scope(&mut wtxn, &tenant)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut dump = Vec::new();
+    assert_eq!(db.dump_ndjson(&rtxn, &mut dump)?, 2);
+    let text = String::from_utf8(dump).unwrap();
+    assert!(text.contains(r#"{"scope":"tenant","key":"b","value":2}"#));
+
+    Ok(())
+}
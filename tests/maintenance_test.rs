@@ -0,0 +1,234 @@
+use heed::EnvOpenOptions;
+use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
+use std::sync::Arc;
+
+// Helper function to create a test environment
+fn setup_test_env() -> (tempfile::TempDir, heed::Env) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(temp_dir.path())
+            .unwrap()
+    };
+
+    (temp_dir, env)
+}
+
+#[test]
+fn test_compact_to_preserves_scopes() -> Result<(), ScopedDbError> {
+    let (_temp_dir, env) = setup_test_env();
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let kept = Scope::named("kept")?;
+    let dropped = Scope::named("dropped")?;
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &kept, b"key", b"value")?;
+    db.put(&mut wtxn, &Scope::Default, b"key", b"default")?;
+    for i in 0..2000u32 {
+        db.put(&mut wtxn, &dropped, &i.to_be_bytes(), &[0u8; 512])?;
+    }
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    db.clear(&mut wtxn, &dropped)?;
+    wtxn.commit()?;
+
+    let dest = tempfile::tempdir().unwrap();
+    let dest_path = dest.path().join("compacted");
+    scoped_heed::maintenance::compact_to(&env, &dest_path)?;
+
+    let source_size = std::fs::metadata(env.path().join("data.mdb"))
+        .unwrap()
+        .len();
+    let compacted_size = std::fs::metadata(dest_path.join("data.mdb")).unwrap().len();
+    assert!(compacted_size < source_size);
+
+    // Copying over an existing copy is refused
+    assert!(scoped_heed::maintenance::compact_to(&env, &dest_path).is_err());
+
+    let copy = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(&dest_path)?
+    };
+    let mut wtxn = copy.write_txn()?;
+    let copy_registry = Arc::new(GlobalScopeRegistry::new(&copy, &mut wtxn)?);
+    let copy_db = scoped_database_options(&copy, copy_registry.clone())
+        .raw_bytes()
+        .name("blobs")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let rtxn = copy.read_txn()?;
+    assert_eq!(copy_db.get(&rtxn, &kept, b"key")?, Some(&b"value"[..]));
+    assert_eq!(
+        copy_db.get(&rtxn, &Scope::Default, b"key")?,
+        Some(&b"default"[..])
+    );
+    assert!(copy_registry.scope_exists(&rtxn, &kept)?);
+    assert!(copy_registry.scope_exists(&rtxn, &dropped)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_rename_database_copies_all_scopes() -> Result<(), ScopedDbError> {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(20)
+            .open(temp_dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let users = scoped_database_options(&env, registry.clone())
+        .types::<u32, String>()
+        .name("users")
+        .create(&mut wtxn)?;
+    let tags = scoped_database_options(&env, registry.clone())
+        .multi_bytes()
+        .name("tags")
+        .create(&mut wtxn)?;
+
+    // Enough entries to span several copy batches
+    let tenant = Scope::named("tenant")?;
+    for i in 0..1500u32 {
+        users.put(&mut wtxn, &tenant, &i, &format!("user{i}"))?;
+    }
+    users.put(&mut wtxn, &Scope::Default, &7, &"root".to_string())?;
+    tags.put(&mut wtxn, &tenant, b"post", b"rust")?;
+    tags.put(&mut wtxn, &tenant, b"post", b"lmdb")?;
+    wtxn.commit()?;
+
+    assert_eq!(
+        scoped_heed::maintenance::rename_database(&env, "users", "accounts")?,
+        1501
+    );
+    assert_eq!(
+        scoped_heed::maintenance::rename_database(&env, "tags", "labels")?,
+        2
+    );
+    assert!(matches!(
+        scoped_heed::maintenance::rename_database(&env, "users", "accounts"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        scoped_heed::maintenance::rename_database(&env, "missing", "other"),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+
+    let mut wtxn = env.write_txn()?;
+    let accounts = scoped_database_options(&env, registry.clone())
+        .types::<u32, String>()
+        .name("accounts")
+        .create(&mut wtxn)?;
+    let labels = scoped_database_options(&env, registry.clone())
+        .multi_bytes()
+        .name("labels")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(accounts.iter(&rtxn, &tenant)?.count(), 1500);
+    assert_eq!(
+        accounts.get(&rtxn, &tenant, &1499)?,
+        Some("user1499".to_string())
+    );
+    assert_eq!(
+        accounts.get(&rtxn, &Scope::Default, &7)?,
+        Some("root".to_string())
+    );
+    let values: Vec<&[u8]> = labels
+        .get_all(&rtxn, &tenant, b"post")?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(values, vec![&b"lmdb"[..], &b"rust"[..]]);
+    assert!(!users.has_data(&rtxn, &tenant)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_no_sync_environment() -> Result<(), ScopedDbError> {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .flags(heed::EnvFlags::NO_SYNC)
+            .open(temp_dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("events")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &tenant, b"key", b"value")?;
+    wtxn.commit()?;
+
+    scoped_heed::maintenance::sync(&env, false)?;
+    scoped_heed::maintenance::sync(&env, true)?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.get(&rtxn, &tenant, b"key")?, Some(&b"value"[..]));
+    assert!(registry.scope_exists(&rtxn, &tenant)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_reserve_grows_map_for_bulk_load() -> Result<(), ScopedDbError> {
+    const MIB: usize = 1024 * 1024;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(MIB)
+            .max_dbs(10)
+            .open(temp_dir.path())?
+    };
+    let initial_size = env.info().map_size;
+
+    // Plenty of room left: nothing changes
+    assert_eq!(
+        unsafe { scoped_heed::maintenance::reserve(&env, 4096)? },
+        initial_size
+    );
+
+    let grown = unsafe { scoped_heed::maintenance::reserve(&env, 8 * MIB)? };
+    assert!(grown >= 8 * MIB);
+    assert_eq!(env.info().map_size, grown);
+
+    // An import that would not have fit in the original map now succeeds
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry)
+        .raw_bytes()
+        .name("import")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    let value = vec![7u8; 1024];
+    for i in 0..2048u32 {
+        db.put(&mut wtxn, &tenant, &i.to_be_bytes(), &value)?;
+    }
+    wtxn.commit()?;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use heed::EnvOpenOptions;
-use scoped_heed::{GlobalScopeRegistry, Scope, scoped_database_options};
+use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
 use std::sync::Arc;
 use tempfile::tempdir;
 
@@ -133,3 +133,98 @@ fn test_cloned_db_shares_global_registry() {
     assert_eq!(retrieved_default_orig, Some(default_val_cloned.clone()));
     rtxn_orig_default.commit().unwrap();
 }
+
+/// Scopes at the top of the hash range, where the exclusive end bound of a scope is the
+/// hardest to get right
+fn top_of_range_scopes() -> Vec<Scope> {
+    [
+        // Last before `u32::MAX` in numeric order, used by the big-endian ordered layout
+        ("below_max", u32::MAX - 1),
+        // Last before `u32::MAX` in little-endian byte order, used by the other layouts
+        ("below_max_le", 0xFEFF_FFFF),
+        ("max", u32::MAX),
+    ]
+    .into_iter()
+    .map(|(name, hash)| Scope::Named {
+        name: name.to_string(),
+        hash,
+    })
+    .collect()
+}
+
+#[test]
+fn test_scopes_at_top_of_hash_range_stay_isolated() -> Result<(), ScopedDbError> {
+    let dir = tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<char, u32>()
+        .name("typed")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("bytes")
+        .create(&mut wtxn)?;
+    let ordered = scoped_database_options(&env, registry.clone())
+        .types::<u32, u32>()
+        .ordered_keys()
+        .name("ordered")
+        .create(&mut wtxn)?;
+
+    // Each scope gets a different number of entries so a leak shows up in the counts
+    let scopes = top_of_range_scopes();
+    for (i, scope) in scopes.iter().enumerate() {
+        for n in 0..=i as u32 {
+            let key = char::from(b'a' + n as u8);
+            db.put(&mut wtxn, scope, &key, &n)?;
+            bytes_db.put(&mut wtxn, scope, &[b'a' + n as u8], b"v")?;
+            ordered.put(&mut wtxn, scope, &n, &n)?;
+        }
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for (i, scope) in scopes.iter().enumerate() {
+        let expected = i + 1;
+        assert_eq!(db.iter(&rtxn, scope)?.count(), expected, "{scope:?}");
+        assert_eq!(db.range(&rtxn, scope, &('a'..))?.count(), expected);
+        assert_eq!(db.range(&rtxn, scope, &(..='z'))?.count(), expected);
+        assert_eq!(bytes_db.iter(&rtxn, scope)?.count(), expected, "{scope:?}");
+        assert_eq!(
+            bytes_db.range(&rtxn, scope, &(&b"a"[..]..))?.count(),
+            expected
+        );
+        assert_eq!(ordered.iter(&rtxn, scope)?.count(), expected, "{scope:?}");
+        assert_eq!(ordered.range(&rtxn, scope, &(0..))?.count(), expected);
+        assert!(db.has_data(&rtxn, scope)?);
+        assert!(bytes_db.has_data(&rtxn, scope)?);
+        assert!(ordered.has_data(&rtxn, scope)?);
+    }
+    drop(rtxn);
+
+    // Clearing each scope in turn removes exactly its own entries
+    for (i, scope) in scopes.iter().enumerate() {
+        let mut wtxn = env.write_txn()?;
+        assert_eq!(db.clear(&mut wtxn, scope)?, i + 1);
+        assert_eq!(bytes_db.clear(&mut wtxn, scope)?, i + 1);
+        assert_eq!(ordered.clear(&mut wtxn, scope)?, i + 1);
+        assert!(!db.has_data(&wtxn, scope)?);
+        assert!(!bytes_db.has_data(&wtxn, scope)?);
+        assert!(!ordered.has_data(&wtxn, scope)?);
+        for (j, other) in scopes.iter().enumerate().skip(i + 1) {
+            assert_eq!(db.iter(&wtxn, other)?.count(), j + 1);
+            assert_eq!(bytes_db.iter(&wtxn, other)?.count(), j + 1);
+            assert_eq!(ordered.iter(&wtxn, other)?.count(), j + 1);
+        }
+        wtxn.commit()?;
+    }
+
+    Ok(())
+}
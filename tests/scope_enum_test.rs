@@ -497,7 +497,7 @@ fn test_range_operations_with_scope_enum() -> Result<(), ScopedDbError> {
 
     let mut wtxn = env.write_txn()?;
     let db = scoped_database_options(env, registry.clone())
-        .types::<String, String>()
+        .types::<char, String>()
         .name("test")
         .create(&mut wtxn)?;
     wtxn.commit()?;
@@ -510,56 +510,16 @@ fn test_range_operations_with_scope_enum() -> Result<(), ScopedDbError> {
         let mut wtxn = env.write_txn()?;
 
         // Default scope
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"a".to_string(),
-            &"default_a".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"b".to_string(),
-            &"default_b".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"c".to_string(),
-            &"default_c".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &Scope::Default,
-            &"d".to_string(),
-            &"default_d".to_string(),
-        )?;
+        db.put(&mut wtxn, &Scope::Default, &'a', &"default_a".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'b', &"default_b".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'c', &"default_c".to_string())?;
+        db.put(&mut wtxn, &Scope::Default, &'d', &"default_d".to_string())?;
 
         // Scope1
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"a".to_string(),
-            &"scope1_a".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"b".to_string(),
-            &"scope1_b".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"c".to_string(),
-            &"scope1_c".to_string(),
-        )?;
-        db.put(
-            &mut wtxn,
-            &scope1,
-            &"d".to_string(),
-            &"scope1_d".to_string(),
-        )?;
+        db.put(&mut wtxn, &scope1, &'a', &"scope1_a".to_string())?;
+        db.put(&mut wtxn, &scope1, &'b', &"scope1_b".to_string())?;
+        db.put(&mut wtxn, &scope1, &'c', &"scope1_c".to_string())?;
+        db.put(&mut wtxn, &scope1, &'d', &"scope1_d".to_string())?;
 
         wtxn.commit()?;
     }
@@ -567,37 +527,34 @@ fn test_range_operations_with_scope_enum() -> Result<(), ScopedDbError> {
     // Test inclusive range [b, c]
     {
         let rtxn = env.read_txn()?;
-        let mut items: Vec<(String, String)> = vec![];
+        let mut items: Vec<(char, String)> = vec![];
 
-        let range = "b".to_string()..="c".to_string();
+        let range = 'b'..='c';
         for result in db.range(&rtxn, &scope1, &range)? {
             let (key, value) = result?;
             items.push((key, value));
         }
 
         assert_eq!(items.len(), 2);
-        assert_eq!(items[0], ("b".to_string(), "scope1_b".to_string()));
-        assert_eq!(items[1], ("c".to_string(), "scope1_c".to_string()));
+        assert_eq!(items[0], ('b', "scope1_b".to_string()));
+        assert_eq!(items[1], ('c', "scope1_c".to_string()));
     }
 
     // Test exclusive range (a, d)
     {
         let rtxn = env.read_txn()?;
-        let mut items: Vec<(String, String)> = vec![];
+        let mut items: Vec<(char, String)> = vec![];
 
         use std::ops::Bound;
-        let range = (
-            Bound::Excluded("a".to_string()),
-            Bound::Excluded("d".to_string()),
-        );
+        let range = (Bound::Excluded('a'), Bound::Excluded('d'));
         for result in db.range(&rtxn, &Scope::Default, &range)? {
             let (key, value) = result?;
             items.push((key, value));
         }
 
         assert_eq!(items.len(), 2);
-        assert_eq!(items[0], ("b".to_string(), "default_b".to_string()));
-        assert_eq!(items[1], ("c".to_string(), "default_c".to_string()));
+        assert_eq!(items[0], ('b', "default_b".to_string()));
+        assert_eq!(items[1], ('c', "default_c".to_string()));
     }
 
     Ok(())
@@ -662,3 +619,53 @@ fn test_bytes_database_with_scope_enum() -> Result<(), ScopedDbError> {
 
     Ok(())
 }
+
+#[test]
+fn test_iter_with_hash() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("iter_with_hash")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("hash_iter")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<String>()
+        .name("hash_iter_bytes")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant = Scope::named("tenant")?;
+    let tenant_hash = tenant.hash();
+
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &tenant, &"a".to_string(), &"1".to_string())?;
+    db.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"b".to_string(),
+        &"2".to_string(),
+    )?;
+    bytes_db.put(&mut wtxn, &tenant, b"c", &"3".to_string())?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let named: Vec<_> = db
+        .iter_with_hash(&rtxn, &tenant)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(named, vec![(tenant_hash, "a".to_string(), "1".to_string())]);
+
+    let default: Vec<_> = db
+        .iter_with_hash(&rtxn, &Scope::Default)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(default, vec![(None, "b".to_string(), "2".to_string())]);
+
+    let bytes: Vec<_> = bytes_db
+        .iter_with_hash(&rtxn, &tenant)?
+        .collect::<Result<_, _>>()?;
+    assert_eq!(bytes, vec![(tenant_hash, &b"c"[..], "3".to_string())]);
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 //! Test suite specifically for verifying Redis-like scope isolation
 use heed::{Env, EnvOpenOptions};
-use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, scoped_database_options};
+use scoped_heed::{
+    GlobalScopeRegistry, Scope, ScopedDbError, TypedScope, TypedScoped, scoped_database_options,
+};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -349,3 +351,451 @@ fn test_scope_names_are_arbitrary_strings() -> Result<(), ScopedDbError> {
 
     Ok(())
 }
+
+#[test]
+fn test_swap_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("swap_scopes")?;
+    let env = &test_env.env;
+
+    // Create a global registry for tracking scopes
+    let mut wtxn = env.write_txn()?;
+    let global_registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    let db = scoped_database_options(env, global_registry.clone())
+        .types::<String, String>()
+        .name("swap_db")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(env, global_registry.clone())
+        .raw_bytes()
+        .name("swap_bytes_db")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let blue = Scope::named("blue")?;
+    let green = Scope::named("green")?;
+    let other = Scope::named("other")?;
+
+    // Overlapping keys in both scopes plus a key unique to each side
+    let mut wtxn = env.write_txn()?;
+    db.put(&mut wtxn, &blue, &"shared".to_string(), &"blue".to_string())?;
+    db.put(&mut wtxn, &blue, &"only_blue".to_string(), &"b".to_string())?;
+    db.put(
+        &mut wtxn,
+        &green,
+        &"shared".to_string(),
+        &"green".to_string(),
+    )?;
+    db.put(
+        &mut wtxn,
+        &green,
+        &"only_green".to_string(),
+        &"g".to_string(),
+    )?;
+    db.put(
+        &mut wtxn,
+        &other,
+        &"shared".to_string(),
+        &"other".to_string(),
+    )?;
+    bytes_db.put(&mut wtxn, &blue, b"shared", b"blue")?;
+    bytes_db.put(&mut wtxn, &green, b"shared", b"green")?;
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    db.swap_scopes(&mut wtxn, &blue, &green)?;
+    bytes_db.swap_scopes(&mut wtxn, &blue, &green)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let blue_entries: Vec<(String, String)> = db.iter(&rtxn, &blue)?.collect::<Result<_, _>>()?;
+    let green_entries: Vec<(String, String)> = db.iter(&rtxn, &green)?.collect::<Result<_, _>>()?;
+    assert_eq!(
+        blue_entries,
+        vec![
+            ("shared".to_string(), "green".to_string()),
+            ("only_green".to_string(), "g".to_string()),
+        ]
+    );
+    assert_eq!(
+        green_entries,
+        vec![
+            ("shared".to_string(), "blue".to_string()),
+            ("only_blue".to_string(), "b".to_string()),
+        ]
+    );
+
+    // Unrelated scopes are untouched
+    assert_eq!(
+        db.get(&rtxn, &other, &"shared".to_string())?,
+        Some("other".to_string())
+    );
+
+    assert_eq!(bytes_db.get(&rtxn, &blue, b"shared")?, Some(&b"green"[..]));
+    assert_eq!(bytes_db.get(&rtxn, &green, b"shared")?, Some(&b"blue"[..]));
+
+    // Both scopes stay registered
+    assert!(global_registry.scope_exists(&rtxn, &blue)?);
+    assert!(global_registry.scope_exists(&rtxn, &green)?);
+    drop(rtxn);
+
+    // The default scope cannot be swapped
+    let mut wtxn = env.write_txn()?;
+    let result = db.swap_scopes(&mut wtxn, &Scope::Default, &blue);
+    assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_delete_prefix_stays_within_scope() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("delete_prefix")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("prefix_bytes")
+        .create(&mut wtxn)?;
+    let typed_db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<u32>()
+        .name("prefix_typed")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Scopes at the top of the hash space, where an overflowing bound would spill over
+    let max_scope = Scope::Named {
+        name: "max".to_string(),
+        hash: u32::MAX,
+    };
+    let below_max_scope = Scope::Named {
+        name: "below_max".to_string(),
+        hash: u32::MAX - 1,
+    };
+    let zero_scope = Scope::Named {
+        name: "zero".to_string(),
+        hash: 0,
+    };
+
+    let keys: [&[u8]; 6] = [
+        b"users/1/a",
+        b"users/1/bb",
+        b"users/10",
+        b"users/2/a",
+        b"\xFF\xFF",
+        b"\xFF\xFF\x01",
+    ];
+    let mut wtxn = env.write_txn()?;
+    for scope in [&max_scope, &below_max_scope, &zero_scope, &Scope::Default] {
+        for key in keys {
+            db.put(&mut wtxn, scope, key, b"v")?;
+            typed_db.put(&mut wtxn, scope, key, &1)?;
+        }
+    }
+    wtxn.commit()?;
+
+    let mut wtxn = env.write_txn()?;
+    // Keys of different lengths sharing the prefix are all removed
+    assert_eq!(db.delete_prefix(&mut wtxn, &max_scope, b"users/1")?, 3);
+    assert_eq!(db.delete_prefix(&mut wtxn, &max_scope, b"\xFF\xFF")?, 2);
+    assert_eq!(db.delete_prefix(&mut wtxn, &Scope::Default, b"\xFF")?, 2);
+    assert_eq!(
+        db.delete_prefix(&mut wtxn, &Scope::Default, b"users/1/")?,
+        2
+    );
+    assert_eq!(
+        typed_db.delete_prefix_with_name(&mut wtxn, None, b"users/")?,
+        4
+    );
+    assert_eq!(typed_db.delete_prefix(&mut wtxn, &zero_scope, b"")?, 6);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let remaining = |scope: &Scope| -> Result<Vec<Vec<u8>>, ScopedDbError> {
+        db.iter(&rtxn, scope)?
+            .map(|r| r.map(|(k, _)| k.to_vec()))
+            .collect()
+    };
+    for key in keys {
+        let expected: Option<&[u8]> = (key == b"users/2/a").then_some(b"v");
+        assert_eq!(db.get(&rtxn, &max_scope, key)?, expected);
+        assert_eq!(typed_db.get(&rtxn, &max_scope, key)?, Some(1));
+    }
+    assert_eq!(remaining(&below_max_scope)?.len(), 6);
+    assert_eq!(remaining(&zero_scope)?.len(), 6);
+    assert_eq!(
+        remaining(&Scope::Default)?,
+        vec![b"users/10".to_vec(), b"users/2/a".to_vec()]
+    );
+
+    assert_eq!(typed_db.iter(&rtxn, &zero_scope)?.count(), 0);
+    assert_eq!(typed_db.iter(&rtxn, &Scope::Default)?.count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_scopes() -> Result<(), ScopedDbError> {
+    struct UserTenant;
+    struct BillingTenant;
+
+    let test_env = TestEnv::new("typed_scopes")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let users: TypedScoped<UserTenant, _> = TypedScoped::new(
+        scoped_database_options(env, registry.clone())
+            .types::<String, String>()
+            .name("users")
+            .create(&mut wtxn)?,
+    );
+    let billing: TypedScoped<BillingTenant, _> = TypedScoped::new(
+        scoped_database_options(env, registry.clone())
+            .raw_bytes()
+            .name("billing")
+            .create(&mut wtxn)?,
+    );
+    wtxn.commit()?;
+
+    let user_tenant = TypedScope::<UserTenant>::named("acme")?;
+    let billing_tenant = TypedScope::<BillingTenant>::named("acme")?;
+    assert_eq!(*user_tenant, *billing_tenant);
+
+    let mut wtxn = env.write_txn()?;
+    users.put(
+        &mut wtxn,
+        &user_tenant,
+        &"alice".to_string(),
+        &"admin".to_string(),
+    )?;
+    billing.put(&mut wtxn, &billing_tenant, b"plan", b"pro")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(
+        users.get(&rtxn, &user_tenant, &"alice".to_string())?,
+        Some("admin".to_string())
+    );
+    assert_eq!(
+        billing.get(&rtxn, &billing_tenant, b"plan")?,
+        Some(&b"pro"[..])
+    );
+    // Typed scopes deref to Scope for the untyped API
+    assert_eq!(users.inner().iter(&rtxn, &user_tenant)?.count(), 1);
+    assert_eq!(users.iter(&rtxn, &TypedScope::default_scope())?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_unified_layout() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("unified_layout")?;
+    let env = &test_env.env;
+
+    // Four logical databases plus the registry fit in max_dbs(5) only when unified
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("generic")
+        .unified()
+        .create(&mut wtxn)?;
+    let bytes_key_db = scoped_database_options(env, registry.clone())
+        .bytes_keys::<String>()
+        .name("bytes_keys")
+        .unified()
+        .create(&mut wtxn)?;
+    let raw_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("raw")
+        .unified()
+        .create(&mut wtxn)?;
+    let tuple_db = scoped_database_options(env, registry.clone())
+        .tuple_keys::<(u64, u32), String>()
+        .name("tuples")
+        .unified()
+        .create(&mut wtxn)?;
+    assert!(db.is_unified());
+    wtxn.commit()?;
+
+    // Named databases are recorded as keys of the unnamed database
+    let rtxn = env.read_txn()?;
+    let main_db = env
+        .open_database::<heed::types::Str, heed::types::DecodeIgnore>(&rtxn, None)?
+        .expect("unnamed database exists");
+    let mut names = main_db
+        .iter(&rtxn)?
+        .map(|result| result.map(|(name, ())| name.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    names.retain(|name| name != "__global_scope_metadata");
+    assert_eq!(
+        names,
+        vec![
+            "bytes_keys_unified",
+            "generic_unified",
+            "raw_unified",
+            "tuples_unified"
+        ]
+    );
+    drop(rtxn);
+
+    let tenant = Scope::named("tenant1")?;
+
+    let mut wtxn = env.write_txn()?;
+    for scope in [&Scope::Default, &tenant] {
+        let label = scope.name().unwrap_or("default");
+        db.put(&mut wtxn, scope, &"key".to_string(), &label.to_string())?;
+        bytes_key_db.put(&mut wtxn, scope, b"key", &label.to_string())?;
+        raw_db.put(&mut wtxn, scope, b"key", label.as_bytes())?;
+        tuple_db.put(&mut wtxn, scope, &(1, 2), &label.to_string())?;
+    }
+    db.put(
+        &mut wtxn,
+        &Scope::Default,
+        &"other".to_string(),
+        &"x".to_string(),
+    )?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for (scope, label) in [(&Scope::Default, "default"), (&tenant, "tenant1")] {
+        assert_eq!(
+            db.get(&rtxn, scope, &"key".to_string())?,
+            Some(label.to_string())
+        );
+        assert_eq!(
+            bytes_key_db.get(&rtxn, scope, b"key")?,
+            Some(label.to_string())
+        );
+        assert_eq!(raw_db.get(&rtxn, scope, b"key")?, Some(label.as_bytes()));
+        assert_eq!(
+            tuple_db.get(&rtxn, scope, &(1, 2))?,
+            Some(label.to_string())
+        );
+    }
+    assert_eq!(db.iter(&rtxn, &Scope::Default)?.count(), 2);
+    assert_eq!(db.iter(&rtxn, &tenant)?.count(), 1);
+    let (hash, _, _) = db.iter_with_hash(&rtxn, &Scope::Default)?.next().unwrap()?;
+    assert_eq!(hash, None);
+    assert_eq!(
+        db.per_scope_counts(&rtxn)?,
+        vec![(Scope::Default, 2), (tenant.clone(), 1)]
+    );
+    // The reserved storage scope is never registered
+    assert_eq!(db.list_scopes(&rtxn)?, vec![Scope::Default, tenant.clone()]);
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(db.clear(&mut wtxn, &Scope::Default)?, 2);
+    assert!(raw_db.delete(&mut wtxn, &Scope::Default, b"key")?);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(db.iter(&rtxn, &Scope::Default)?.count(), 0);
+    assert_eq!(
+        db.get(&rtxn, &tenant, &"key".to_string())?,
+        Some("tenant1".to_string())
+    );
+    assert_eq!(raw_db.get(&rtxn, &Scope::Default, b"key")?, None);
+    assert_eq!(raw_db.get(&rtxn, &tenant, b"key")?, Some(&b"tenant1"[..]));
+    drop(rtxn);
+
+    // Named scopes cannot use the hash reserved for the default scope
+    let reserved = Scope::Named {
+        name: "reserved".to_string(),
+        hash: scoped_heed::DEFAULT_SCOPE_HASH,
+    };
+    let mut wtxn = env.write_txn()?;
+    assert!(matches!(
+        db.put(&mut wtxn, &reserved, &"key".to_string(), &"x".to_string()),
+        Err(ScopedDbError::InvalidInput(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_keys_in_named_scopes() -> Result<(), ScopedDbError> {
+    let test_env = TestEnv::new("empty_keys")?;
+    let env = &test_env.env;
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(env, &mut wtxn)?);
+    let bytes_db = scoped_database_options(env, registry.clone())
+        .raw_bytes()
+        .name("bytes")
+        .create(&mut wtxn)?;
+    let typed_db = scoped_database_options(env, registry.clone())
+        .types::<String, String>()
+        .name("typed")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    let tenant1 = Scope::named("tenant1")?;
+    let tenant2 = Scope::named("tenant2")?;
+
+    let mut wtxn = env.write_txn()?;
+    // The separate default database has no key header, so LMDB sees a zero-length key
+    assert!(matches!(
+        bytes_db.put(&mut wtxn, &Scope::Default, b"", b"empty"),
+        Err(ScopedDbError::Heed(heed::Error::Mdb(
+            heed::MdbError::BadValSize
+        )))
+    ));
+    for scope in [&tenant1, &tenant2] {
+        bytes_db.put(&mut wtxn, scope, b"", b"empty")?;
+        bytes_db.put(&mut wtxn, scope, b"a", b"A")?;
+    }
+    // Bincode prefixes strings with their length, so typed keys are never zero-length
+    for scope in [&tenant1, &tenant2, &Scope::Default] {
+        typed_db.put(&mut wtxn, scope, &String::new(), &"empty".to_string())?;
+        typed_db.put(&mut wtxn, scope, &"a".to_string(), &"A".to_string())?;
+    }
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    for scope in [&tenant1, &tenant2] {
+        assert_eq!(bytes_db.get(&rtxn, scope, b"")?, Some(&b"empty"[..]));
+        let entries: Vec<_> = bytes_db.iter(&rtxn, scope)?.collect::<Result<_, _>>()?;
+        assert_eq!(
+            entries,
+            vec![(&b""[..], &b"empty"[..]), (&b"a"[..], &b"A"[..])]
+        );
+    }
+    for scope in [&tenant1, &tenant2, &Scope::Default] {
+        assert_eq!(
+            typed_db.get(&rtxn, scope, &String::new())?,
+            Some("empty".to_string())
+        );
+        let keys: Vec<String> = typed_db
+            .iter(&rtxn, scope)?
+            .map(|entry| entry.map(|(key, _)| key))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(keys, vec![String::new(), "a".to_string()]);
+    }
+    drop(rtxn);
+
+    // Clearing one scope removes its empty key and leaves the neighbours intact
+    let mut wtxn = env.write_txn()?;
+    assert_eq!(bytes_db.clear(&mut wtxn, &tenant1)?, 2);
+    assert_eq!(typed_db.clear(&mut wtxn, &tenant1)?, 2);
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    assert_eq!(bytes_db.get(&rtxn, &tenant1, b"")?, None);
+    assert_eq!(typed_db.get(&rtxn, &tenant1, &String::new())?, None);
+    assert_eq!(bytes_db.get(&rtxn, &tenant2, b"")?, Some(&b"empty"[..]));
+    assert_eq!(bytes_db.iter(&rtxn, &tenant2)?.count(), 2);
+    for scope in [&tenant2, &Scope::Default] {
+        assert_eq!(
+            typed_db.get(&rtxn, scope, &String::new())?,
+            Some("empty".to_string())
+        );
+        assert_eq!(typed_db.iter(&rtxn, scope)?.count(), 2);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use heed::EnvOpenOptions;
+use scoped_heed::{GlobalScopeRegistry, Scope, ScopedDbError, ScopedEnv, scoped_database_options};
+use std::sync::Arc;
+
+#[test]
+fn test_scoped_env_auto_grow_retries_full_writes() -> Result<(), ScopedDbError> {
+    const MIB: usize = 1024 * 1024;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(MIB)
+            .max_dbs(10)
+            .open(temp_dir.path())?
+    };
+    let initial_size = env.info().map_size;
+
+    // Safety: every transaction in this test goes through `scoped_env`
+    let scoped_env = unsafe { ScopedEnv::new(env).auto_grow(8.0)? };
+    assert_eq!(scoped_env.growth_factor(), Some(8.0));
+
+    let db = scoped_env.write(|wtxn| {
+        let registry = Arc::new(GlobalScopeRegistry::new(scoped_env.env(), wtxn)?);
+        scoped_database_options(scoped_env.env(), registry)
+            .raw_bytes()
+            .name("import")
+            .create(wtxn)
+    })?;
+
+    // Two values per page need far more than the original map
+    let tenant = Scope::named("tenant")?;
+    let value = vec![7u8; 1024];
+    let mut attempts = 0;
+    let written = scoped_env.write(|wtxn| {
+        attempts += 1;
+        for i in 0..2048u32 {
+            db.put(wtxn, &tenant, &i.to_be_bytes(), &value)?;
+        }
+        Ok(2048)
+    })?;
+    assert_eq!(written, 2048);
+    assert_eq!(attempts, 2);
+    assert!(scoped_env.env().info().map_size >= 8 * initial_size);
+
+    let count = scoped_env.read(|rtxn| Ok(db.iter(rtxn, &tenant)?.count()))?;
+    assert_eq!(count, 2048);
+
+    // Bad growth factors are rejected
+    for factor in [1.0, 0.5, f64::NAN, f64::INFINITY] {
+        let result = unsafe { ScopedEnv::new(scoped_env.env().clone()).auto_grow(factor) };
+        assert!(matches!(result, Err(ScopedDbError::InvalidInput(_))));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_scoped_env_without_auto_grow_reports_map_full() -> Result<(), ScopedDbError> {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(1024 * 1024)
+            .max_dbs(10)
+            .open(temp_dir.path())?
+    };
+    let scoped_env = ScopedEnv::new(env);
+    assert_eq!(scoped_env.growth_factor(), None);
+
+    let db = scoped_env.write(|wtxn| {
+        let registry = Arc::new(GlobalScopeRegistry::new(scoped_env.env(), wtxn)?);
+        scoped_database_options(scoped_env.env(), registry)
+            .raw_bytes()
+            .name("import")
+            .create(wtxn)
+    })?;
+
+    let tenant = Scope::named("tenant")?;
+    let value = vec![7u8; 1024];
+    let result = scoped_env.write(|wtxn| {
+        for i in 0..2048u32 {
+            db.put(wtxn, &tenant, &i.to_be_bytes(), &value)?;
+        }
+        Ok(())
+    });
+    assert!(matches!(
+        result,
+        Err(ScopedDbError::Heed(heed::Error::Mdb(
+            heed::MdbError::MapFull
+        )))
+    ));
+
+    // The failed transaction was aborted
+    assert!(!scoped_env.read(|rtxn| db.has_data(rtxn, &tenant))?);
+
+    Ok(())
+}
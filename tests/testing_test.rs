@@ -0,0 +1,82 @@
+#![cfg(feature = "test-util")]
+
+use scoped_heed::testing::{temp_env, temp_scoped_db};
+use scoped_heed::{
+    GlobalScopeRegistry, Scope, ScopeSnapshot, ScopedDbError, scoped_database_options,
+};
+use std::sync::Arc;
+
+#[test]
+fn test_temp_scoped_db() -> Result<(), ScopedDbError> {
+    let test_db = temp_scoped_db::<String, u32>("items")?;
+    let path = test_db.dir.path().to_path_buf();
+
+    let tenant = Scope::named("tenant")?;
+    let mut wtxn = test_db.env.write_txn()?;
+    test_db.db.put(&mut wtxn, &tenant, &"a".to_string(), &1)?;
+    wtxn.commit()?;
+
+    let rtxn = test_db.env.read_txn()?;
+    assert_eq!(test_db.db.get(&rtxn, &tenant, &"a".to_string())?, Some(1));
+    assert!(test_db.registry.scope_exists(&rtxn, &tenant)?);
+    drop(rtxn);
+
+    drop(test_db);
+    assert!(!path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_temp_env() -> Result<(), ScopedDbError> {
+    let (_dir, env) = temp_env(5)?;
+    let mut wtxn = env.write_txn()?;
+    GlobalScopeRegistry::new(&env, &mut wtxn)?;
+    wtxn.commit()?;
+    Ok(())
+}
+
+#[test]
+fn test_scope_eq_and_scope_diff() -> Result<(), ScopedDbError> {
+    let (_dir, env) = temp_env(5)?;
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry)
+        .raw_bytes()
+        .name("contents")
+        .create(&mut wtxn)?;
+    let tenant = Scope::named("tenant")?;
+    db.put(&mut wtxn, &tenant, b"long_key", b"1")?;
+    db.put(&mut wtxn, &tenant, b"b", b"2")?;
+    db.put(&mut wtxn, &Scope::Default, b"other", b"3")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let mut expected = ScopeSnapshot::from([
+        (b"long_key".to_vec(), b"1".to_vec()),
+        (b"b".to_vec(), b"2".to_vec()),
+    ]);
+    assert!(db.scope_eq(&rtxn, &tenant, &expected)?);
+    assert!(db.scope_diff(&rtxn, &tenant, &expected)?.is_empty());
+    assert!(!db.scope_eq(&rtxn, &Scope::Default, &expected)?);
+
+    expected.insert(b"b".to_vec(), b"20".to_vec());
+    expected.insert(b"missing".to_vec(), b"4".to_vec());
+    expected.remove(b"long_key".as_slice());
+    assert!(!db.scope_eq(&rtxn, &tenant, &expected)?);
+    let diff = db.scope_diff(&rtxn, &tenant, &expected)?;
+    assert_eq!(
+        diff.added,
+        ScopeSnapshot::from([(b"long_key".to_vec(), b"1".to_vec())])
+    );
+    assert_eq!(
+        diff.removed,
+        ScopeSnapshot::from([(b"missing".to_vec(), b"4".to_vec())])
+    );
+    assert_eq!(
+        diff.changed.get(b"b".as_slice()),
+        Some(&(b"20".to_vec(), b"2".to_vec()))
+    );
+
+    Ok(())
+}
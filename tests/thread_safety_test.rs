@@ -0,0 +1,78 @@
+use heed::EnvOpenOptions;
+use scoped_heed::{
+    GlobalScopeRegistry, Scope, ScopedBytesDatabase, ScopedBytesKeyDatabase, ScopedDatabase,
+    ScopedDbError, ScopedTupleKeyDatabase, scoped_database_options,
+};
+use std::sync::Arc;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_database_types_are_send_sync() {
+    assert_send_sync::<GlobalScopeRegistry>();
+    assert_send_sync::<Scope>();
+    assert_send_sync::<ScopedDatabase<String, String>>();
+    assert_send_sync::<ScopedDatabase<u64, Vec<u8>>>();
+    assert_send_sync::<ScopedBytesKeyDatabase<String>>();
+    assert_send_sync::<ScopedBytesDatabase>();
+    assert_send_sync::<ScopedTupleKeyDatabase<(u64, u32), String>>();
+    assert_send_sync::<ScopedDbError>();
+}
+
+#[test]
+fn test_share_databases_across_threads_without_arc() -> Result<(), ScopedDbError> {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024)
+            .max_dbs(10)
+            .open(temp_dir.path())?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let registry = Arc::new(GlobalScopeRegistry::new(&env, &mut wtxn)?);
+    let db = scoped_database_options(&env, registry.clone())
+        .types::<String, u32>()
+        .name("shared")
+        .create(&mut wtxn)?;
+    let bytes_db = scoped_database_options(&env, registry.clone())
+        .raw_bytes()
+        .name("shared_bytes")
+        .create(&mut wtxn)?;
+    wtxn.commit()?;
+
+    // Each thread writes to its own scope through a plain reference
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..4u32)
+            .map(|worker| {
+                let (env, db, bytes_db) = (&env, &db, &bytes_db);
+                s.spawn(move || -> Result<(), ScopedDbError> {
+                    let scope = Scope::named(&format!("worker_{}", worker))?;
+                    let mut wtxn = env.write_txn()?;
+                    db.put(&mut wtxn, &scope, &"id".to_string(), &worker)?;
+                    bytes_db.put(&mut wtxn, &scope, b"id", &worker.to_le_bytes())?;
+                    wtxn.commit()?;
+                    Ok(())
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().unwrap())
+    })?;
+
+    let rtxn = env.read_txn()?;
+    for worker in 0..4u32 {
+        let scope = Scope::named(&format!("worker_{}", worker))?;
+        assert_eq!(db.get(&rtxn, &scope, &"id".to_string())?, Some(worker));
+        assert_eq!(
+            bytes_db.get(&rtxn, &scope, b"id")?,
+            Some(&worker.to_le_bytes()[..])
+        );
+    }
+    assert_eq!(registry.list_all_scopes(&rtxn)?.len(), 5);
+
+    Ok(())
+}